@@ -0,0 +1,64 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+// Fallbacks used by the Solana CLI when its config file does not set a value
+const DEFAULT_RPC_URL: &str = "https://api.mainnet-beta.solana.com";
+const DEFAULT_KEYPAIR: &str = ".config/solana/id.json";
+const DEFAULT_CONFIG: &str = ".config/solana/cli/config.yml";
+
+// Subset of the Solana CLI config (`solana config get`) used by this tool
+#[derive(Deserialize, Default)]
+pub struct SolanaConfig {
+    pub json_rpc_url: Option<String>, // Cluster RPC endpoint
+    pub keypair_path: Option<String>, // Default signer
+}
+
+// Load the Solana CLI config from `path`, or from its default location
+// A missing default file is not an error: the CLI falls back to its defaults too
+pub fn load(path: Option<&Path>) -> Result<SolanaConfig> {
+    let (path, explicit) = match path {
+        Some(path) => (path.to_path_buf(), true),
+        None => match dirs::home_dir() {
+            Some(home) => (home.join(DEFAULT_CONFIG), false),
+            None => return Ok(SolanaConfig::default()),
+        },
+    };
+    if !explicit && !path.exists() {
+        return Ok(SolanaConfig::default());
+    }
+    let file = std::fs::File::open(&path)
+        .with_context(|| format!("cannot open Solana config {}", path.display()))?;
+    serde_yaml::from_reader(file)
+        .with_context(|| format!("cannot parse Solana config {}", path.display()))
+}
+
+impl SolanaConfig {
+    // RPC URL from the flag, else the config file, else mainnet-beta
+    // Accepts the same cluster monikers as `solana --url`
+    pub fn rpc_url(&self, flag: Option<&str>) -> String {
+        let url = flag
+            .or(self.json_rpc_url.as_deref())
+            .unwrap_or(DEFAULT_RPC_URL);
+        match url {
+            "l" | "localhost" => "http://localhost:8899",
+            "d" | "devnet" => "https://api.devnet.solana.com",
+            "t" | "testnet" => "https://api.testnet.solana.com",
+            "m" | "mainnet-beta" => DEFAULT_RPC_URL,
+            url => url,
+        }
+        .to_string()
+    }
+
+    // Keypair path from the flag, else the config file, else ~/.config/solana/id.json
+    pub fn keypair_path(&self, flag: Option<&Path>) -> PathBuf {
+        if let Some(path) = flag {
+            return path.to_path_buf();
+        }
+        if let Some(path) = &self.keypair_path {
+            return PathBuf::from(path);
+        }
+        dirs::home_dir().unwrap_or_default().join(DEFAULT_KEYPAIR)
+    }
+}
@@ -0,0 +1,837 @@
+// Command-line tool for nft-staking pool admins and stakers
+// Signs with the keypair and talks to the cluster from the Solana CLI config
+// unless overridden with --keypair / --url
+
+mod config; // Solana CLI config loading
+mod output; // JSON and table printing
+mod rpc; // Minimal JSON-RPC client
+
+use std::path::PathBuf;
+
+use anchor_spl::associated_token::spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use anchor_spl::metadata::mpl_token_metadata::{
+    accounts::Metadata,
+    types::{ProgrammableConfig, TokenStandard},
+};
+use anchor_spl::{token, token_2022};
+use anyhow::{anyhow, bail, Context, Result};
+use clap::{Args, Parser, Subcommand};
+use nft_staking_client as client;
+use serde_json::json;
+use solana_sdk::{
+    instruction::Instruction,
+    message::Message,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair, Signer},
+    transaction::Transaction,
+};
+
+use output::{Format, PendingView, PoolView, StakeView, TxView, UserView};
+use rpc::RpcClient;
+
+// Most stake records one claim transaction settles; with the reward ATA instruction
+// a treasury claim of this many stays under the 1232-byte transaction limit
+const SETTLE_PER_CLAIM: usize = 20;
+
+#[derive(Parser)]
+#[command(name = "nft-staking", version, about = "Operate nft-staking pools")]
+struct Cli {
+    /// RPC URL or moniker (localhost, devnet, testnet, mainnet-beta) [default: Solana CLI config]
+    #[arg(long, short = 'u', global = true)]
+    url: Option<String>,
+
+    /// Signer keypair file [default: Solana CLI config]
+    #[arg(long, short = 'k', global = true)]
+    keypair: Option<PathBuf>,
+
+    /// Solana CLI config file [default: ~/.config/solana/cli/config.yml]
+    #[arg(long, short = 'C', global = true)]
+    config: Option<PathBuf>,
+
+    /// Output format
+    #[arg(long, short = 'o', value_enum, default_value = "table", global = true)]
+    output: Format,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+// Identifies a pool: its config PDA is derived from the collection and pool id
+#[derive(Args)]
+struct PoolArgs {
+    /// Collection accepted by the pool
+    #[arg(long)]
+    collection: Pubkey,
+
+    /// Pool id within the collection
+    #[arg(long, default_value_t = 0)]
+    pool_id: u16,
+}
+
+impl PoolArgs {
+    fn config(&self) -> Pubkey {
+        client::config_pda(&self.collection, self.pool_id).0
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create a pool (signer must be the program's upgrade authority)
+    InitPool {
+        #[command(flatten)]
+        pool: PoolArgs,
+        /// Points earned per NFT per period
+        #[arg(long)]
+        points_per_stake: u8,
+        /// Period length in seconds
+        #[arg(long)]
+        points_period: u32,
+        /// Maximum NFTs a user can stake at once
+        #[arg(long)]
+        max_stake: u8,
+        /// Minimum stake duration in seconds
+        #[arg(long)]
+        freeze_period: u32,
+        /// Reward token base units minted per claimed point
+        #[arg(long, default_value_t = 1)]
+        reward_per_point: u64,
+        /// Points per second split among all staked NFTs, instead of flat per-NFT points
+        #[arg(long, default_value_t = 0)]
+        emission_per_second: u64,
+        /// Most reward tokens claims can ever mint, in base units (0: uncapped)
+        #[arg(long, default_value_t = 0)]
+        max_supply: u64,
+        /// Most reward tokens claims can mint per day, in base units (0: unlimited)
+        #[arg(long, default_value_t = 0)]
+        daily_mint_limit: u64,
+        /// Hold staked NFTs in a vault instead of freezing them in place
+        #[arg(long)]
+        custody: bool,
+        /// Create the reward mint under Token-2022 instead of SPL Token
+        #[arg(long)]
+        token_2022: bool,
+    },
+    /// Change pool parameters (signer must be the pool admin)
+    UpdatePool {
+        #[command(flatten)]
+        pool: PoolArgs,
+        /// Points earned per NFT per period; also re-rates unsettled time on flat pools
+        #[arg(long)]
+        points_per_stake: Option<u8>,
+        /// Period length in seconds; also re-rates unsettled time on flat pools
+        #[arg(long)]
+        points_period: Option<u32>,
+        /// Maximum NFTs a user can stake at once
+        #[arg(long)]
+        max_stake: Option<u8>,
+        /// Minimum stake duration in seconds
+        #[arg(long)]
+        freeze_period: Option<u32>,
+        /// Reward token base units minted per claimed point
+        #[arg(long)]
+        reward_per_point: Option<u64>,
+        /// Points per second split among all staked NFTs (emission pools only)
+        #[arg(long)]
+        emission_per_second: Option<u64>,
+        /// Most reward tokens claims can ever mint, in base units (0: uncapped)
+        #[arg(long)]
+        max_supply: Option<u64>,
+        /// Most reward tokens claims can mint per day, in base units (0: unlimited)
+        #[arg(long)]
+        daily_mint_limit: Option<u64>,
+    },
+    /// Replace an emission pool's start/end window and rate changes (signer must be the pool admin)
+    SetSchedule {
+        #[command(flatten)]
+        pool: PoolArgs,
+        /// Unix time emission begins
+        #[arg(long)]
+        start_time: i64,
+        /// Unix time emission stops [default: never]
+        #[arg(long)]
+        end_time: Option<i64>,
+        /// Rate change as UNIX_TIME:POINTS_PER_SECOND, in increasing time order (repeatable)
+        #[arg(long = "segment", value_parser = parse_segment)]
+        segments: Vec<client::EmissionSegment>,
+    },
+    /// Replace the lock tiers offered to new stakes (signer must be the pool admin)
+    SetLockTiers {
+        #[command(flatten)]
+        pool: PoolArgs,
+        /// Tier as LOCK_SECONDS:MULTIPLIER_BPS (10000 = 1x), becoming tiers 1, 2, ... (repeatable)
+        #[arg(long = "tier", value_parser = parse_tier)]
+        tiers: Vec<client::LockTier>,
+    },
+    /// Pay claims from a treasury of an existing token instead of minting (signer must be the pool admin)
+    InitTreasury {
+        #[command(flatten)]
+        pool: PoolArgs,
+        /// Token mint the treasury pays out
+        #[arg(long)]
+        mint: Pubkey,
+    },
+    /// Deposit tokens from the signer's ATA into the pool treasury
+    FundTreasury {
+        #[command(flatten)]
+        pool: PoolArgs,
+        /// Amount in base units
+        #[arg(long)]
+        amount: u64,
+    },
+    /// Create the signer's user account in a pool
+    InitUser {
+        #[command(flatten)]
+        pool: PoolArgs,
+    },
+    /// Stake an NFT held by the signer (creates the user account if needed)
+    ///
+    /// Legacy and programmable NFTs, Token-2022 NFTs and Core assets are frozen in place,
+    /// or moved into the vault on custody pools; compressed NFTs are not supported
+    Stake {
+        #[command(flatten)]
+        pool: PoolArgs,
+        /// NFT mint or Core asset
+        #[arg(long)]
+        mint: Pubkey,
+        /// Lock tier (0: no lock beyond the freeze period; see `show pool`)
+        #[arg(long, default_value_t = 0)]
+        tier: u8,
+    },
+    /// Unstake an NFT after its freeze period and tier lock
+    Unstake {
+        #[command(flatten)]
+        pool: PoolArgs,
+        /// NFT mint or Core asset
+        #[arg(long)]
+        mint: Pubkey,
+    },
+    /// Turn the signer's points into reward tokens, settling every NFT they have staked
+    /// (minted, or paid from the treasury on treasury pools)
+    ///
+    /// Past 20 staked NFTs the settling is split across several claim transactions
+    Claim {
+        #[command(flatten)]
+        pool: PoolArgs,
+    },
+    /// Print on-chain accounts
+    #[command(subcommand)]
+    Show(Show),
+}
+
+#[derive(Subcommand)]
+enum Show {
+    /// The pool's StakeConfig
+    Pool {
+        #[command(flatten)]
+        pool: PoolArgs,
+    },
+    /// A wallet's UserAccount in the pool
+    User {
+        #[command(flatten)]
+        pool: PoolArgs,
+        /// Wallet to inspect [default: signer]
+        #[arg(long)]
+        wallet: Option<Pubkey>,
+    },
+    /// Points a wallet would receive if it claimed now, simulated on-chain
+    Pending {
+        #[command(flatten)]
+        pool: PoolArgs,
+        /// Wallet to inspect [default: signer]
+        #[arg(long)]
+        wallet: Option<Pubkey>,
+        /// Only include this staked NFT [default: every NFT the wallet has staked]
+        #[arg(long)]
+        mint: Option<Pubkey>,
+    },
+    /// Every StakeAccount a wallet holds in the pool
+    Stakes {
+        #[command(flatten)]
+        pool: PoolArgs,
+        /// Wallet to inspect [default: signer]
+        #[arg(long)]
+        wallet: Option<Pubkey>,
+    },
+}
+
+// Parse a `--segment` value of the form UNIX_TIME:POINTS_PER_SECOND
+fn parse_segment(value: &str) -> Result<client::EmissionSegment> {
+    let (start_ts, rate) = value
+        .split_once(':')
+        .ok_or_else(|| anyhow!("expected UNIX_TIME:POINTS_PER_SECOND, got {value}"))?;
+    Ok(client::EmissionSegment {
+        start_ts: start_ts.parse().context("invalid segment start time")?,
+        rate: rate.parse().context("invalid segment rate")?,
+    })
+}
+
+// Parse a `--tier` value of the form LOCK_SECONDS:MULTIPLIER_BPS
+fn parse_tier(value: &str) -> Result<client::LockTier> {
+    let (lock_period, multiplier_bps) = value
+        .split_once(':')
+        .ok_or_else(|| anyhow!("expected LOCK_SECONDS:MULTIPLIER_BPS, got {value}"))?;
+    Ok(client::LockTier {
+        lock_period: lock_period.parse().context("invalid tier lock period")?,
+        multiplier_bps: multiplier_bps.parse().context("invalid tier multiplier")?,
+    })
+}
+
+// Stake and unstake instructions that fit a pool and an NFT
+#[derive(Debug, PartialEq)]
+enum StakePath {
+    Legacy,               // SPL Token NFT frozen in place
+    Pnft(Option<Pubkey>), // Programmable NFT locked in place, with its rule set if any
+    Token22,              // Token-2022 NFT frozen by the program's freeze PDA
+    Core,                 // Core asset frozen with a FreezeDelegate plugin
+    Vault(Pubkey),        // Custody pool: moved into the vault under this token program
+}
+
+// Pick the stake path from the pool's custody mode, the program owning the NFT's
+// mint (or Core asset) and, for SPL Token mints, its Metaplex metadata
+fn stake_path(custody: bool, owner: &Pubkey, metadata: Option<&Metadata>) -> Result<StakePath> {
+    let programmable = metadata.is_some_and(|metadata| {
+        matches!(
+            metadata.token_standard,
+            Some(TokenStandard::ProgrammableNonFungible)
+        )
+    });
+    Ok(match *owner {
+        token::ID if programmable && custody => {
+            bail!("programmable NFTs cannot be moved into a custody pool's vault")
+        }
+        token::ID if programmable => {
+            let rule_set = match metadata.and_then(|metadata| metadata.programmable_config.as_ref())
+            {
+                Some(ProgrammableConfig::V1 { rule_set }) => *rule_set,
+                None => None,
+            };
+            StakePath::Pnft(rule_set)
+        }
+        token::ID | token_2022::ID if custody => StakePath::Vault(*owner),
+        token::ID => StakePath::Legacy,
+        token_2022::ID => StakePath::Token22,
+        mpl_core::ID if custody => bail!("Core assets cannot be moved into a custody pool's vault"),
+        mpl_core::ID => StakePath::Core,
+        _ => bail!("owned by {owner}, not SPL Token, Token-2022 or Metaplex Core"),
+    })
+}
+
+// Resolved connection settings shared by every command
+struct Session {
+    rpc: RpcClient,
+    keypair_path: PathBuf,
+    format: Format,
+}
+
+impl Session {
+    fn signer(&self) -> Result<Keypair> {
+        read_keypair_file(&self.keypair_path)
+            .map_err(|e| anyhow!("cannot read keypair {}: {e}", self.keypair_path.display()))
+    }
+
+    // The given wallet, or the signer's address
+    fn wallet(&self, wallet: Option<Pubkey>) -> Result<Pubkey> {
+        match wallet {
+            Some(wallet) => Ok(wallet),
+            None => Ok(self.signer()?.pubkey()),
+        }
+    }
+
+    // Sign `instructions` with the signer as fee payer and send them
+    fn submit(&self, signer: &Keypair, instructions: &[Instruction]) -> Result<TxView> {
+        let blockhash = self.rpc.get_latest_blockhash()?;
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&signer.pubkey()),
+            &[signer],
+            blockhash,
+        );
+        let signature = self.rpc.send_and_confirm(&transaction)?;
+        Ok(TxView {
+            signature: signature.to_string(),
+        })
+    }
+
+    // Send `instructions` in one transaction and print its signature
+    fn send(&self, signer: &Keypair, instructions: &[Instruction]) -> Result<()> {
+        output::print_one(self.format, &self.submit(signer, instructions)?)
+    }
+
+    // Simulate `get_pending_rewards` for `wallet` over `stakes`
+    fn pending(
+        &self,
+        wallet: &Pubkey,
+        config: &Pubkey,
+        stakes: &[Pubkey],
+    ) -> Result<client::PendingRewards> {
+        // The wallet pays for the simulation only; nothing is signed or sent
+        let ix = client::get_pending_rewards(wallet, config, stakes);
+        let transaction = Transaction::new_unsigned(Message::new(&[ix], Some(wallet)));
+        Ok(client::decode_pending_rewards(
+            &self.rpc.simulate(&transaction)?,
+        )?)
+    }
+
+    // Fetch and decode a pool config
+    fn pool(&self, config: &Pubkey) -> Result<client::StakeConfig> {
+        let account = self
+            .rpc
+            .get_account(config)?
+            .ok_or_else(|| anyhow!("pool {config} does not exist"))?;
+        Ok(client::decode_stake_config(&account.data)?)
+    }
+
+    // Owner of `mint`: SPL Token or Token-2022
+    fn token_program(&self, mint: &Pubkey) -> Result<Pubkey> {
+        Ok(self
+            .rpc
+            .get_account(mint)?
+            .ok_or_else(|| anyhow!("mint {mint} does not exist"))?
+            .owner)
+    }
+
+    // Stake path for `mint` in a pool with the given custody mode
+    fn stake_path(&self, custody: bool, mint: &Pubkey) -> Result<StakePath> {
+        // Compressed NFTs have no account: staking one needs its leaf and proof from the DAS API
+        let account = self.rpc.get_account(mint)?.ok_or_else(|| {
+            anyhow!("{mint} has no account (compressed NFTs are not supported by this CLI)")
+        })?;
+        let metadata = match account.owner {
+            token::ID => self
+                .rpc
+                .get_account(&client::metadata_pda(mint).0)?
+                .map(|account| Metadata::safe_deserialize(&account.data))
+                .transpose()
+                .with_context(|| format!("cannot decode the metadata of {mint}"))?,
+            _ => None,
+        };
+        stake_path(custody, &account.owner, metadata.as_ref()).with_context(|| format!("{mint}"))
+    }
+
+    // Every stake account `wallet` holds in the pool, with its address
+    fn stakes(
+        &self,
+        config: &Pubkey,
+        wallet: &Pubkey,
+    ) -> Result<Vec<(Pubkey, client::StakeAccount)>> {
+        let filters = json!([
+            { "dataSize": client::STAKE_ACCOUNT_SIZE },
+            { "memcmp": { "offset": client::STAKE_ACCOUNT_OWNER_OFFSET, "bytes": wallet.to_string() } },
+        ]);
+        let mut stakes = Vec::new();
+        for (address, account) in self.rpc.get_program_accounts(&client::ID, filters)? {
+            let stake = client::decode_stake_account(&account.data)?;
+            // Stake accounts do not store their pool; keep those whose PDA is this pool's
+            if client::stake_account_pda(&stake.mint, config).0 == address {
+                stakes.push((address, stake));
+            }
+        }
+        Ok(stakes)
+    }
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let solana_config = config::load(cli.config.as_deref())?;
+    let ctx = Session {
+        rpc: RpcClient::new(solana_config.rpc_url(cli.url.as_deref())),
+        keypair_path: solana_config.keypair_path(cli.keypair.as_deref()),
+        format: cli.output,
+    };
+
+    match cli.command {
+        Command::InitPool {
+            pool,
+            points_per_stake,
+            points_period,
+            max_stake,
+            freeze_period,
+            reward_per_point,
+            emission_per_second,
+            max_supply,
+            daily_mint_limit,
+            custody,
+            token_2022,
+        } => {
+            let signer = ctx.signer()?;
+            let token_program = if token_2022 {
+                token_2022::ID
+            } else {
+                token::ID
+            };
+            let params = client::ConfigParams {
+                points_per_stake,
+                points_period,
+                max_stake,
+                freeze_period,
+                reward_per_point,
+                emission_per_second,
+                max_supply,
+                daily_mint_limit,
+                custody,
+            };
+            let ix = client::initialize_config(
+                &signer.pubkey(),
+                &pool.collection,
+                pool.pool_id,
+                params,
+                &token_program,
+            );
+            ctx.send(&signer, &[ix])
+        }
+        Command::UpdatePool {
+            pool,
+            points_per_stake,
+            points_period,
+            max_stake,
+            freeze_period,
+            reward_per_point,
+            emission_per_second,
+            max_supply,
+            daily_mint_limit,
+        } => {
+            let signer = ctx.signer()?;
+            let params = client::UpdateConfigParams {
+                points_per_stake,
+                points_period,
+                max_stake,
+                freeze_period,
+                reward_per_point,
+                emission_per_second,
+                max_supply,
+                daily_mint_limit,
+            };
+            let ix = client::update_config(&signer.pubkey(), &pool.config(), params);
+            ctx.send(&signer, &[ix])
+        }
+        Command::SetSchedule {
+            pool,
+            start_time,
+            end_time,
+            segments,
+        } => {
+            let signer = ctx.signer()?;
+            let params = client::ScheduleParams {
+                start_time,
+                end_time,
+                segments,
+            };
+            let ix = client::set_emission_schedule(&signer.pubkey(), &pool.config(), params);
+            ctx.send(&signer, &[ix])
+        }
+        Command::SetLockTiers { pool, tiers } => {
+            let signer = ctx.signer()?;
+            let ix = client::set_lock_tiers(&signer.pubkey(), &pool.config(), tiers);
+            ctx.send(&signer, &[ix])
+        }
+        Command::InitTreasury { pool, mint } => {
+            let signer = ctx.signer()?;
+            let token_program = ctx.token_program(&mint)?;
+            let ix = client::initialize_treasury(
+                &signer.pubkey(),
+                &pool.config(),
+                &mint,
+                &token_program,
+            );
+            ctx.send(&signer, &[ix])
+        }
+        Command::FundTreasury { pool, amount } => {
+            let signer = ctx.signer()?;
+            let config = pool.config();
+            let mint = ctx
+                .pool(&config)?
+                .treasury_mint
+                .ok_or_else(|| anyhow!("pool {config} mints its rewards and has no treasury"))?;
+            let token_program = ctx.token_program(&mint)?;
+            let ix =
+                client::fund_treasury(&signer.pubkey(), &config, &mint, &token_program, amount);
+            ctx.send(&signer, &[ix])
+        }
+        Command::InitUser { pool } => {
+            let signer = ctx.signer()?;
+            let ix = client::initialize_user(&signer.pubkey(), &pool.config());
+            ctx.send(&signer, &[ix])
+        }
+        Command::Stake { pool, mint, tier } => {
+            let signer = ctx.signer()?;
+            let user = signer.pubkey();
+            let config = pool.config();
+            let stake_config = ctx.pool(&config)?;
+            let mut ixs = Vec::new();
+            // First stake in a pool also creates the user account
+            let user_account = client::user_account_pda(&config, &user).0;
+            if ctx.rpc.get_account(&user_account)?.is_none() {
+                ixs.push(client::initialize_user(&user, &config));
+            }
+            ixs.push(match ctx.stake_path(stake_config.custody, &mint)? {
+                StakePath::Legacy => client::stake(&user, &config, &mint, tier),
+                StakePath::Pnft(rule_set) => {
+                    client::stake_pnft(&user, &config, &mint, rule_set.as_ref(), tier)
+                }
+                StakePath::Token22 => client::stake_token22(&user, &config, &mint, tier),
+                StakePath::Core => {
+                    client::stake_core(&user, &config, &mint, &stake_config.collection, tier)
+                }
+                StakePath::Vault(token_program) => {
+                    client::stake_vault(&user, &config, &mint, &token_program, tier)
+                }
+            });
+            ctx.send(&signer, &ixs)
+        }
+        Command::Unstake { pool, mint } => {
+            let signer = ctx.signer()?;
+            let user = signer.pubkey();
+            let config = pool.config();
+            let stake_config = ctx.pool(&config)?;
+            let ix = match ctx.stake_path(stake_config.custody, &mint)? {
+                StakePath::Legacy => client::unstake(&user, &config, &mint),
+                StakePath::Pnft(rule_set) => {
+                    client::unstake_pnft(&user, &config, &mint, rule_set.as_ref())
+                }
+                StakePath::Token22 => client::unstake_token22(&user, &config, &mint),
+                StakePath::Core => {
+                    client::unstake_core(&user, &config, &mint, &stake_config.collection)
+                }
+                StakePath::Vault(token_program) => {
+                    client::unstake_vault(&user, &config, &mint, &token_program)
+                }
+            };
+            ctx.send(&signer, &[ix])
+        }
+        Command::Claim { pool } => {
+            let signer = ctx.signer()?;
+            let user = signer.pubkey();
+            let config = pool.config();
+
+            // Treasury pools pay in their treasury token, others mint the pool's reward token
+            let treasury_mint = ctx.pool(&config)?.treasury_mint;
+            let reward_mint = treasury_mint.unwrap_or(client::rewards_mint_pda(&config).0);
+            // Reward mint may live under SPL Token or Token-2022
+            let token_program = ctx.token_program(&reward_mint)?;
+
+            // Settle every NFT still staked so its accrued points are claimed too
+            let settle: Vec<Pubkey> = ctx
+                .stakes(&config, &user)?
+                .into_iter()
+                .map(|(_, stake)| stake.mint)
+                .collect();
+
+            // One claim per batch of stakes; batches that accrued nothing are skipped
+            // because a claim with no points fails, but one claim always goes out
+            let mut batches = Vec::new();
+            for batch in settle.chunks(SETTLE_PER_CLAIM) {
+                if ctx.pending(&user, &config, batch)?.pending > 0 {
+                    batches.push(batch);
+                }
+            }
+            if batches.is_empty() {
+                batches.push(&[]);
+            }
+
+            let mut sent = Vec::new();
+            let mut settled = 0;
+            for batch in batches {
+                let mut ixs = Vec::new();
+                if sent.is_empty() {
+                    ixs.push(create_associated_token_account_idempotent(
+                        &user,
+                        &user,
+                        &reward_mint,
+                        &token_program,
+                    ));
+                }
+                ixs.push(match treasury_mint {
+                    Some(mint) => {
+                        client::claim_treasury(&user, &config, &mint, &token_program, batch)
+                    }
+                    None => client::claim(&user, &config, &token_program, batch),
+                });
+                // Earlier claims stand; the stakes not yet settled keep their points
+                let tx = ctx.submit(&signer, &ixs).with_context(|| {
+                    format!(
+                        "claimed {} times, settling {settled} of {} staked NFTs",
+                        sent.len(),
+                        settle.len()
+                    )
+                })?;
+                sent.push(tx);
+                settled += batch.len();
+            }
+            output::print_many(ctx.format, &sent)
+        }
+        Command::Show(Show::Pool { pool }) => {
+            let config = pool.config();
+            let stake_config = ctx.pool(&config)?;
+            output::print_one(ctx.format, &PoolView::new(&config, &stake_config))
+        }
+        Command::Show(Show::User { pool, wallet }) => {
+            let wallet = ctx.wallet(wallet)?;
+            let address = client::user_account_pda(&pool.config(), &wallet).0;
+            let account = ctx
+                .rpc
+                .get_account(&address)?
+                .with_context(|| format!("{wallet} has no user account in this pool"))?;
+            let user = client::decode_user_account(&account.data)?;
+            output::print_one(ctx.format, &UserView::new(&address, &wallet, &user))
+        }
+        Command::Show(Show::Pending { pool, wallet, mint }) => {
+            let wallet = ctx.wallet(wallet)?;
+            let config = pool.config();
+            let stakes = match mint {
+                Some(mint) => vec![mint],
+                None => ctx
+                    .stakes(&config, &wallet)?
+                    .into_iter()
+                    .map(|(_, stake)| stake.mint)
+                    .collect(),
+            };
+            let rewards = ctx.pending(&wallet, &config, &stakes)?;
+            output::print_one(ctx.format, &PendingView::new(&wallet, &rewards))
+        }
+        Command::Show(Show::Stakes { pool, wallet }) => {
+            let wallet = ctx.wallet(wallet)?;
+            let stakes: Vec<StakeView> = ctx
+                .stakes(&pool.config(), &wallet)?
+                .iter()
+                .map(|(address, stake)| StakeView::new(address, stake))
+                .collect();
+            output::print_many(ctx.format, &stakes)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn cli_definition_is_valid() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn pool_args_derive_config_pda() {
+        let collection = Pubkey::new_unique();
+        let cli = Cli::try_parse_from([
+            "nft-staking",
+            "show",
+            "pool",
+            "--collection",
+            &collection.to_string(),
+            "--pool-id",
+            "7",
+        ])
+        .unwrap();
+        let Command::Show(Show::Pool { pool }) = cli.command else {
+            panic!("expected show pool");
+        };
+        assert_eq!(pool.config(), client::config_pda(&collection, 7).0);
+    }
+
+    fn metadata(token_standard: TokenStandard, rule_set: Option<Pubkey>) -> Metadata {
+        Metadata {
+            key: anchor_spl::metadata::mpl_token_metadata::types::Key::MetadataV1,
+            update_authority: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            name: String::new(),
+            symbol: String::new(),
+            uri: String::new(),
+            seller_fee_basis_points: 0,
+            creators: None,
+            primary_sale_happened: false,
+            is_mutable: true,
+            edition_nonce: None,
+            token_standard: Some(token_standard),
+            collection: None,
+            uses: None,
+            collection_details: None,
+            programmable_config: rule_set.map(|rule_set| ProgrammableConfig::V1 {
+                rule_set: Some(rule_set),
+            }),
+        }
+    }
+
+    #[test]
+    fn stake_path_freezes_in_place_or_vaults_by_custody_mode() {
+        let nft = metadata(TokenStandard::NonFungible, None);
+        assert_eq!(
+            stake_path(false, &token::ID, Some(&nft)).unwrap(),
+            StakePath::Legacy
+        );
+        assert_eq!(
+            stake_path(true, &token::ID, Some(&nft)).unwrap(),
+            StakePath::Vault(token::ID)
+        );
+        assert_eq!(
+            stake_path(false, &token_2022::ID, None).unwrap(),
+            StakePath::Token22
+        );
+        assert_eq!(
+            stake_path(true, &token_2022::ID, None).unwrap(),
+            StakePath::Vault(token_2022::ID)
+        );
+    }
+
+    #[test]
+    fn stake_path_locks_pnfts_with_their_rule_set() {
+        let rule_set = Pubkey::new_unique();
+        let pnft = metadata(TokenStandard::ProgrammableNonFungible, Some(rule_set));
+        assert_eq!(
+            stake_path(false, &token::ID, Some(&pnft)).unwrap(),
+            StakePath::Pnft(Some(rule_set))
+        );
+        let pnft = metadata(TokenStandard::ProgrammableNonFungible, None);
+        assert_eq!(
+            stake_path(false, &token::ID, Some(&pnft)).unwrap(),
+            StakePath::Pnft(None)
+        );
+        assert!(stake_path(true, &token::ID, Some(&pnft)).is_err()); // Frozen pNFTs cannot move
+    }
+
+    #[test]
+    fn stake_path_freezes_core_assets_outside_custody_pools_only() {
+        assert_eq!(
+            stake_path(false, &mpl_core::ID, None).unwrap(),
+            StakePath::Core
+        );
+        assert!(stake_path(true, &mpl_core::ID, None).is_err());
+        assert!(stake_path(false, &Pubkey::new_unique(), None).is_err());
+    }
+
+    #[test]
+    fn claim_batches_fit_in_one_transaction() {
+        let user = Pubkey::new_unique();
+        let config = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let settle: Vec<Pubkey> = (0..SETTLE_PER_CLAIM)
+            .map(|_| Pubkey::new_unique())
+            .collect();
+        // Treasury claims carry the most accounts, and the first batch also creates the ATA
+        let ixs = [
+            create_associated_token_account_idempotent(&user, &user, &mint, &token_2022::ID),
+            client::claim_treasury(&user, &config, &mint, &token_2022::ID, &settle),
+        ];
+        let transaction = Transaction::new_with_payer(&ixs, Some(&user));
+        let size = bincode::serialized_size(&transaction).unwrap() as usize;
+        assert!(size <= solana_sdk::packet::PACKET_DATA_SIZE, "{size} bytes");
+    }
+
+    #[test]
+    fn parse_segment_reads_start_and_rate() {
+        let segment = parse_segment("1767225600:5").unwrap();
+        assert_eq!(segment.start_ts, 1_767_225_600);
+        assert_eq!(segment.rate, 5);
+        assert!(parse_segment("1767225600").is_err());
+        assert!(parse_segment("soon:5").is_err());
+    }
+
+    #[test]
+    fn parse_tier_reads_lock_and_multiplier() {
+        let tier = parse_tier("2592000:15000").unwrap();
+        assert_eq!(tier.lock_period, 2_592_000);
+        assert_eq!(tier.multiplier_bps, 15_000);
+        assert!(parse_tier("2592000").is_err());
+        assert!(parse_tier("2592000:70000").is_err()); // Multiplier does not fit in u16
+    }
+}
@@ -0,0 +1,319 @@
+use anyhow::Result;
+use clap::ValueEnum;
+use comfy_table::{presets::UTF8_FULL, Table};
+use nft_staking_client::{PendingRewards, StakeAccount, StakeConfig, UserAccount};
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+// How results are printed
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Format {
+    Table, // Human-readable tables
+    Json,  // Machine-readable JSON
+}
+
+// A result printable either as JSON or as table rows
+pub trait Record: Serialize {
+    // (column, value) pairs in display order
+    fn fields(&self) -> Vec<(&'static str, String)>;
+}
+
+// Print one record: JSON object, or a two-column field/value table
+pub fn print_one<R: Record>(format: Format, record: &R) -> Result<()> {
+    match format {
+        Format::Json => println!("{}", serde_json::to_string_pretty(record)?),
+        Format::Table => {
+            let mut table = Table::new();
+            table.load_preset(UTF8_FULL).set_header(["field", "value"]);
+            for (field, value) in record.fields() {
+                table.add_row([field.to_string(), value]);
+            }
+            println!("{table}");
+        }
+    }
+    Ok(())
+}
+
+// Print a list of records: JSON array, or a table with one row per record
+pub fn print_many<R: Record>(format: Format, records: &[R]) -> Result<()> {
+    match format {
+        Format::Json => println!("{}", serde_json::to_string_pretty(records)?),
+        Format::Table => {
+            let mut table = Table::new();
+            table.load_preset(UTF8_FULL);
+            if let Some(first) = records.first() {
+                table.set_header(first.fields().into_iter().map(|(field, _)| field));
+            }
+            for record in records {
+                table.add_row(record.fields().into_iter().map(|(_, value)| value));
+            }
+            println!("{table}");
+        }
+    }
+    Ok(())
+}
+
+// A sent transaction
+#[derive(Serialize)]
+pub struct TxView {
+    pub signature: String,
+}
+
+impl Record for TxView {
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        vec![("signature", self.signature.clone())]
+    }
+}
+
+// A pool's `StakeConfig`
+#[derive(Serialize)]
+pub struct PoolView {
+    pub address: String,
+    pub collection: String,
+    pub pool_id: u16,
+    pub admin: String,
+    pub pending_admin: Option<String>,
+    pub points_per_stake: u8,
+    pub points_period: u32,
+    pub max_stake: u8,
+    pub freeze_period: u32,
+    pub reward_per_point: u64,
+    pub treasury_mint: Option<String>,
+    pub emission_per_second: u64,
+    pub acc_reward_per_nft: u128,
+    pub last_reward_time: i64,
+    pub total_staked: u64,
+    pub total_weight: u64,
+    pub start_time: i64,
+    pub end_time: Option<i64>,
+    pub schedule: Vec<(i64, u64)>,
+    pub max_supply: u64,
+    pub daily_mint_limit: u64,
+    pub total_minted: u64,
+    pub mint_window_start: i64,
+    pub window_minted: u64,
+    pub lock_tiers: Vec<(u32, u16)>,
+    pub paused: u8,
+    pub emergency: bool,
+    pub custody: bool,
+}
+
+impl PoolView {
+    pub fn new(address: &Pubkey, config: &StakeConfig) -> Self {
+        Self {
+            address: address.to_string(),
+            collection: config.collection.to_string(),
+            pool_id: config.pool_id,
+            admin: config.admin.to_string(),
+            pending_admin: config.pending_admin.map(|admin| admin.to_string()),
+            points_per_stake: config.points_per_stake,
+            points_period: config.points_period,
+            max_stake: config.max_stake,
+            freeze_period: config.freeze_period,
+            reward_per_point: config.reward_per_point,
+            treasury_mint: config.treasury_mint.map(|mint| mint.to_string()),
+            emission_per_second: config.emission_per_second,
+            acc_reward_per_nft: config.acc_reward_per_nft,
+            last_reward_time: config.last_reward_time,
+            total_staked: config.total_staked,
+            total_weight: config.total_weight,
+            start_time: config.start_time,
+            end_time: config.end_time,
+            schedule: config
+                .schedule
+                .iter()
+                .map(|segment| (segment.start_ts, segment.rate))
+                .collect(),
+            max_supply: config.max_supply,
+            daily_mint_limit: config.daily_mint_limit,
+            total_minted: config.total_minted,
+            mint_window_start: config.mint_window_start,
+            window_minted: config.window_minted,
+            lock_tiers: config
+                .lock_tiers
+                .iter()
+                .map(|tier| (tier.lock_period, tier.multiplier_bps))
+                .collect(),
+            paused: config.paused,
+            emergency: config.emergency,
+            custody: config.custody,
+        }
+    }
+}
+
+impl Record for PoolView {
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("address", self.address.clone()),
+            ("collection", self.collection.clone()),
+            ("pool_id", self.pool_id.to_string()),
+            ("admin", self.admin.clone()),
+            (
+                "pending_admin",
+                self.pending_admin.clone().unwrap_or_else(|| "-".into()),
+            ),
+            ("points_per_stake", self.points_per_stake.to_string()),
+            ("points_period", self.points_period.to_string()),
+            ("max_stake", self.max_stake.to_string()),
+            ("freeze_period", self.freeze_period.to_string()),
+            ("reward_per_point", self.reward_per_point.to_string()),
+            (
+                "treasury_mint",
+                self.treasury_mint.clone().unwrap_or_else(|| "-".into()),
+            ),
+            ("emission_per_second", self.emission_per_second.to_string()),
+            ("acc_reward_per_nft", self.acc_reward_per_nft.to_string()),
+            ("last_reward_time", self.last_reward_time.to_string()),
+            ("total_staked", self.total_staked.to_string()),
+            ("total_weight", self.total_weight.to_string()),
+            ("start_time", self.start_time.to_string()),
+            (
+                "end_time",
+                self.end_time
+                    .map_or_else(|| "-".into(), |end| end.to_string()),
+            ),
+            (
+                "schedule",
+                if self.schedule.is_empty() {
+                    "-".into()
+                } else {
+                    self.schedule
+                        .iter()
+                        .map(|(start_ts, rate)| format!("{start_ts}:{rate}"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                },
+            ),
+            ("max_supply", self.max_supply.to_string()),
+            ("daily_mint_limit", self.daily_mint_limit.to_string()),
+            ("total_minted", self.total_minted.to_string()),
+            ("mint_window_start", self.mint_window_start.to_string()),
+            ("window_minted", self.window_minted.to_string()),
+            (
+                "lock_tiers",
+                if self.lock_tiers.is_empty() {
+                    "-".into()
+                } else {
+                    self.lock_tiers
+                        .iter()
+                        .map(|(lock_period, multiplier_bps)| {
+                            format!("{lock_period}:{multiplier_bps}")
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                },
+            ),
+            ("paused", format!("{:#05b}", self.paused)),
+            ("emergency", self.emergency.to_string()),
+            ("custody", self.custody.to_string()),
+        ]
+    }
+}
+
+// A wallet's `UserAccount` in a pool
+#[derive(Serialize)]
+pub struct UserView {
+    pub address: String,
+    pub owner: String,
+    pub points: u64,
+    pub amount_staked: u8,
+}
+
+impl UserView {
+    pub fn new(address: &Pubkey, owner: &Pubkey, user: &UserAccount) -> Self {
+        Self {
+            address: address.to_string(),
+            owner: owner.to_string(),
+            points: user.points,
+            amount_staked: user.amount_staked,
+        }
+    }
+}
+
+impl Record for UserView {
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("address", self.address.clone()),
+            ("owner", self.owner.clone()),
+            ("points", self.points.to_string()),
+            ("amount_staked", self.amount_staked.to_string()),
+        ]
+    }
+}
+
+// One `StakeAccount`
+#[derive(Serialize)]
+pub struct StakeView {
+    pub address: String,
+    pub mint: String,
+    pub staked_at: i64,
+    pub last_update: i64,
+    pub tier: u8,
+    pub lock_period: u32,
+    pub multiplier_bps: u16,
+}
+
+impl StakeView {
+    pub fn new(address: &Pubkey, stake: &StakeAccount) -> Self {
+        Self {
+            address: address.to_string(),
+            mint: stake.mint.to_string(),
+            staked_at: stake.staked_at,
+            last_update: stake.last_update,
+            tier: stake.tier,
+            lock_period: stake.lock_period,
+            multiplier_bps: stake.multiplier_bps,
+        }
+    }
+}
+
+impl Record for StakeView {
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("address", self.address.clone()),
+            ("mint", self.mint.clone()),
+            ("staked_at", self.staked_at.to_string()),
+            ("last_update", self.last_update.to_string()),
+            ("tier", self.tier.to_string()),
+            ("lock_period", self.lock_period.to_string()),
+            ("multiplier_bps", self.multiplier_bps.to_string()),
+        ]
+    }
+}
+
+// A simulated `get_pending_rewards` result
+#[derive(Serialize)]
+pub struct PendingView {
+    pub owner: String,
+    pub credited: u64,
+    pub pending: u64,
+    pub total: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+impl PendingView {
+    pub fn new(owner: &Pubkey, rewards: &PendingRewards) -> Self {
+        Self {
+            owner: owner.to_string(),
+            credited: rewards.credited,
+            pending: rewards.pending,
+            total: rewards.total,
+            amount: rewards.amount,
+            timestamp: rewards.timestamp,
+        }
+    }
+}
+
+impl Record for PendingView {
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("owner", self.owner.clone()),
+            ("credited", self.credited.to_string()),
+            ("pending", self.pending.to_string()),
+            ("total", self.total.to_string()),
+            ("amount", self.amount.to_string()),
+            ("timestamp", self.timestamp.to_string()),
+        ]
+    }
+}
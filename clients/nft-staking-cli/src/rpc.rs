@@ -0,0 +1,193 @@
+use std::str::FromStr;
+use std::thread::sleep;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use solana_sdk::{hash::Hash, pubkey::Pubkey, signature::Signature, transaction::Transaction};
+
+// How long to wait for a sent transaction to reach `confirmed`
+const CONFIRM_POLLS: u32 = 60;
+const CONFIRM_INTERVAL: Duration = Duration::from_millis(500);
+
+// Minimal JSON-RPC client covering the calls this tool makes
+pub struct RpcClient {
+    url: String,
+    agent: ureq::Agent,
+}
+
+// An account's owner and raw data
+pub struct Account {
+    pub owner: Pubkey,
+    pub data: Vec<u8>,
+}
+
+// `{ "owner": ..., "data": [base64, "base64"] }` as returned with base64 encoding
+#[derive(Deserialize)]
+struct UiAccount {
+    owner: String,
+    data: (String, String),
+}
+
+#[derive(Deserialize)]
+struct KeyedUiAccount {
+    pubkey: String,
+    account: UiAccount,
+}
+
+#[derive(Deserialize)]
+struct WithContext<T> {
+    value: T,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BlockhashValue {
+    blockhash: String,
+}
+
+// `simulateTransaction` result; only the fields this tool reads
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SimulateValue {
+    err: Option<Value>,
+    logs: Option<Vec<String>>,
+    return_data: Option<ReturnData>,
+}
+
+#[derive(Deserialize)]
+struct ReturnData {
+    data: (String, String),
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SignatureStatus {
+    err: Option<Value>,
+    confirmation_status: Option<String>,
+}
+
+impl UiAccount {
+    fn decode(self) -> Result<Account> {
+        Ok(Account {
+            owner: Pubkey::from_str(&self.owner)?,
+            data: BASE64.decode(self.data.0)?,
+        })
+    }
+}
+
+impl RpcClient {
+    pub fn new(url: String) -> Self {
+        Self {
+            url,
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    // Send one JSON-RPC request and return its `result`
+    fn call<T: DeserializeOwned>(&self, method: &str, params: Value) -> Result<T> {
+        let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let mut response: Value = self
+            .agent
+            .post(&self.url)
+            .send_json(request)
+            .with_context(|| format!("{method} request to {} failed", self.url))?
+            .into_json()?;
+        if let Some(error) = response.get("error") {
+            bail!("{method} failed: {error}");
+        }
+        serde_json::from_value(response["result"].take())
+            .with_context(|| format!("unexpected {method} response"))
+    }
+
+    // Fetch one account, or `None` if it does not exist
+    pub fn get_account(&self, address: &Pubkey) -> Result<Option<Account>> {
+        let response: WithContext<Option<UiAccount>> = self.call(
+            "getAccountInfo",
+            json!([address.to_string(), { "encoding": "base64", "commitment": "confirmed" }]),
+        )?;
+        response.value.map(UiAccount::decode).transpose()
+    }
+
+    // Fetch every account owned by `program` matching `filters`
+    pub fn get_program_accounts(
+        &self,
+        program: &Pubkey,
+        filters: Value,
+    ) -> Result<Vec<(Pubkey, Account)>> {
+        let accounts: Vec<KeyedUiAccount> = self.call(
+            "getProgramAccounts",
+            json!([program.to_string(), {
+                "encoding": "base64",
+                "commitment": "confirmed",
+                "filters": filters,
+            }]),
+        )?;
+        accounts
+            .into_iter()
+            .map(|keyed| Ok((Pubkey::from_str(&keyed.pubkey)?, keyed.account.decode()?)))
+            .collect()
+    }
+
+    pub fn get_latest_blockhash(&self) -> Result<Hash> {
+        let response: WithContext<BlockhashValue> =
+            self.call("getLatestBlockhash", json!([{ "commitment": "confirmed" }]))?;
+        Ok(Hash::from_str(&response.value.blockhash)?)
+    }
+
+    // Simulate an unsigned transaction and return the data its last instruction returned
+    pub fn simulate(&self, transaction: &Transaction) -> Result<Vec<u8>> {
+        let wire = BASE64.encode(bincode::serialize(transaction)?);
+        let response: WithContext<SimulateValue> = self.call(
+            "simulateTransaction",
+            json!([wire, {
+                "encoding": "base64",
+                "commitment": "confirmed",
+                "sigVerify": false,
+                "replaceRecentBlockhash": true,
+            }]),
+        )?;
+        let value = response.value;
+        if let Some(err) = value.err {
+            let logs = value.logs.unwrap_or_default().join("\n");
+            bail!("simulation failed: {err}\n{logs}");
+        }
+        let return_data = value
+            .return_data
+            .ok_or_else(|| anyhow!("simulation returned no data"))?;
+        Ok(BASE64.decode(return_data.data.0)?)
+    }
+
+    // Send a signed transaction and wait until it is confirmed
+    pub fn send_and_confirm(&self, transaction: &Transaction) -> Result<Signature> {
+        let wire = BASE64.encode(bincode::serialize(transaction)?);
+        let signature: String = self.call(
+            "sendTransaction",
+            json!([wire, { "encoding": "base64", "preflightCommitment": "confirmed" }]),
+        )?;
+        let signature = Signature::from_str(&signature)?;
+
+        for _ in 0..CONFIRM_POLLS {
+            let statuses: WithContext<Vec<Option<SignatureStatus>>> =
+                self.call("getSignatureStatuses", json!([[signature.to_string()]]))?;
+            if let Some(Some(status)) = statuses.value.into_iter().next() {
+                if let Some(err) = status.err {
+                    bail!("transaction {signature} failed: {err}");
+                }
+                if matches!(
+                    status.confirmation_status.as_deref(),
+                    Some("confirmed" | "finalized")
+                ) {
+                    return Ok(signature);
+                }
+            }
+            sleep(CONFIRM_INTERVAL);
+        }
+        Err(anyhow!("transaction {signature} was not confirmed in time"))
+    }
+}
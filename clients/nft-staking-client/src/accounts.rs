@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*; // Result type and account traits
+use nft_staking::{PendingRewards, StakeAccount, StakeConfig, UserAccount};
+
+// Decoders for raw account data fetched over RPC
+// Each checks the 8-byte Anchor discriminator before deserializing
+
+// Byte offset of `StakeAccount.owner`, for `getProgramAccounts` memcmp filters
+pub const STAKE_ACCOUNT_OWNER_OFFSET: usize = 8;
+// Size of a `StakeAccount`, for `getProgramAccounts` data size filters
+pub const STAKE_ACCOUNT_SIZE: usize = 8 + StakeAccount::INIT_SPACE;
+
+// Decode any of the program's accounts
+pub fn decode<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+    T::try_deserialize(&mut &data[..])
+}
+
+// Decode a pool's `StakeConfig`
+pub fn decode_stake_config(data: &[u8]) -> Result<StakeConfig> {
+    decode(data)
+}
+
+// Decode a user's `UserAccount`
+pub fn decode_user_account(data: &[u8]) -> Result<UserAccount> {
+    decode(data)
+}
+
+// Decode an NFT's `StakeAccount`
+pub fn decode_stake_account(data: &[u8]) -> Result<StakeAccount> {
+    decode(data)
+}
+
+// Decode the return data of a simulated `get_pending_rewards` (already base64-decoded)
+pub fn decode_pending_rewards(data: &[u8]) -> Result<PendingRewards> {
+    Ok(PendingRewards::deserialize(&mut &data[..])?)
+}
@@ -0,0 +1,1045 @@
+use anchor_lang::prelude::*; // Pubkey, AccountMeta and the account-list traits
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::sysvar; // Instructions sysvar address
+use anchor_lang::system_program; // System program ID
+use anchor_lang::InstructionData; // Serializes instruction arguments with their discriminator
+use anchor_spl::associated_token::{self, get_associated_token_address_with_program_id};
+use anchor_spl::metadata::mpl_token_metadata; // Token Metadata program ID
+use anchor_spl::token; // Legacy SPL Token program ID
+use anchor_spl::token_2022; // Token-2022 program ID
+use nft_staking::{
+    accounts, instruction, CnftLeaf, ConfigParams, LockTier, ScheduleParams, UpdateConfigParams,
+    MPL_ACCOUNT_COMPRESSION_ID, MPL_NOOP_ID,
+};
+
+use crate::pda::*; // PDA derivation helpers
+
+// Typed builders for the core instructions
+// Every PDA, ATA and program account is derived here so callers only pass
+// the wallets, pool and mint involved
+
+// Token Auth Rules program, which evaluates a pNFT's rule set
+pub const MPL_TOKEN_AUTH_RULES_ID: Pubkey = pubkey!("auth9SigNpDKz4sJJ1DfCTuZrZNSAgh9sFD3rboVmgg");
+
+// Build an instruction from the program's generated account and data structs
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: nft_staking::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+// Append the stake accounts of `mints` after the named accounts
+fn push_stakes(ix: &mut Instruction, config: &Pubkey, mints: &[Pubkey], writable: bool) {
+    ix.accounts.extend(mints.iter().map(|mint| {
+        let stake = stake_account_pda(mint, config).0;
+        if writable {
+            AccountMeta::new(stake, false)
+        } else {
+            AccountMeta::new_readonly(stake, false)
+        }
+    }));
+}
+
+// Create a pool and its reward mint; `admin` must be the program's upgrade authority
+// `token_program` is SPL Token or Token-2022 and owns the reward mint
+pub fn initialize_config(
+    admin: &Pubkey,
+    collection: &Pubkey,
+    pool_id: u16,
+    params: ConfigParams,
+    token_program: &Pubkey,
+) -> Instruction {
+    let config = config_pda(collection, pool_id).0;
+    build(
+        accounts::InitializeConfig {
+            admin: *admin,
+            program_data: program_data_address(),
+            config,
+            rewards_mint: rewards_mint_pda(&config).0,
+            system_program: system_program::ID,
+            token_program: *token_program,
+            event_authority: event_authority_pda().0,
+            program: nft_staking::ID,
+        },
+        instruction::InitializeConfig {
+            collection: *collection,
+            pool_id,
+            params,
+        },
+    )
+}
+
+// Overwrite the parameters set in `params`; `admin` must be the pool admin
+pub fn update_config(admin: &Pubkey, config: &Pubkey, params: UpdateConfigParams) -> Instruction {
+    build(
+        accounts::UpdateConfig {
+            admin: *admin,
+            config: *config,
+            event_authority: event_authority_pda().0,
+            program: nft_staking::ID,
+        },
+        instruction::UpdateConfig { params },
+    )
+}
+
+// Replace an emission pool's schedule; `admin` must be the pool admin
+pub fn set_emission_schedule(
+    admin: &Pubkey,
+    config: &Pubkey,
+    params: ScheduleParams,
+) -> Instruction {
+    build(
+        accounts::SetEmissionSchedule {
+            admin: *admin,
+            config: *config,
+            event_authority: event_authority_pda().0,
+            program: nft_staking::ID,
+        },
+        instruction::SetEmissionSchedule { params },
+    )
+}
+
+// Replace the lock tiers offered to new stakes; `admin` must be the pool admin
+pub fn set_lock_tiers(admin: &Pubkey, config: &Pubkey, tiers: Vec<LockTier>) -> Instruction {
+    build(
+        accounts::SetLockTiers {
+            admin: *admin,
+            config: *config,
+            event_authority: event_authority_pda().0,
+            program: nft_staking::ID,
+        },
+        instruction::SetLockTiers { tiers },
+    )
+}
+
+// Create `user`'s staking account in a pool
+pub fn initialize_user(user: &Pubkey, config: &Pubkey) -> Instruction {
+    build(
+        accounts::Initialize {
+            user: *user,
+            config: *config,
+            user_account: user_account_pda(config, user).0,
+            system_program: system_program::ID,
+            event_authority: event_authority_pda().0,
+            program: nft_staking::ID,
+        },
+        instruction::InitializeUser {},
+    )
+}
+
+// Freeze `mint` (a legacy SPL Token NFT held in `user`'s ATA) in a pool, in lock tier `tier`
+pub fn stake(user: &Pubkey, config: &Pubkey, mint: &Pubkey, tier: u8) -> Instruction {
+    build(
+        accounts::Stake {
+            user: *user,
+            mint: *mint,
+            mint_ata: get_associated_token_address_with_program_id(user, mint, &token::ID),
+            metadata: metadata_pda(mint).0,
+            edition: master_edition_pda(mint).0,
+            config: *config,
+            stake_account: stake_account_pda(mint, config).0,
+            user_account: user_account_pda(config, user).0,
+            system_program: system_program::ID,
+            token_program: token::ID,
+            metadata_program: mpl_token_metadata::ID,
+            event_authority: event_authority_pda().0,
+            program: nft_staking::ID,
+        },
+        instruction::Stake { tier },
+    )
+}
+
+// Thaw `mint` after its freeze period and credit the points it accrued
+pub fn unstake(user: &Pubkey, config: &Pubkey, mint: &Pubkey) -> Instruction {
+    build(
+        accounts::Unstake {
+            user: *user,
+            mint: *mint,
+            mint_ata: get_associated_token_address_with_program_id(user, mint, &token::ID),
+            edition: master_edition_pda(mint).0,
+            config: *config,
+            stake_account: stake_account_pda(mint, config).0,
+            user_account: user_account_pda(config, user).0,
+            token_program: token::ID,
+            metadata_program: mpl_token_metadata::ID,
+            event_authority: event_authority_pda().0,
+            program: nft_staking::ID,
+        },
+        instruction::Unstake {},
+    )
+}
+
+// Lock a programmable NFT in `user`'s ATA with the stake PDA as staking delegate, in lock tier `tier`
+// `rule_set` is the pNFT's authorization rule set, if its programmable config names one
+pub fn stake_pnft(
+    user: &Pubkey,
+    config: &Pubkey,
+    mint: &Pubkey,
+    rule_set: Option<&Pubkey>,
+    tier: u8,
+) -> Instruction {
+    let mint_ata = get_associated_token_address_with_program_id(user, mint, &token::ID);
+    build(
+        accounts::StakePnft {
+            user: *user,
+            mint: *mint,
+            mint_ata,
+            metadata: metadata_pda(mint).0,
+            edition: master_edition_pda(mint).0,
+            token_record: token_record_pda(mint, &mint_ata).0,
+            config: *config,
+            stake_account: stake_account_pda(mint, config).0,
+            user_account: user_account_pda(config, user).0,
+            authorization_rules_program: rule_set.map(|_| MPL_TOKEN_AUTH_RULES_ID),
+            authorization_rules: rule_set.copied(),
+            sysvar_instructions: sysvar::instructions::ID,
+            system_program: system_program::ID,
+            token_program: token::ID,
+            metadata_program: mpl_token_metadata::ID,
+            event_authority: event_authority_pda().0,
+            program: nft_staking::ID,
+        },
+        instruction::StakePnft { tier },
+    )
+}
+
+// Unlock a staked programmable NFT, revoke the stake PDA's delegation and credit the points it accrued
+pub fn unstake_pnft(
+    user: &Pubkey,
+    config: &Pubkey,
+    mint: &Pubkey,
+    rule_set: Option<&Pubkey>,
+) -> Instruction {
+    let mint_ata = get_associated_token_address_with_program_id(user, mint, &token::ID);
+    build(
+        accounts::UnstakePnft {
+            user: *user,
+            mint: *mint,
+            mint_ata,
+            metadata: metadata_pda(mint).0,
+            edition: master_edition_pda(mint).0,
+            token_record: token_record_pda(mint, &mint_ata).0,
+            config: *config,
+            stake_account: stake_account_pda(mint, config).0,
+            user_account: user_account_pda(config, user).0,
+            authorization_rules_program: rule_set.map(|_| MPL_TOKEN_AUTH_RULES_ID),
+            authorization_rules: rule_set.copied(),
+            sysvar_instructions: sysvar::instructions::ID,
+            system_program: system_program::ID,
+            token_program: token::ID,
+            metadata_program: mpl_token_metadata::ID,
+            event_authority: event_authority_pda().0,
+            program: nft_staking::ID,
+        },
+        instruction::UnstakePnft {},
+    )
+}
+
+// Freeze a Core `asset` of the pool's Core `collection` with a FreezeDelegate plugin,
+// in lock tier `tier`; the asset address takes the mint's place in the stake PDA
+pub fn stake_core(
+    user: &Pubkey,
+    config: &Pubkey,
+    asset: &Pubkey,
+    collection: &Pubkey,
+    tier: u8,
+) -> Instruction {
+    build(
+        accounts::StakeCore {
+            user: *user,
+            asset: *asset,
+            collection: *collection,
+            config: *config,
+            stake_account: stake_account_pda(asset, config).0,
+            user_account: user_account_pda(config, user).0,
+            core_program: mpl_core::ID,
+            system_program: system_program::ID,
+            event_authority: event_authority_pda().0,
+            program: nft_staking::ID,
+        },
+        instruction::StakeCore { tier },
+    )
+}
+
+// Thaw a Core `asset`, remove its FreezeDelegate plugin and credit the points it accrued
+pub fn unstake_core(
+    user: &Pubkey,
+    config: &Pubkey,
+    asset: &Pubkey,
+    collection: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::UnstakeCore {
+            user: *user,
+            asset: *asset,
+            collection: *collection,
+            config: *config,
+            stake_account: stake_account_pda(asset, config).0,
+            user_account: user_account_pda(config, user).0,
+            core_program: mpl_core::ID,
+            system_program: system_program::ID,
+            event_authority: event_authority_pda().0,
+            program: nft_staking::ID,
+        },
+        instruction::UnstakeCore {},
+    )
+}
+
+// Delegate a compressed NFT in `merkle_tree` to its stake PDA and freeze it, in lock tier `tier`
+// `leaf` and `proof` are the leaf's current contents and Merkle proof nodes (from the DAS
+// API's getAsset / getAssetProof); the proof goes in as read-only remaining accounts
+pub fn stake_cnft(
+    user: &Pubkey,
+    config: &Pubkey,
+    merkle_tree: &Pubkey,
+    leaf: CnftLeaf,
+    proof: &[Pubkey],
+    tier: u8,
+) -> Instruction {
+    let asset_id = cnft_asset_id(merkle_tree, leaf.nonce);
+    let mut ix = build(
+        accounts::StakeCnft {
+            user: *user,
+            tree_config: tree_config_pda(merkle_tree).0,
+            merkle_tree: *merkle_tree,
+            config: *config,
+            stake_account: stake_account_pda(&asset_id, config).0,
+            user_account: user_account_pda(config, user).0,
+            bubblegum_program: mpl_bubblegum::ID,
+            log_wrapper: MPL_NOOP_ID,
+            compression_program: MPL_ACCOUNT_COMPRESSION_ID,
+            system_program: system_program::ID,
+            event_authority: event_authority_pda().0,
+            program: nft_staking::ID,
+        },
+        instruction::StakeCnft { leaf, tier },
+    );
+    ix.accounts.extend(
+        proof
+            .iter()
+            .map(|node| AccountMeta::new_readonly(*node, false)),
+    );
+    ix
+}
+
+// Thaw a staked compressed NFT, revoke the stake PDA's delegation and credit the points it accrued
+// `leaf` must describe the leaf as staked: delegated to the stake PDA and frozen
+pub fn unstake_cnft(
+    user: &Pubkey,
+    config: &Pubkey,
+    merkle_tree: &Pubkey,
+    leaf: CnftLeaf,
+    proof: &[Pubkey],
+) -> Instruction {
+    let asset_id = cnft_asset_id(merkle_tree, leaf.nonce);
+    let mut ix = build(
+        accounts::UnstakeCnft {
+            user: *user,
+            tree_config: tree_config_pda(merkle_tree).0,
+            merkle_tree: *merkle_tree,
+            config: *config,
+            stake_account: stake_account_pda(&asset_id, config).0,
+            user_account: user_account_pda(config, user).0,
+            bubblegum_program: mpl_bubblegum::ID,
+            log_wrapper: MPL_NOOP_ID,
+            compression_program: MPL_ACCOUNT_COMPRESSION_ID,
+            system_program: system_program::ID,
+            event_authority: event_authority_pda().0,
+            program: nft_staking::ID,
+        },
+        instruction::UnstakeCnft { leaf },
+    );
+    ix.accounts.extend(
+        proof
+            .iter()
+            .map(|node| AccountMeta::new_readonly(*node, false)),
+    );
+    ix
+}
+
+// Freeze a Token-2022 `mint` in the user's ATA and start accruing points at `tier`
+// (freeze authority rules: README "Stake Token-2022 NFT")
+pub fn stake_token22(user: &Pubkey, config: &Pubkey, mint: &Pubkey, tier: u8) -> Instruction {
+    build(
+        accounts::StakeToken22 {
+            user: *user,
+            freeze_authority: freeze_authority_pda().0,
+            mint: *mint,
+            mint_ata: get_associated_token_address_with_program_id(user, mint, &token_2022::ID),
+            config: *config,
+            stake_account: stake_account_pda(mint, config).0,
+            user_account: user_account_pda(config, user).0,
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+            event_authority: event_authority_pda().0,
+            program: nft_staking::ID,
+        },
+        instruction::StakeToken22 { tier },
+    )
+}
+
+// Thaw a Token-2022 `mint` after its lock and credit the points it accrued
+pub fn unstake_token22(user: &Pubkey, config: &Pubkey, mint: &Pubkey) -> Instruction {
+    build(
+        accounts::UnstakeToken22 {
+            user: *user,
+            freeze_authority: freeze_authority_pda().0,
+            mint: *mint,
+            mint_ata: get_associated_token_address_with_program_id(user, mint, &token_2022::ID),
+            config: *config,
+            stake_account: stake_account_pda(mint, config).0,
+            user_account: user_account_pda(config, user).0,
+            token_program: token_2022::ID,
+            event_authority: event_authority_pda().0,
+            program: nft_staking::ID,
+        },
+        instruction::UnstakeToken22 {},
+    )
+}
+
+// Move `mint` from `user`'s ATA into the pool vault, in lock tier `tier` (custody pools only)
+// `token_program` is SPL Token or Token-2022 and owns the NFT mint
+pub fn stake_vault(
+    user: &Pubkey,
+    config: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    tier: u8,
+) -> Instruction {
+    build(
+        accounts::StakeVault {
+            user: *user,
+            mint: *mint,
+            mint_ata: get_associated_token_address_with_program_id(user, mint, token_program),
+            metadata: metadata_pda(mint).0,
+            config: *config,
+            vault: get_associated_token_address_with_program_id(config, mint, token_program),
+            stake_account: stake_account_pda(mint, config).0,
+            user_account: user_account_pda(config, user).0,
+            system_program: system_program::ID,
+            token_program: *token_program,
+            associated_token_program: associated_token::ID,
+            metadata_program: mpl_token_metadata::ID,
+            event_authority: event_authority_pda().0,
+            program: nft_staking::ID,
+        },
+        instruction::StakeVault { tier },
+    )
+}
+
+// Return a vaulted `mint` to `user`'s ATA and credit the points it accrued
+pub fn unstake_vault(
+    user: &Pubkey,
+    config: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::UnstakeVault {
+            user: *user,
+            mint: *mint,
+            mint_ata: get_associated_token_address_with_program_id(user, mint, token_program),
+            vault: get_associated_token_address_with_program_id(config, mint, token_program),
+            config: *config,
+            stake_account: stake_account_pda(mint, config).0,
+            user_account: user_account_pda(config, user).0,
+            system_program: system_program::ID,
+            token_program: *token_program,
+            associated_token_program: associated_token::ID,
+            event_authority: event_authority_pda().0,
+            program: nft_staking::ID,
+        },
+        instruction::UnstakeVault {},
+    )
+}
+
+// Freeze every legacy NFT in `mints` in one instruction, all in lock tier `tier`
+// Each mint adds its group of accounts (mint, ATA, metadata, edition, stake record);
+// if any NFT fails the whole batch reverts and the log names its index
+pub fn stake_many(user: &Pubkey, config: &Pubkey, mints: &[Pubkey], tier: u8) -> Instruction {
+    let mut ix = build(
+        accounts::StakeMany {
+            user: *user,
+            config: *config,
+            user_account: user_account_pda(config, user).0,
+            system_program: system_program::ID,
+            token_program: token::ID,
+            metadata_program: mpl_token_metadata::ID,
+            event_authority: event_authority_pda().0,
+            program: nft_staking::ID,
+        },
+        instruction::StakeMany { tier },
+    );
+    for mint in mints {
+        ix.accounts.extend([
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(
+                get_associated_token_address_with_program_id(user, mint, &token::ID),
+                false,
+            ),
+            AccountMeta::new_readonly(metadata_pda(mint).0, false),
+            AccountMeta::new_readonly(master_edition_pda(mint).0, false),
+            AccountMeta::new(stake_account_pda(mint, config).0, false),
+        ]);
+    }
+    ix
+}
+
+// Thaw every NFT in `mints` in one instruction; reverses `stake_many`
+// Each mint adds its group of accounts (mint, ATA, edition, stake record)
+pub fn unstake_many(user: &Pubkey, config: &Pubkey, mints: &[Pubkey]) -> Instruction {
+    let mut ix = build(
+        accounts::UnstakeMany {
+            user: *user,
+            config: *config,
+            user_account: user_account_pda(config, user).0,
+            token_program: token::ID,
+            metadata_program: mpl_token_metadata::ID,
+            event_authority: event_authority_pda().0,
+            program: nft_staking::ID,
+        },
+        instruction::UnstakeMany {},
+    );
+    for mint in mints {
+        ix.accounts.extend([
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(
+                get_associated_token_address_with_program_id(user, mint, &token::ID),
+                false,
+            ),
+            AccountMeta::new_readonly(master_edition_pda(mint).0, false),
+            AccountMeta::new(stake_account_pda(mint, config).0, false),
+        ]);
+    }
+    ix
+}
+
+// Mint `user`'s points as reward tokens; the reward ATA must already exist
+// `settle` lists still-staked mints whose accrued points are credited first
+pub fn claim(
+    user: &Pubkey,
+    config: &Pubkey,
+    token_program: &Pubkey,
+    settle: &[Pubkey],
+) -> Instruction {
+    let reward_mint = rewards_mint_pda(config).0;
+    let mut ix = build(
+        accounts::Claim {
+            user: *user,
+            user_account: user_account_pda(config, user).0,
+            config: *config,
+            reward_mint,
+            user_reward_ata: get_associated_token_address_with_program_id(
+                user,
+                &reward_mint,
+                token_program,
+            ),
+            token_program: *token_program,
+            event_authority: event_authority_pda().0,
+            program: nft_staking::ID,
+        },
+        instruction::Claim {},
+    );
+    push_stakes(&mut ix, config, settle, true);
+    ix
+}
+
+// Read-only view of `user`'s points at the current clock; run it with `simulateTransaction`
+// and decode the return data with `decode_pending_rewards`
+// `stakes` lists the staked mints whose accrued points are included
+pub fn get_pending_rewards(user: &Pubkey, config: &Pubkey, stakes: &[Pubkey]) -> Instruction {
+    let mut ix = build(
+        accounts::GetPendingRewards {
+            user: *user,
+            config: *config,
+            user_account: user_account_pda(config, user).0,
+        },
+        instruction::GetPendingRewards {},
+    );
+    push_stakes(&mut ix, config, stakes, false);
+    ix
+}
+
+// Switch a pool to paying claims from a treasury of `mint` and create its vault
+pub fn initialize_treasury(
+    admin: &Pubkey,
+    config: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::InitializeTreasury {
+            admin: *admin,
+            config: *config,
+            treasury_mint: *mint,
+            treasury_vault: treasury_vault_address(config, mint, token_program),
+            system_program: system_program::ID,
+            token_program: *token_program,
+            associated_token_program: associated_token::ID,
+            event_authority: event_authority_pda().0,
+            program: nft_staking::ID,
+        },
+        instruction::InitializeTreasury {},
+    )
+}
+
+// Deposit `amount` base units of the treasury token from `funder`'s ATA
+pub fn fund_treasury(
+    funder: &Pubkey,
+    config: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    build(
+        accounts::FundTreasury {
+            funder: *funder,
+            config: *config,
+            treasury_mint: *mint,
+            funder_ata: get_associated_token_address_with_program_id(funder, mint, token_program),
+            treasury_vault: treasury_vault_address(config, mint, token_program),
+            token_program: *token_program,
+            event_authority: event_authority_pda().0,
+            program: nft_staking::ID,
+        },
+        instruction::FundTreasury { amount },
+    )
+}
+
+// Pay `user`'s points from the pool treasury; the user's ATA for `mint` must already exist
+// `settle` lists still-staked mints whose accrued points are credited first
+pub fn claim_treasury(
+    user: &Pubkey,
+    config: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    settle: &[Pubkey],
+) -> Instruction {
+    let mut ix = build(
+        accounts::ClaimTreasury {
+            user: *user,
+            user_account: user_account_pda(config, user).0,
+            config: *config,
+            treasury_mint: *mint,
+            treasury_vault: treasury_vault_address(config, mint, token_program),
+            user_reward_ata: get_associated_token_address_with_program_id(
+                user,
+                mint,
+                token_program,
+            ),
+            token_program: *token_program,
+            event_authority: event_authority_pda().0,
+            program: nft_staking::ID,
+        },
+        instruction::ClaimTreasury {},
+    );
+    push_stakes(&mut ix, config, settle, true);
+    ix
+}
+
+#[cfg(test)]
+mod tests {
+    // Each builder is checked against what the program declares, without going through
+    // the generated `accounts::*` structs or this crate's PDA helpers: addresses come
+    // from literal seeds, flags from the `mut` / `Signer` markers in the program's
+    // account structs, and data from Anchor's discriminator rule plus a Borsh decode
+    use super::*;
+    use anchor_lang::solana_program::hash::hash; // SHA-256, for Anchor discriminators
+
+    // Expected account entry: address, is_signer, is_writable
+    type Meta = (Pubkey, bool, bool);
+
+    fn payer(key: Pubkey) -> Meta {
+        (key, true, true)
+    }
+
+    fn writable(key: Pubkey) -> Meta {
+        (key, false, true)
+    }
+
+    fn readonly(key: Pubkey) -> Meta {
+        (key, false, false)
+    }
+
+    fn assert_accounts(ix: &Instruction, expected: &[Meta]) {
+        assert_eq!(ix.program_id, nft_staking::ID);
+        let actual: Vec<Meta> = ix
+            .accounts
+            .iter()
+            .map(|meta| (meta.pubkey, meta.is_signer, meta.is_writable))
+            .collect();
+        assert_eq!(actual, expected);
+    }
+
+    // Check Anchor's discriminator (first 8 bytes of sha256("global:<name>")) and decode the args
+    fn args<T: AnchorDeserialize>(ix: &Instruction, name: &str) -> T {
+        let discriminator = hash(format!("global:{name}").as_bytes()).to_bytes();
+        assert_eq!(ix.data[..8], discriminator[..8], "discriminator of {name}");
+        T::try_from_slice(&ix.data[8..]).unwrap()
+    }
+
+    fn program_pda(seeds: &[&[u8]]) -> Pubkey {
+        Pubkey::find_program_address(seeds, &nft_staking::ID).0
+    }
+
+    fn metadata_pda(seeds: &[&[u8]]) -> Pubkey {
+        let seeds = [
+            &[b"metadata".as_ref(), mpl_token_metadata::ID.as_ref()],
+            seeds,
+        ]
+        .concat();
+        Pubkey::find_program_address(&seeds, &mpl_token_metadata::ID).0
+    }
+
+    // Trailing accounts every `#[event_cpi]` instruction takes
+    fn event_cpi() -> [Meta; 2] {
+        [
+            readonly(pubkey!("8HmH5PwHMeDmUW4TgNqhmm2KHMMgDWj6JFchKQzTWNVG")), // Event authority
+            readonly(nft_staking::ID),
+        ]
+    }
+
+    fn ata(owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+        get_associated_token_address_with_program_id(owner, mint, token_program)
+    }
+
+    #[test]
+    fn initialize_config_pays_from_admin_and_creates_config_and_rewards_mint() {
+        let admin = Pubkey::new_unique();
+        let collection = Pubkey::new_unique();
+        let params = ConfigParams {
+            points_per_stake: 10,
+            points_period: 86_400,
+            max_stake: 5,
+            freeze_period: 0,
+            reward_per_point: 1,
+            emission_per_second: 0,
+            max_supply: 0,
+            daily_mint_limit: 0,
+            custody: true,
+        };
+        let ix = initialize_config(&admin, &collection, 0x0102, params, &token_2022::ID);
+
+        let config = program_pda(&[b"config", collection.as_ref(), &[0x02, 0x01]]);
+        let program_data = Pubkey::find_program_address(
+            &[nft_staking::ID.as_ref()],
+            &anchor_lang::solana_program::bpf_loader_upgradeable::ID,
+        )
+        .0;
+        let expected = [
+            payer(admin),
+            readonly(program_data),
+            writable(config),
+            writable(program_pda(&[b"rewards", config.as_ref()])),
+            readonly(system_program::ID),
+            readonly(token_2022::ID),
+        ];
+        assert_accounts(&ix, &[&expected[..], &event_cpi()].concat());
+        let decoded: instruction::InitializeConfig = args(&ix, "initialize_config");
+        assert_eq!(decoded.pool_id, 0x0102);
+        assert_eq!(decoded.params.points_period, 86_400);
+        assert!(decoded.params.custody);
+    }
+
+    #[test]
+    fn stake_freezes_from_the_users_legacy_ata() {
+        let (user, config, mint) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let ix = stake(&user, &config, &mint, 2);
+
+        let expected = [
+            payer(user),
+            readonly(mint),
+            writable(ata(&user, &mint, &token::ID)),
+            readonly(metadata_pda(&[mint.as_ref()])),
+            readonly(metadata_pda(&[mint.as_ref(), b"edition"])),
+            writable(config),
+            writable(program_pda(&[b"stake", mint.as_ref(), config.as_ref()])),
+            writable(program_pda(&[b"user", config.as_ref(), user.as_ref()])),
+            readonly(system_program::ID),
+            readonly(token::ID),
+            readonly(mpl_token_metadata::ID),
+        ];
+        assert_accounts(&ix, &[&expected[..], &event_cpi()].concat());
+        assert_eq!(args::<instruction::Stake>(&ix, "stake").tier, 2);
+    }
+
+    #[test]
+    fn unstake_closes_stake_record_to_user() {
+        let (user, config, mint) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let ix = unstake(&user, &config, &mint);
+
+        let expected = [
+            payer(user),
+            readonly(mint),
+            writable(ata(&user, &mint, &token::ID)),
+            readonly(metadata_pda(&[mint.as_ref(), b"edition"])),
+            writable(config),
+            writable(program_pda(&[b"stake", mint.as_ref(), config.as_ref()])),
+            writable(program_pda(&[b"user", config.as_ref(), user.as_ref()])),
+            readonly(token::ID),
+            readonly(mpl_token_metadata::ID),
+        ];
+        assert_accounts(&ix, &[&expected[..], &event_cpi()].concat());
+        args::<instruction::Unstake>(&ix, "unstake");
+        assert_eq!(ix.data.len(), 8); // No arguments
+    }
+
+    #[test]
+    fn stake_pnft_fills_absent_rule_set_with_program_placeholders() {
+        let (user, config, mint) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let mint_ata = ata(&user, &mint, &token::ID);
+        let rule_set = Pubkey::new_unique();
+
+        let expected = |rules: [Meta; 2]| {
+            [
+                &[
+                    payer(user),
+                    readonly(mint),
+                    writable(mint_ata),
+                    writable(metadata_pda(&[mint.as_ref()])),
+                    readonly(metadata_pda(&[mint.as_ref(), b"edition"])),
+                    writable(metadata_pda(&[
+                        mint.as_ref(),
+                        b"token_record",
+                        mint_ata.as_ref(),
+                    ])),
+                    writable(config),
+                    writable(program_pda(&[b"stake", mint.as_ref(), config.as_ref()])),
+                    writable(program_pda(&[b"user", config.as_ref(), user.as_ref()])),
+                ][..],
+                &rules,
+                &[
+                    readonly(pubkey!("Sysvar1nstructions1111111111111111111111111")),
+                    readonly(system_program::ID),
+                    readonly(token::ID),
+                    readonly(mpl_token_metadata::ID),
+                ],
+                &event_cpi(),
+            ]
+            .concat()
+        };
+        // Anchor encodes a missing optional account as the program ID
+        let ix = stake_pnft(&user, &config, &mint, None, 1);
+        assert_accounts(&ix, &expected([readonly(nft_staking::ID); 2]));
+        assert_eq!(args::<instruction::StakePnft>(&ix, "stake_pnft").tier, 1);
+
+        let ix = stake_pnft(&user, &config, &mint, Some(&rule_set), 1);
+        let rules = [
+            readonly(pubkey!("auth9SigNpDKz4sJJ1DfCTuZrZNSAgh9sFD3rboVmgg")),
+            readonly(rule_set),
+        ];
+        assert_accounts(&ix, &expected(rules));
+    }
+
+    #[test]
+    fn stake_core_keys_stake_record_by_asset() {
+        let (user, config) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (asset, collection) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let ix = stake_core(&user, &config, &asset, &collection, 0);
+
+        let expected = [
+            payer(user),
+            writable(asset),
+            writable(collection),
+            writable(config),
+            writable(program_pda(&[b"stake", asset.as_ref(), config.as_ref()])),
+            writable(program_pda(&[b"user", config.as_ref(), user.as_ref()])),
+            readonly(pubkey!("CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7d")),
+            readonly(system_program::ID),
+        ];
+        assert_accounts(&ix, &[&expected[..], &event_cpi()].concat());
+        assert_eq!(args::<instruction::StakeCore>(&ix, "stake_core").tier, 0);
+    }
+
+    #[test]
+    fn stake_token22_signs_nothing_but_the_user_and_uses_the_freeze_pda() {
+        let (user, config, mint) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let ix = stake_token22(&user, &config, &mint, 1);
+
+        let expected = [
+            payer(user),
+            readonly(program_pda(&[b"freeze"])),
+            readonly(mint),
+            writable(ata(&user, &mint, &token_2022::ID)),
+            writable(config),
+            writable(program_pda(&[b"stake", mint.as_ref(), config.as_ref()])),
+            writable(program_pda(&[b"user", config.as_ref(), user.as_ref()])),
+            readonly(system_program::ID),
+            readonly(token_2022::ID),
+        ];
+        assert_accounts(&ix, &[&expected[..], &event_cpi()].concat());
+        assert_eq!(
+            args::<instruction::StakeToken22>(&ix, "stake_token22").tier,
+            1
+        );
+    }
+
+    #[test]
+    fn stake_vault_moves_nft_into_config_owned_ata() {
+        let (user, config, mint) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let ix = stake_vault(&user, &config, &mint, &token_2022::ID, 3);
+
+        let expected = [
+            payer(user),
+            readonly(mint),
+            writable(ata(&user, &mint, &token_2022::ID)),
+            readonly(metadata_pda(&[mint.as_ref()])),
+            writable(config),
+            writable(ata(&config, &mint, &token_2022::ID)),
+            writable(program_pda(&[b"stake", mint.as_ref(), config.as_ref()])),
+            writable(program_pda(&[b"user", config.as_ref(), user.as_ref()])),
+            readonly(system_program::ID),
+            readonly(token_2022::ID),
+            readonly(associated_token::ID),
+            readonly(mpl_token_metadata::ID),
+        ];
+        assert_accounts(&ix, &[&expected[..], &event_cpi()].concat());
+        assert_eq!(args::<instruction::StakeVault>(&ix, "stake_vault").tier, 3);
+    }
+
+    #[test]
+    fn stake_many_appends_one_group_per_mint() {
+        let (user, config) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mints = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let ix = stake_many(&user, &config, &mints, 1);
+
+        let mut expected = vec![
+            payer(user),
+            writable(config),
+            writable(program_pda(&[b"user", config.as_ref(), user.as_ref()])),
+            readonly(system_program::ID),
+            readonly(token::ID),
+            readonly(mpl_token_metadata::ID),
+        ];
+        expected.extend(event_cpi());
+        for mint in &mints {
+            expected.extend([
+                readonly(*mint),
+                writable(ata(&user, mint, &token::ID)),
+                readonly(metadata_pda(&[mint.as_ref()])),
+                readonly(metadata_pda(&[mint.as_ref(), b"edition"])),
+                writable(program_pda(&[b"stake", mint.as_ref(), config.as_ref()])),
+            ]);
+        }
+        assert_accounts(&ix, &expected);
+        assert_eq!(args::<instruction::StakeMany>(&ix, "stake_many").tier, 1);
+    }
+
+    #[test]
+    fn stake_cnft_keys_stake_record_by_asset_id_and_appends_proof() {
+        let (user, config, tree) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let proof = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let leaf = CnftLeaf {
+            root: [1; 32],
+            data_hash: [2; 32],
+            creator_hash: [3; 32],
+            asset_data_hash: None,
+            flags: Some(0),
+            nonce: 4,
+            index: 9,
+        };
+        let ix = stake_cnft(&user, &config, &tree, leaf, &proof, 0);
+
+        let bubblegum = pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+        let asset_id = Pubkey::find_program_address(
+            &[b"asset", tree.as_ref(), &4u64.to_le_bytes()],
+            &bubblegum,
+        )
+        .0;
+        let mut expected = vec![
+            payer(user),
+            writable(Pubkey::find_program_address(&[tree.as_ref()], &bubblegum).0),
+            writable(tree),
+            writable(config),
+            writable(program_pda(&[b"stake", asset_id.as_ref(), config.as_ref()])),
+            writable(program_pda(&[b"user", config.as_ref(), user.as_ref()])),
+            readonly(bubblegum),
+            readonly(pubkey!("mnoopTCrg4p8ry25e4bcWA9XZjbNjMTfgYVGGEdRsf3")),
+            readonly(pubkey!("mcmt6YrQEMKw8Mw43FmpRLmf7BqRnFMKmAcbxE3xkAW")),
+            readonly(system_program::ID),
+        ];
+        expected.extend(event_cpi());
+        expected.extend(proof.map(readonly));
+        assert_accounts(&ix, &expected);
+
+        let decoded: instruction::StakeCnft = args(&ix, "stake_cnft");
+        assert_eq!(decoded.leaf.root, [1; 32]);
+        assert_eq!(decoded.leaf.flags, Some(0));
+        assert_eq!((decoded.leaf.nonce, decoded.leaf.index), (4, 9));
+        assert_eq!(decoded.tier, 0);
+    }
+
+    #[test]
+    fn claim_appends_writable_stake_records() {
+        let (user, config) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let settle = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let ix = claim(&user, &config, &token::ID, &settle);
+
+        let reward_mint = program_pda(&[b"rewards", config.as_ref()]);
+        let mut expected = vec![
+            payer(user),
+            writable(program_pda(&[b"user", config.as_ref(), user.as_ref()])),
+            writable(config),
+            writable(reward_mint),
+            writable(ata(&user, &reward_mint, &token::ID)),
+            readonly(token::ID),
+        ];
+        expected.extend(event_cpi());
+        for mint in &settle {
+            expected.push(writable(program_pda(&[
+                b"stake",
+                mint.as_ref(),
+                config.as_ref(),
+            ])));
+        }
+        assert_accounts(&ix, &expected);
+        args::<instruction::Claim>(&ix, "claim");
+    }
+
+    #[test]
+    fn get_pending_rewards_needs_no_signer_or_writable_account() {
+        let (user, config, mint) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let ix = get_pending_rewards(&user, &config, &[mint]);
+
+        assert_accounts(
+            &ix,
+            &[
+                readonly(user),
+                readonly(config),
+                readonly(program_pda(&[b"user", config.as_ref(), user.as_ref()])),
+                readonly(program_pda(&[b"stake", mint.as_ref(), config.as_ref()])),
+            ],
+        );
+        args::<instruction::GetPendingRewards>(&ix, "get_pending_rewards");
+    }
+}
@@ -0,0 +1,20 @@
+// Rust client for the nft-staking program
+// Backends, bots and the CLI use these helpers instead of hand-deriving seeds
+// or assembling account lists, so they stay in step with the program
+
+#![allow(deprecated)] // Loader module paths are deprecated upstream but still re-exported by Anchor
+
+pub mod accounts; // Decoders for the program's on-chain accounts
+pub mod instructions; // Typed instruction builders
+pub mod pda; // PDA derivation helpers
+
+// Re-export the helpers at the crate root
+pub use accounts::*;
+pub use instructions::*;
+pub use pda::*;
+
+// Re-export the program crate for its ID, parameter types, accounts and events
+pub use nft_staking::{
+    self, CnftLeaf, ConfigParams, EmissionSegment, LockTier, PendingRewards, ScheduleParams,
+    StakeAccount, StakeConfig, UpdateConfigParams, UserAccount, ID,
+};
@@ -0,0 +1,220 @@
+use anchor_lang::prelude::Pubkey; // Address type shared with the program
+use anchor_lang::solana_program::bpf_loader_upgradeable; // Loader that owns the ProgramData account
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::metadata::mpl_token_metadata; // Token Metadata program ID
+
+// PDA derivation helpers mirroring the `seeds` constraints in the program
+// Each returns the address and its canonical bump
+
+// Pool config: one per collection and pool id
+pub fn config_pda(collection: &Pubkey, pool_id: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"config", collection.as_ref(), &pool_id.to_le_bytes()],
+        &nft_staking::ID,
+    )
+}
+
+// Reward token mint of a pool (mint authority is the config PDA)
+pub fn rewards_mint_pda(config: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"rewards", config.as_ref()], &nft_staking::ID)
+}
+
+// A user's staking account in a pool
+pub fn user_account_pda(config: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"user", config.as_ref(), user.as_ref()], &nft_staking::ID)
+}
+
+// Stake record of an NFT in a pool (`mint` is the Core asset or cNFT asset id for those paths)
+pub fn stake_account_pda(mint: &Pubkey, config: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"stake", mint.as_ref(), config.as_ref()],
+        &nft_staking::ID,
+    )
+}
+
+// Program-wide freeze authority every stakeable Token-2022 mint must name
+pub fn freeze_authority_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"freeze"], &nft_staking::ID)
+}
+
+// Config-owned token account holding a treasury pool's rewards
+pub fn treasury_vault_address(config: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(config, mint, token_program)
+}
+
+// Signer of the self-CPI used by `emit_cpi!`; every instruction takes it
+pub fn event_authority_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"__event_authority"], &nft_staking::ID)
+}
+
+// The program's ProgramData account, checked by `initialize_config`
+pub fn program_data_address() -> Pubkey {
+    Pubkey::find_program_address(&[nft_staking::ID.as_ref()], &bpf_loader_upgradeable::ID).0
+}
+
+// Token Metadata account of a mint
+pub fn metadata_pda(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"metadata", mpl_token_metadata::ID.as_ref(), mint.as_ref()],
+        &mpl_token_metadata::ID,
+    )
+}
+
+// Token Metadata master edition account of a mint
+pub fn master_edition_pda(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"metadata",
+            mpl_token_metadata::ID.as_ref(),
+            mint.as_ref(),
+            b"edition",
+        ],
+        &mpl_token_metadata::ID,
+    )
+}
+
+// Token Metadata token record of a programmable NFT held in `token_account`
+pub fn token_record_pda(mint: &Pubkey, token_account: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"metadata",
+            mpl_token_metadata::ID.as_ref(),
+            mint.as_ref(),
+            b"token_record",
+            token_account.as_ref(),
+        ],
+        &mpl_token_metadata::ID,
+    )
+}
+
+// Bubblegum tree config of `merkle_tree`
+pub fn tree_config_pda(merkle_tree: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[merkle_tree.as_ref()], &mpl_bubblegum::ID)
+}
+
+// Asset id of the compressed NFT minted at `nonce` in `merkle_tree`; it keys the cNFT's stake PDA
+pub fn cnft_asset_id(merkle_tree: &Pubkey, nonce: u64) -> Pubkey {
+    mpl_bubblegum::utils::get_asset_id(merkle_tree, nonce)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::prelude::pubkey; // Literal addresses
+
+    // Addresses derived straight from the seeds in the program's account constraints
+    fn program_pda(seeds: &[&[u8]]) -> (Pubkey, u8) {
+        Pubkey::find_program_address(seeds, &nft_staking::ID)
+    }
+
+    #[test]
+    fn config_pda_uses_collection_and_little_endian_pool_id() {
+        let collection = Pubkey::new_unique();
+        let pool_id: u16 = 0x0102;
+        assert_eq!(
+            config_pda(&collection, pool_id),
+            program_pda(&[b"config", collection.as_ref(), &[0x02, 0x01]])
+        );
+        // Every pool id of a collection gets its own config
+        assert_ne!(config_pda(&collection, 0).0, config_pda(&collection, 1).0);
+    }
+
+    #[test]
+    fn stake_account_pda_puts_mint_before_config() {
+        let mint = Pubkey::new_unique();
+        let config = Pubkey::new_unique();
+        assert_eq!(
+            stake_account_pda(&mint, &config),
+            program_pda(&[b"stake", mint.as_ref(), config.as_ref()])
+        );
+        assert_ne!(
+            stake_account_pda(&mint, &config).0,
+            stake_account_pda(&config, &mint).0
+        );
+    }
+
+    #[test]
+    fn pool_scoped_pdas_match_program_seeds() {
+        let config = Pubkey::new_unique();
+        let user = Pubkey::new_unique();
+        assert_eq!(
+            user_account_pda(&config, &user),
+            program_pda(&[b"user", config.as_ref(), user.as_ref()])
+        );
+        assert_eq!(
+            rewards_mint_pda(&config),
+            program_pda(&[b"rewards", config.as_ref()])
+        );
+        assert_eq!(event_authority_pda(), program_pda(&[b"__event_authority"]));
+    }
+
+    #[test]
+    fn program_wide_pdas_match_known_addresses() {
+        // Pinned for the program ID in `declare_id!`; a changed seed or ID shows up here
+        assert_eq!(
+            nft_staking::ID,
+            pubkey!("6YvXnSvATQbKDtaoSxpenuZmsYwTnFW2ie4CarKpX86r")
+        );
+        assert_eq!(
+            event_authority_pda().0,
+            pubkey!("8HmH5PwHMeDmUW4TgNqhmm2KHMMgDWj6JFchKQzTWNVG")
+        );
+        assert_eq!(
+            freeze_authority_pda().0,
+            pubkey!("149xmUPvVF7tj2u5GwFxEa84NsCEgwbgrWcAjUwGVhaj")
+        );
+        assert_eq!(
+            config_pda(&Pubkey::new_from_array([7; 32]), 0).0,
+            pubkey!("BhJD7kuv3cNXQ6K9TWuVwEXoXDLf9bR1eL3zwBDFvRty")
+        );
+        assert_eq!(freeze_authority_pda(), program_pda(&[b"freeze"]));
+    }
+
+    #[test]
+    fn metaplex_pdas_match_their_programs_seeds() {
+        let mint = Pubkey::new_unique();
+        let token = Pubkey::new_unique();
+        let metadata_seeds: &[&[u8]] =
+            &[b"metadata", mpl_token_metadata::ID.as_ref(), mint.as_ref()];
+        assert_eq!(
+            metadata_pda(&mint),
+            Pubkey::find_program_address(metadata_seeds, &mpl_token_metadata::ID)
+        );
+        assert_eq!(
+            master_edition_pda(&mint),
+            Pubkey::find_program_address(
+                &[metadata_seeds, &[b"edition"]].concat(),
+                &mpl_token_metadata::ID
+            )
+        );
+        assert_eq!(
+            token_record_pda(&mint, &token),
+            Pubkey::find_program_address(
+                &[metadata_seeds, &[b"token_record", token.as_ref()]].concat(),
+                &mpl_token_metadata::ID
+            )
+        );
+
+        let tree = Pubkey::new_unique();
+        assert_eq!(
+            tree_config_pda(&tree),
+            Pubkey::find_program_address(&[tree.as_ref()], &mpl_bubblegum::ID)
+        );
+        assert_eq!(
+            cnft_asset_id(&tree, 5),
+            Pubkey::find_program_address(
+                &[b"asset", tree.as_ref(), &5u64.to_le_bytes()],
+                &mpl_bubblegum::ID
+            )
+            .0
+        );
+    }
+
+    #[test]
+    fn program_data_address_matches_loader() {
+        assert_eq!(
+            program_data_address(),
+            bpf_loader_upgradeable::get_program_data_address(&nft_staking::ID)
+        );
+    }
+}
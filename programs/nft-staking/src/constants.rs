@@ -2,3 +2,37 @@ use anchor_lang::prelude::*;
 
 #[constant]
 pub const SEED: &str = "anchor";
+
+// Bits of `StakeConfig.paused`; each disables one user operation
+#[constant]
+pub const PAUSE_STAKE: u8 = 1 << 0;
+#[constant]
+pub const PAUSE_UNSTAKE: u8 = 1 << 1;
+#[constant]
+pub const PAUSE_CLAIM: u8 = 1 << 2;
+#[constant]
+pub const PAUSE_ALL: u8 = PAUSE_STAKE | PAUSE_UNSTAKE | PAUSE_CLAIM;
+
+// Fixed-point scale of `StakeConfig.acc_reward_per_nft` and `StakeAccount.reward_debt`
+#[constant]
+pub const ACC_PRECISION: u128 = 1_000_000_000_000;
+
+// `LockTier.multiplier_bps` of a 1x reward multiplier
+#[constant]
+pub const MULTIPLIER_BASE: u16 = 10_000;
+
+// Most lock tiers a pool can offer on top of the default tier 0
+pub const MAX_LOCK_TIERS: usize = 4;
+
+// Length of the window `StakeConfig.daily_mint_limit` applies to (seconds)
+#[constant]
+pub const MINT_WINDOW: i64 = 86_400;
+
+// Most rate changes an emission schedule can hold
+pub const MAX_SCHEDULE_SEGMENTS: usize = 8;
+
+// Programs used by Bubblegum V2 trees (compressed NFT staking)
+pub const MPL_ACCOUNT_COMPRESSION_ID: Pubkey =
+    pubkey!("mcmt6YrQEMKw8Mw43FmpRLmf7BqRnFMKmAcbxE3xkAW");
+pub const MPL_NOOP_ID: Pubkey = pubkey!("mnoopTCrg4p8ry25e4bcWA9XZjbNjMTfgYVGGEdRsf3");
+//declare_id!("6YvXnSvATQbKDtaoSxpenuZmsYwTnFW2ie4CarKpX86r");
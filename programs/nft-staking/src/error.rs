@@ -5,7 +5,7 @@ use anchor_lang::prelude::*; // Import Anchor framework essentials
 #[error_code]
 pub enum ErrorCode {
     #[msg("Time has not yet elapsed")] // Error message shown to users
-    TimeNotElapsed, // Thrown when trying to unstake before freeze period ends
+    TimeNotElapsed, // Thrown when trying to unstake before freeze period or tier lock ends
 
     #[msg("Maximum stake limit reached")] // Error message shown to users
     MaxStake, // Thrown when user tries to stake more NFTs than allowed
@@ -15,4 +15,96 @@ pub enum ErrorCode {
 
     #[msg("Arithmetic overflow")] // Error message shown to users
     Overflow, // Thrown when addition would exceed maximum value limits
+
+    #[msg("Points period must be greater than zero")] // Error message shown to users
+    InvalidPointsPeriod, // Thrown when config would accrue over a zero-length period
+
+    #[msg("Invalid stake account")] // Error message shown to users
+    InvalidStakeAccount, // Thrown when a settled stake account is not the user's record for this pool
+
+    #[msg("Signer is not the config admin")] // Error message shown to users
+    Unauthorized, // Thrown when a non-admin tries to change the config
+
+    #[msg("Signer is not the pending admin")] // Error message shown to users
+    NotPendingAdmin, // Thrown when accepting an admin transfer that was not offered to the signer
+
+    #[msg("Signer is not the program upgrade authority")] // Error message shown to users
+    NotUpgradeAuthority, // Thrown when someone other than the deployer initializes the config
+
+    #[msg("NFT metadata has no collection")] // Error message shown to users
+    MissingCollection, // Thrown when staking an NFT that does not belong to any collection
+
+    #[msg("NFT collection does not match the pool collection")] // Error message shown to users
+    CollectionMismatch, // Thrown when staking an NFT from a different collection
+
+    #[msg("NFT collection is not verified")] // Error message shown to users
+    CollectionNotVerified, // Thrown when the collection creator has not verified the NFT
+
+    #[msg("No NFTs are staked")] // Error message shown to users
+    NothingStaked, // Thrown when unstaking while the user has nothing staked
+
+    #[msg("No points to claim")] // Error message shown to users
+    NoPointsToClaim, // Thrown when claiming with zero accumulated points
+
+    #[msg("Signer does not own this stake")] // Error message shown to users
+    WrongOwner, // Thrown when acting on a stake account created by another wallet
+
+    #[msg("Staking pool is paused")] // Error message shown to users
+    PoolPaused, // Thrown when the admin has disabled the requested operation
+
+    #[msg("Unknown pause flags")] // Error message shown to users
+    InvalidPauseFlags, // Thrown when the pause bitfield sets bits outside PAUSE_ALL
+
+    #[msg("NFT is not a programmable NFT")] // Error message shown to users
+    NotProgrammable, // Thrown when a non-pNFT is passed to the pNFT staking path
+
+    #[msg("Account is not a Metaplex Core asset")] // Error message shown to users
+    InvalidCoreAsset, // Thrown when the Core staking path is given a non-asset account
+
+    #[msg("Mint is not a 1-of-1 NFT")] // Error message shown to users
+    InvalidNftMint, // Thrown when a Token-2022 mint has decimals or supply other than 0 and 1
+
+    #[msg("Mint freeze authority is not the program's freeze PDA")] // Error message shown to users
+    InvalidFreezeAuthority, // Thrown when the program cannot freeze a Token-2022 NFT
+
+    #[msg("Instruction does not match the pool's custody mode")] // Error message shown to users
+    WrongCustodyMode, // Thrown when using a freeze-in-place path on a vault pool or vice versa
+
+    #[msg("Batch accounts are missing or do not match their NFT")] // Error message shown to users
+    InvalidBatch, // Thrown when a batch is empty, not split into whole groups, or has a mismatched account
+
+    #[msg("Reward per point must be greater than zero")] // Error message shown to users
+    InvalidRewardRate, // Thrown when the points-to-token conversion rate is set to zero
+
+    #[msg("Instruction does not match the pool's reward mode")] // Error message shown to users
+    WrongRewardMode, // Thrown when minting on a treasury pool, paying from a treasury that does not exist, or initializing it twice
+
+    #[msg("Reward treasury cannot pay out a single point")] // Error message shown to users
+    TreasuryEmpty, // Thrown when the treasury vault holds less than one point's worth of tokens
+
+    #[msg("Amount must be greater than zero")] // Error message shown to users
+    InvalidAmount, // Thrown when depositing zero tokens into the treasury
+
+    #[msg("Treasury token must have decimals")] // Error message shown to users
+    InvalidTreasuryMint, // Thrown when the treasury mint has 0 decimals, so its vault could be a custody NFT vault
+
+    #[msg("Emission rate can only be changed to a non-zero value on emission pools")]
+    // Error message shown to users
+    InvalidEmissionRate, // Thrown when update_config sets an emission rate on a flat pool or sets it to zero
+
+    #[msg("Emission schedule needs an emission pool, increasing segment starts and an end after its start")]
+    // Error message shown to users
+    InvalidSchedule, // Thrown when set_emission_schedule gets a flat pool, unordered or too many segments, or an empty window
+
+    #[msg("Reward mint supply cap or daily mint limit reached")] // Error message shown to users
+    MintLimitReached, // Thrown when a claim cannot mint even one point's worth of tokens
+
+    #[msg("Lock tier does not exist or has a multiplier below 1x")] // Error message shown to users
+    InvalidLockTier, // Thrown when staking in an unknown tier or setting tiers that earn less than no lock
+
+    #[msg("Token account does not hold the NFT")] // Error message shown to users
+    NftNotHeld, // Thrown when the user's Token-2022 account balance is not exactly 1
+
+    #[msg("Stake account is listed more than once")] // Error message shown to users
+    DuplicateStakeAccount, // Thrown when get_pending_rewards is given the same stake record twice
 }
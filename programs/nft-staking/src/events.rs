@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*; // Import Anchor framework essentials
+
+use crate::state::{EmissionSegment, LockTier}; // Schedule entries and lock tiers carried by events
+
+// Events emitted through `emit_cpi!` on every state change
+// Indexers can rebuild pool, user and stake history from transaction data alone;
+// `pool` is always the config PDA and `timestamp` the on-chain unix time
+
+// Emitted when a staking pool is created
+#[event]
+pub struct ConfigInitialized {
+    pub pool: Pubkey,             // Config PDA of the new pool
+    pub collection: Pubkey,       // Collection accepted by the pool
+    pub pool_id: u16,             // Pool id within the collection
+    pub admin: Pubkey,            // Initial admin
+    pub points_per_stake: u8,     // Points per NFT per period
+    pub points_period: u32,       // Period length (seconds)
+    pub max_stake: u8,            // Per-user stake limit
+    pub freeze_period: u32,       // Minimum stake duration (seconds)
+    pub reward_per_point: u64,    // Token base units per claimed point
+    pub emission_per_second: u64, // Points per second shared by staked NFTs (0: flat points)
+    pub max_supply: u64,          // Lifetime reward mint cap (0: uncapped)
+    pub daily_mint_limit: u64,    // Reward mint cap per window (0: unlimited)
+    pub custody: bool,            // Vault custody instead of freeze in place
+    pub timestamp: i64,           // When the pool was created
+}
+
+// Emitted when the admin changes staking parameters (values after the update)
+#[event]
+pub struct ConfigUpdated {
+    pub pool: Pubkey,             // Config PDA
+    pub admin: Pubkey,            // Admin who made the change
+    pub points_per_stake: u8,     // Points per NFT per period
+    pub points_period: u32,       // Period length (seconds)
+    pub max_stake: u8,            // Per-user stake limit
+    pub freeze_period: u32,       // Minimum stake duration (seconds)
+    pub reward_per_point: u64,    // Token base units per claimed point
+    pub emission_per_second: u64, // Points per second shared by staked NFTs (0: flat points)
+    pub max_supply: u64,          // Lifetime reward mint cap (0: uncapped)
+    pub daily_mint_limit: u64,    // Reward mint cap per window (0: unlimited)
+    pub timestamp: i64,           // When the change was made
+}
+
+// Emitted when the admin nominates a successor
+#[event]
+pub struct AdminTransferStarted {
+    pub pool: Pubkey,          // Config PDA
+    pub admin: Pubkey,         // Current admin
+    pub pending_admin: Pubkey, // Nominated admin
+    pub timestamp: i64,        // When the nomination was made
+}
+
+// Emitted when a nominee accepts the admin role
+#[event]
+pub struct AdminTransferred {
+    pub pool: Pubkey,           // Config PDA
+    pub previous_admin: Pubkey, // Admin being replaced
+    pub admin: Pubkey,          // New admin
+    pub timestamp: i64,         // When the transfer completed
+}
+
+// Emitted when the admin replaces the pause bitfield
+#[event]
+pub struct PausedSet {
+    pub pool: Pubkey,   // Config PDA
+    pub admin: Pubkey,  // Admin who made the change
+    pub paused: u8,     // New PAUSE_* bitfield
+    pub timestamp: i64, // When the change was made
+}
+
+// Emitted when the admin toggles emergency mode
+#[event]
+pub struct EmergencySet {
+    pub pool: Pubkey,    // Config PDA
+    pub admin: Pubkey,   // Admin who made the change
+    pub emergency: bool, // New emergency flag
+    pub timestamp: i64,  // When the change was made
+}
+
+// Emitted when a user creates their staking account in a pool
+#[event]
+pub struct UserInitialized {
+    pub pool: Pubkey,   // Config PDA
+    pub owner: Pubkey,  // User wallet
+    pub timestamp: i64, // When the account was created
+}
+
+// Emitted for every NFT staked, by any staking path
+#[event]
+pub struct Staked {
+    pub pool: Pubkey,      // Config PDA
+    pub owner: Pubkey,     // Staker
+    pub mint: Pubkey,      // NFT mint, Core asset or cNFT asset id
+    pub tier: u8,          // Lock tier chosen (0: default)
+    pub amount_staked: u8, // Owner's staked count after this stake
+    pub timestamp: i64,    // When the NFT was staked
+}
+
+// Emitted for every NFT unstaked, by any unstaking path
+#[event]
+pub struct Unstaked {
+    pub pool: Pubkey,      // Config PDA
+    pub owner: Pubkey,     // Staker
+    pub mint: Pubkey,      // NFT mint, Core asset or cNFT asset id
+    pub points: u64,       // Points credited for this NFT (0 in emergency mode)
+    pub amount_staked: u8, // Owner's staked count after this unstake
+    pub timestamp: i64,    // When the NFT was unstaked
+}
+
+// Emitted when a user converts their points into reward tokens
+#[event]
+pub struct Claimed {
+    pub pool: Pubkey,   // Config PDA
+    pub owner: Pubkey,  // Claimer
+    pub settled: u64,   // Points credited from still-staked NFTs before claiming
+    pub points: u64,    // Points spent by the claim
+    pub amount: u64,    // Reward tokens minted or paid from the treasury
+    pub remaining: u64, // Points left unpaid because the treasury ran dry or a mint limit was hit
+    pub timestamp: i64, // When the claim was made
+}
+
+// Emitted when the admin switches a pool to paying rewards from a treasury
+#[event]
+pub struct TreasuryInitialized {
+    pub pool: Pubkey,   // Config PDA
+    pub admin: Pubkey,  // Admin who made the change
+    pub mint: Pubkey,   // Token paid out by claims
+    pub vault: Pubkey,  // Config-owned token account holding the treasury
+    pub timestamp: i64, // When the treasury was set up
+}
+
+// Emitted when tokens are deposited into a pool's treasury
+#[event]
+pub struct TreasuryFunded {
+    pub pool: Pubkey,   // Config PDA
+    pub funder: Pubkey, // Wallet that deposited
+    pub amount: u64,    // Tokens deposited
+    pub balance: u64,   // Treasury balance after the deposit
+    pub timestamp: i64, // When the deposit was made
+}
+
+// Emitted when the admin replaces an emission pool's schedule
+#[event]
+pub struct ScheduleSet {
+    pub pool: Pubkey,                   // Config PDA
+    pub admin: Pubkey,                  // Admin who made the change
+    pub start_time: i64,                // When emission begins
+    pub end_time: Option<i64>,          // When emission stops (None = never)
+    pub segments: Vec<EmissionSegment>, // Rate changes in start order
+    pub timestamp: i64,                 // When the schedule was set
+}
+
+// Emitted when the admin replaces a pool's lock tiers
+#[event]
+pub struct LockTiersSet {
+    pub pool: Pubkey,         // Config PDA
+    pub admin: Pubkey,        // Admin who made the change
+    pub tiers: Vec<LockTier>, // Tiers 1.. in order (tier 0 is always the default)
+    pub timestamp: i64,       // When the tiers were set
+}
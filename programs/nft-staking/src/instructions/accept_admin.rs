@@ -0,0 +1,39 @@
+#![allow(unexpected_cfgs)] // Allow compiler warnings for unrecognized configuration flags
+
+use anchor_lang::prelude::*; // Import essential Anchor framework items
+
+use crate::error::ErrorCode; // Import custom error types
+use crate::events::AdminTransferred; // Event recording the completed transfer
+use crate::state::StakeConfig; // Import the global configuration structure
+
+// Account validation struct for completing a two-step admin transfer
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    pub new_admin: Signer<'info>, // The wallet nominated by `transfer_admin`
+
+    #[account(
+        mut, // Account will be modified (admin is replaced)
+        seeds = [b"config".as_ref(), config.collection.as_ref(), config.pool_id.to_le_bytes().as_ref()], // Pool config PDA seeds
+        bump = config.bump, // Use stored bump from config
+        constraint = config.pending_admin == Some(new_admin.key()) @ ErrorCode::NotPendingAdmin, // Must match the nomination
+    )]
+    pub config: Account<'info, StakeConfig>, // Pool staking configuration
+}
+
+// Implementation block containing the acceptance logic
+impl<'info> AcceptAdmin<'info> {
+    // Function to make the nominated wallet the config admin
+    pub fn accept_admin(&mut self) -> Result<AdminTransferred> {
+        let previous_admin = self.config.admin; // Reported in the event
+        self.config.admin = self.new_admin.key(); // Nominee becomes the admin
+        self.config.pending_admin = None; // Clear the completed nomination
+
+        Ok(AdminTransferred {
+            pool: self.config.key(),
+            previous_admin,
+            admin: self.new_admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        })
+    }
+}
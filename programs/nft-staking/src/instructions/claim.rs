@@ -1,12 +1,15 @@
-// Import custom error types and state structures
+// Import pause flags, custom error types and state structures
+use crate::constants::PAUSE_CLAIM;
 use crate::error::ErrorCode; // Fixed import path for error types
+use crate::events::Claimed; // Event recording the claim
 use crate::state::*; // Import all state structures
                      // Import essential Anchor and SPL Token types
 use anchor_lang::prelude::*;
-use anchor_spl::token::{mint_to, Mint, MintTo, Token, TokenAccount}; // For minting reward tokens
+use anchor_spl::token_interface::{mint_to, Mint, MintTo, TokenAccount, TokenInterface}; // For minting reward tokens
 
 // Account validation struct for claiming staking rewards
 // Allows users to mint reward tokens based on their accumulated points
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Claim<'info> {
     /// User claiming their staking rewards
@@ -15,50 +18,81 @@ pub struct Claim<'info> {
 
     /// User's staking account (holds accumulated points)
     #[account(
-        mut, // Account will be modified (points reset to zero after claiming)
-        seeds = [b"user", user.key.as_ref()], // User's staking account PDA
+        mut, // Account will be modified (paid points are deducted)
+        seeds = [b"user", config.key().as_ref(), user.key.as_ref()], // User's staking account PDA for this pool
         bump = user_account.bump // Use stored bump from user account
     )]
     pub user_account: Account<'info, UserAccount>, // User's staking statistics and points
 
-    /// Global staking configuration
+    /// Pool staking configuration
     #[account(
-        seeds = [b"config"], // Global config PDA seed
-        bump = config.bump // Use stored bump from config
+        mut, // Account will be modified (reward index and minted totals are brought up to date)
+        seeds = [b"config", config.collection.as_ref(), config.pool_id.to_le_bytes().as_ref()], // Pool config PDA seeds
+        bump = config.bump, // Use stored bump from config
+        constraint = !config.is_paused(PAUSE_CLAIM) @ ErrorCode::PoolPaused, // Claiming must be enabled
+        constraint = config.treasury_mint.is_none() @ ErrorCode::WrongRewardMode, // Pool mints its rewards
     )]
-    pub config: Account<'info, StakeConfig>, // Global staking configuration
+    pub config: Account<'info, StakeConfig>, // Pool staking configuration
 
     /// Reward token mint
     #[account(
         mut, // Account will be modified (tokens will be minted)
         seeds = [b"rewards", config.key().as_ref()], // Rewards mint PDA using config as seed
-        bump = config.rewards_bump // Use stored bump from config
+        bump = config.rewards_bump, // Use stored bump from config
+        mint::token_program = token_program, // Mint must belong to the given token program
     )]
-    pub reward_mint: Account<'info, Mint>, // The mint for reward tokens
+    pub reward_mint: InterfaceAccount<'info, Mint>, // The mint for reward tokens
 
     /// User's associated token account to receive reward tokens
     #[account(
         mut, // Account will be modified (receives newly minted tokens)
         associated_token::mint = reward_mint, // Must be ATA for the reward token mint
-        associated_token::authority = user // Must be owned by the user
+        associated_token::authority = user, // Must be owned by the user
+        associated_token::token_program = token_program, // ATA of the reward mint's token program
     )]
-    pub user_reward_ata: Account<'info, TokenAccount>, // User's token account for reward tokens
+    pub user_reward_ata: InterfaceAccount<'info, TokenAccount>, // User's token account for reward tokens
 
     /// Programs and sysvars
-    pub token_program: Program<'info, Token>, // SPL Token program for minting operations
+    pub token_program: Interface<'info, TokenInterface>, // SPL Token or Token-2022 program for minting
 }
 
 // Implementation block containing the claiming logic
 impl<'info> Claim<'info> {
     // Function to claim accumulated reward points as tokens
-    pub fn claim(&mut self) -> Result<()> {
-        let amount = self.user_account.points; // Get user's accumulated points
+    // `stake_accounts` are the user's still-staked records to settle first
+    // Points over the supply cap or daily mint limit stay on the user account
+    pub fn claim(&mut self, stake_accounts: &'info [AccountInfo<'info>]) -> Result<Claimed> {
+        // Credit points accrued by NFTs that are still staked
+        let now = Clock::get()?.unix_timestamp; // Get current timestamp
+        let settled = settle(
+            &mut self.config,
+            self.user.key,
+            &mut self.user_account,
+            stake_accounts,
+            now,
+        )?;
+
+        let points = self.user_account.points; // Get user's accumulated points
 
         // Don't allow claiming if no points
-        require!(amount > 0, ErrorCode::MaxStake); // Reusing MaxStake error for no rewards validation
+        require!(points > 0, ErrorCode::NoPointsToClaim);
+
+        // Pay only whole points the supply cap and daily limit still allow
+        let affordable = self.config.mint_allowance(now) / self.config.reward_per_point;
+        let paid = points.min(affordable);
+        require!(paid > 0, ErrorCode::MintLimitReached);
+
+        // Convert points to reward token base units at the pool's rate
+        let amount = self.config.reward_amount(paid)?;
 
         // Mint reward tokens to user's ATA
-        let seeds: &[&[u8]] = &[b"config", &[self.config.bump]]; // Config PDA seeds for signing
+        let pool_id = self.config.pool_id.to_le_bytes();
+        let seeds: &[&[u8]] = &[
+            b"config",                       // Config PDA seed
+            self.config.collection.as_ref(), // Pool collection
+            &pool_id,                        // Pool id within the collection
+            &[self.config.bump],             // PDA bump
+        ]; // Config PDA seeds for signing
         let signer = &[seeds]; // Format for CPI signing
 
         // Prepare accounts for minting tokens
@@ -72,12 +106,60 @@ impl<'info> Claim<'info> {
         let cpi_ctx =
             CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, signer);
 
-        // Mint tokens equal to accumulated points (converted to u64)
-        mint_to(cpi_ctx, amount.into())?; // Mint reward tokens to user
+        // Mint the converted amount of reward tokens
+        mint_to(cpi_ctx, amount)?; // Mint reward tokens to user
+        self.config.record_mint(amount)?; // Count the tokens against the mint limits
 
-        // Reset user points after claiming
-        self.user_account.points = 0; // Clear points since they've been claimed as tokens
+        // Keep points over the limits queued for a later claim
+        let remaining = points.checked_sub(paid).ok_or(ErrorCode::Underflow)?;
+        self.user_account.points = remaining;
 
-        Ok(()) // Return success
+        Ok(Claimed {
+            pool: self.config.key(),
+            owner: self.user.key(),
+            settled,
+            points: paid,
+            amount,
+            remaining,
+            timestamp: now,
+        })
     }
 }
+
+// Credit accrued points from each of the user's stake accounts
+// Shared by both claim paths; returns the total points credited
+pub(crate) fn settle<'info>(
+    config: &mut Account<'info, StakeConfig>,
+    user: &Pubkey,
+    user_account: &mut UserAccount,
+    stake_accounts: &'info [AccountInfo<'info>],
+    now: i64,
+) -> Result<u64> {
+    // Unsettled points are forfeited while the pool is in emergency mode
+    if config.emergency {
+        return Ok(0);
+    }
+
+    let mut settled: u64 = 0;
+    let config_key = config.key();
+    config.update_pool(now)?; // Bring the reward index up to date once for every stake
+
+    for info in stake_accounts {
+        // Deserialize and check the account is a StakeAccount owned by this program
+        let mut stake_account = Account::<StakeAccount>::try_from(info)?;
+
+        // Must be writable, staked by this user, and the stake PDA for this config
+        require!(info.is_writable, ErrorCode::InvalidStakeAccount);
+        stake_account.verify(info.key, user, &config_key)?;
+
+        // Move the checkpoint forward and credit the earned points
+        let earned = stake_account.accrue(config, now)?;
+        user_account.credit(earned)?;
+        settled = settled.checked_add(earned).ok_or(ErrorCode::Overflow)?;
+
+        // Persist the new checkpoint (remaining accounts are not saved automatically)
+        stake_account.exit(&crate::ID)?;
+    }
+
+    Ok(settled)
+}
@@ -0,0 +1,125 @@
+#![allow(unexpected_cfgs)] // Allow compiler warnings for unrecognized configuration flags
+
+use anchor_lang::prelude::*; // Import essential Anchor framework items
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+}; // Import SPL Token / Token-2022 interface types
+
+use crate::constants::PAUSE_CLAIM;
+use crate::error::ErrorCode; // Import custom error types
+use crate::events::Claimed; // Event recording the claim
+use crate::instructions::claim::settle; // Shared stake settlement
+use crate::state::*; // Import all state structures
+
+// Account validation struct for claiming rewards from a pool's treasury
+// Treasury pools pay out deposited tokens instead of minting new ones
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimTreasury<'info> {
+    #[account(mut)] // Account can be modified (pays transaction fees)
+    pub user: Signer<'info>, // The user claiming their reward tokens
+
+    #[account(
+        mut, // Account will be modified (paid points are deducted)
+        seeds = [b"user", config.key().as_ref(), user.key.as_ref()], // User's staking account PDA for this pool
+        bump = user_account.bump // Use stored bump from user account
+    )]
+    pub user_account: Account<'info, UserAccount>, // User's staking statistics and points
+
+    #[account(
+        mut, // Account will be modified (pool reward index is brought up to date)
+        seeds = [b"config", config.collection.as_ref(), config.pool_id.to_le_bytes().as_ref()], // Pool config PDA seeds
+        bump = config.bump, // Use stored bump from config
+        constraint = !config.is_paused(PAUSE_CLAIM) @ ErrorCode::PoolPaused, // Claiming must be enabled
+        constraint = config.treasury_mint == Some(treasury_mint.key()) @ ErrorCode::WrongRewardMode, // Pool pays from a treasury of this token
+    )]
+    pub config: Account<'info, StakeConfig>, // Pool staking configuration
+
+    #[account(
+        mint::token_program = token_program, // Mint must belong to the given token program
+    )]
+    pub treasury_mint: InterfaceAccount<'info, Mint>, // Token held by the treasury
+
+    #[account(
+        mut, // Account will be modified (rewards are transferred out)
+        associated_token::mint = treasury_mint, // Vault holds the treasury token
+        associated_token::authority = config, // Config PDA owns the vault
+        associated_token::token_program = token_program, // ATA of the mint's token program
+    )]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>, // Pool treasury
+
+    #[account(
+        mut, // Account will be modified (receives the rewards)
+        associated_token::mint = treasury_mint, // Must be ATA for the treasury token
+        associated_token::authority = user, // Must be owned by the user
+        associated_token::token_program = token_program, // ATA of the mint's token program
+    )]
+    pub user_reward_ata: InterfaceAccount<'info, TokenAccount>, // User's token account for rewards
+
+    /// Programs
+    pub token_program: Interface<'info, TokenInterface>, // SPL Token or Token-2022 program of the treasury mint
+}
+
+// Implementation block containing the treasury claiming logic
+impl<'info> ClaimTreasury<'info> {
+    // Function to pay the user's points out of the treasury
+    // When the treasury cannot cover every point, as many whole points as it can
+    // afford are paid and the rest stay on the user account for a later claim
+    pub fn claim_treasury(
+        &mut self,
+        stake_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<Claimed> {
+        // Credit points accrued by NFTs that are still staked
+        let now = Clock::get()?.unix_timestamp; // Get current timestamp
+        let settled = settle(
+            &mut self.config,
+            self.user.key,
+            &mut self.user_account,
+            stake_accounts,
+            now,
+        )?;
+
+        let points = self.user_account.points; // Get user's accumulated points
+        require!(points > 0, ErrorCode::NoPointsToClaim);
+
+        // Pay only whole points the treasury can cover at the pool's rate
+        let affordable = self.treasury_vault.amount / self.config.reward_per_point;
+        let paid = points.min(affordable);
+        require!(paid > 0, ErrorCode::TreasuryEmpty);
+        let amount = self.config.reward_amount(paid)?;
+
+        // Transfer from the vault, signed by the config PDA
+        let pool_id = self.config.pool_id.to_le_bytes();
+        let seeds: &[&[u8]] = &[
+            b"config",                       // Config PDA seed
+            self.config.collection.as_ref(), // Pool collection
+            &pool_id,                        // Pool id within the collection
+            &[self.config.bump],             // PDA bump
+        ]; // Config PDA seeds for signing
+        let signer = &[seeds]; // Format for CPI signing
+
+        let cpi_accounts = TransferChecked {
+            from: self.treasury_vault.to_account_info(),
+            mint: self.treasury_mint.to_account_info(),
+            to: self.user_reward_ata.to_account_info(),
+            authority: self.config.to_account_info(), // Config PDA owns the vault
+        };
+        let cpi_ctx =
+            CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, signer);
+        transfer_checked(cpi_ctx, amount, self.treasury_mint.decimals)?;
+
+        // Keep the unpaid points for when the treasury is refilled
+        let remaining = points.checked_sub(paid).ok_or(ErrorCode::Underflow)?;
+        self.user_account.points = remaining;
+
+        Ok(Claimed {
+            pool: self.config.key(),
+            owner: self.user.key(),
+            settled,
+            points: paid,
+            amount,
+            remaining,
+            timestamp: now,
+        })
+    }
+}
@@ -0,0 +1,78 @@
+#![allow(unexpected_cfgs)] // Allow compiler warnings for unrecognized configuration flags
+
+use anchor_lang::prelude::*; // Import essential Anchor framework items
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+}; // Import SPL Token / Token-2022 interface types
+
+use crate::error::ErrorCode; // Import custom error types
+use crate::events::TreasuryFunded; // Event recording the deposit
+use crate::state::StakeConfig; // Import the global configuration structure
+
+// Account validation struct for depositing reward tokens into a pool's treasury
+// Anyone may top up a treasury; only claims can take tokens out
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FundTreasury<'info> {
+    pub funder: Signer<'info>, // Wallet depositing the tokens
+
+    #[account(
+        seeds = [b"config".as_ref(), config.collection.as_ref(), config.pool_id.to_le_bytes().as_ref()], // Pool config PDA seeds
+        bump = config.bump, // Use stored bump from config
+        constraint = config.treasury_mint == Some(treasury_mint.key()) @ ErrorCode::WrongRewardMode, // Pool pays from a treasury of this token
+    )]
+    pub config: Account<'info, StakeConfig>, // Pool staking configuration
+
+    #[account(
+        mint::token_program = token_program, // Mint must belong to the given token program
+    )]
+    pub treasury_mint: InterfaceAccount<'info, Mint>, // Token held by the treasury
+
+    #[account(
+        mut, // Account will be modified (tokens are transferred out)
+        token::mint = treasury_mint, // Must hold the treasury token
+        token::authority = funder, // Must be owned by the funder
+        token::token_program = token_program, // Token account of the mint's token program
+    )]
+    pub funder_ata: InterfaceAccount<'info, TokenAccount>, // Funder's token account
+
+    #[account(
+        mut, // Account will be modified (receives the deposit)
+        associated_token::mint = treasury_mint, // Vault holds the treasury token
+        associated_token::authority = config, // Config PDA owns the vault
+        associated_token::token_program = token_program, // ATA of the mint's token program
+    )]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>, // Pool treasury
+
+    /// Programs
+    pub token_program: Interface<'info, TokenInterface>, // SPL Token or Token-2022 program of the treasury mint
+}
+
+// Implementation block containing the deposit logic
+impl<'info> FundTreasury<'info> {
+    // Function to move `amount` treasury tokens from the funder into the vault
+    pub fn fund_treasury(&mut self, amount: u64) -> Result<TreasuryFunded> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+
+        // Funder signs for their own token account
+        let cpi_accounts = TransferChecked {
+            from: self.funder_ata.to_account_info(),
+            mint: self.treasury_mint.to_account_info(),
+            to: self.treasury_vault.to_account_info(),
+            authority: self.funder.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+        transfer_checked(cpi_ctx, amount, self.treasury_mint.decimals)?;
+
+        // Report the balance after the deposit (transfer fees may leave less than `amount`)
+        self.treasury_vault.reload()?;
+
+        Ok(TreasuryFunded {
+            pool: self.config.key(),
+            funder: self.funder.key(),
+            amount,
+            balance: self.treasury_vault.amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        })
+    }
+}
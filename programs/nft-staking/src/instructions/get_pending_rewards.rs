@@ -0,0 +1,77 @@
+#![allow(unexpected_cfgs)] // Allow compiler warnings for unrecognized configuration flags
+
+use anchor_lang::prelude::*; // Import essential Anchor framework items
+
+use crate::error::ErrorCode; // Import custom error types
+use crate::state::{StakeAccount, StakeConfig, UserAccount}; // Import the pool, user and stake structures
+
+// Account validation struct for the read-only pending rewards view
+// Meant to be run through `simulateTransaction`; nothing is written
+#[derive(Accounts)]
+pub struct GetPendingRewards<'info> {
+    /// CHECK: Only used as a seed and to match stake account owners; need not sign
+    pub user: UncheckedAccount<'info>, // Wallet whose rewards are computed
+
+    #[account(
+        seeds = [b"config".as_ref(), config.collection.as_ref(), config.pool_id.to_le_bytes().as_ref()], // Pool config PDA seeds
+        bump = config.bump // Use stored bump from config
+    )]
+    pub config: Account<'info, StakeConfig>, // Pool holding the accrual parameters
+
+    #[account(
+        seeds = [b"user".as_ref(), config.key().as_ref(), user.key().as_ref()], // User's staking account PDA for this pool
+        bump = user_account.bump // Use stored bump from user account
+    )]
+    pub user_account: Account<'info, UserAccount>, // Points already credited to the user
+}
+
+// Points a user would receive if they settled and claimed now
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PendingRewards {
+    pub credited: u64,  // Points already on the user account
+    pub pending: u64,   // Points accrued by the given stake accounts but not yet credited
+    pub total: u64,     // credited + pending
+    pub amount: u64,    // Reward token base units `total` converts to
+    pub timestamp: i64, // Clock used for the computation
+}
+
+// Implementation block containing the view logic
+impl<'info> GetPendingRewards<'info> {
+    // Function to compute the user's rewards at the current clock
+    // `stake_accounts` are the records to include: all of the user's, or a single one
+    pub fn get_pending_rewards(
+        &self,
+        stake_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<PendingRewards> {
+        let now = Clock::get()?.unix_timestamp; // Get current timestamp
+        let config_key = self.config.key();
+
+        let mut pending: u64 = 0;
+        for (index, info) in stake_accounts.iter().enumerate() {
+            // Nothing is written back here, so a repeated record would be counted twice
+            require!(
+                stake_accounts[..index]
+                    .iter()
+                    .all(|seen| seen.key != info.key),
+                ErrorCode::DuplicateStakeAccount
+            );
+
+            // Deserialize and check the account is a StakeAccount owned by this program
+            let stake_account = Account::<StakeAccount>::try_from(info)?;
+            stake_account.verify(info.key, self.user.key, &config_key)?;
+
+            let earned = stake_account.pending(&self.config, now)?;
+            pending = pending.checked_add(earned).ok_or(ErrorCode::Overflow)?;
+        }
+
+        let credited = self.user_account.points;
+        let total = credited.checked_add(pending).ok_or(ErrorCode::Overflow)?;
+        Ok(PendingRewards {
+            credited,
+            pending,
+            total,
+            amount: self.config.reward_amount(total)?,
+            timestamp: now,
+        })
+    }
+}
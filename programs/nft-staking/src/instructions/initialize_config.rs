@@ -1,25 +1,39 @@
 #![allow(unexpected_cfgs)] // Allow compiler warnings for unrecognized configuration flags
 
 use anchor_lang::prelude::*; // Import essential Anchor framework items
-use anchor_spl::token::{Mint, Token}; // Import SPL Token program types
+use anchor_lang::solana_program::bpf_loader_upgradeable; // Loader that owns the ProgramData account
+use anchor_spl::token_interface::{Mint, TokenInterface}; // Import SPL Token / Token-2022 interface types
 
+use crate::error::ErrorCode; // Import custom error types
+use crate::events::ConfigInitialized; // Event recording the new pool
 use crate::state::StakeConfig; // Import the global configuration structure
 
 // Account validation struct for initializing the staking program configuration
 // This defines what accounts must be provided and how they should be validated
+// Only the program's upgrade authority may initialize, so a fresh deployment cannot be front-run
+#[event_cpi]
 #[derive(Accounts)]
+#[instruction(collection: Pubkey, pool_id: u16)]
 pub struct InitializeConfig<'info> {
     #[account(mut)] // Account can be modified (will pay for account creation)
     pub admin: Signer<'info>, // The admin wallet that's setting up the program
 
+    #[account(
+        seeds = [crate::ID.as_ref()], // ProgramData PDA is derived from the program ID
+        seeds::program = bpf_loader_upgradeable::ID, // Derived under the upgradeable loader
+        bump, // Anchor finds the canonical bump automatically
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ ErrorCode::NotUpgradeAuthority, // Admin must be the deployer
+    )]
+    pub program_data: Account<'info, ProgramData>, // This program's upgradeable loader data
+
     #[account(
         init, // Create a new account
         payer = admin, // Admin pays the rent for account creation
-        seeds = [b"config".as_ref()], // PDA seed to generate deterministic address
+        seeds = [b"config".as_ref(), collection.as_ref(), pool_id.to_le_bytes().as_ref()], // One pool per collection and pool id
         bump, // Anchor finds the canonical bump seed automatically
         space = 8 + StakeConfig::INIT_SPACE, // 8 bytes for discriminator + struct size
     )]
-    pub config: Account<'info, StakeConfig>, // The global config account being created
+    pub config: Account<'info, StakeConfig>, // The pool config account being created
 
     #[account(
         init_if_needed, // Create only if account doesn't exist yet
@@ -28,31 +42,92 @@ pub struct InitializeConfig<'info> {
         bump, // Anchor finds the canonical bump seed automatically
         mint::decimals = 6, // Reward token will have 6 decimal places
         mint::authority = config, // Config PDA will be the mint authority
+        mint::token_program = token_program, // Created under the given token program
     )]
-    pub rewards_mint: Account<'info, Mint>, // Mint for reward tokens users can claim
+    pub rewards_mint: InterfaceAccount<'info, Mint>, // Mint for reward tokens users can claim
     pub system_program: Program<'info, System>, // Solana system program for account creation
-    pub token_program: Program<'info, Token>,   // SPL Token program for mint operations
+    pub token_program: Interface<'info, TokenInterface>, // SPL Token or Token-2022 program for the rewards mint
+}
+
+// Tunable pool parameters supplied at initialization
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ConfigParams {
+    pub points_per_stake: u8,     // How many points earned per staking period
+    pub points_period: u32,       // Length of one staking period (seconds)
+    pub max_stake: u8,            // Maximum NFTs a user can stake at once
+    pub freeze_period: u32,       // Minimum time NFTs must stay staked (seconds)
+    pub reward_per_point: u64,    // Reward token base units minted per claimed point
+    pub emission_per_second: u64, // Points per second shared by all staked NFTs (0: flat points_per_stake)
+    pub max_supply: u64,          // Most reward tokens claims can ever mint (0: uncapped)
+    pub daily_mint_limit: u64, // Most reward tokens claims can mint per MINT_WINDOW (0: unlimited)
+    pub custody: bool,         // Hold staked NFTs in a vault instead of freezing them in place
 }
 
 // Implementation block containing the actual instruction logic
 impl<'info> InitializeConfig<'info> {
-    // Function to initialize the global staking configuration
+    // Function to initialize a staking pool's configuration
     pub fn initialize_config(
         &mut self,
-        points_per_stake: u8,
-        max_stake: u8,
-        freeze_period: u32,
+        collection: Pubkey,
+        pool_id: u16,
+        params: ConfigParams,
         bumps: &InitializeConfigBumps,
-    ) -> Result<()> {
+    ) -> Result<ConfigInitialized> {
+        // Points accrue per whole period, so the period cannot be empty
+        require!(params.points_period > 0, ErrorCode::InvalidPointsPeriod);
+        // A zero rate would burn points on claim without minting anything
+        require!(params.reward_per_point > 0, ErrorCode::InvalidRewardRate);
+
+        let now = Clock::get()?.unix_timestamp;
+
         // Set the configuration data in the newly created account
         self.config.set_inner(StakeConfig {
-            points_per_stake,                 // How many points earned per staking period
-            max_stake,                        // Maximum NFTs a user can stake at once
-            freeze_period,                    // Minimum time NFTs must stay staked (seconds)
+            collection,                                      // Collection accepted by this pool
+            pool_id,                 // Distinguishes pools for the same collection
+            admin: self.admin.key(), // Signer becomes the config admin
+            pending_admin: None,     // No admin transfer in progress
+            points_per_stake: params.points_per_stake, // How many points earned per staking period
+            points_period: params.points_period, // Length of one staking period (seconds)
+            max_stake: params.max_stake, // Maximum NFTs a user can stake at once
+            freeze_period: params.freeze_period, // Minimum time NFTs must stay staked (seconds)
+            reward_per_point: params.reward_per_point, // Token base units per claimed point
+            treasury_mint: None,     // Claims mint new rewards until a treasury is set up
+            emission_per_second: params.emission_per_second, // 0 keeps flat per-NFT points
+            acc_reward_per_nft: 0,   // Nothing emitted yet
+            last_reward_time: now,   // Emission starts now
+            total_staked: 0,         // No NFTs staked yet
+            total_weight: 0,         // No multiplier weight staked yet
+            start_time: now,         // Emission window opens immediately
+            end_time: None,          // and stays open until a schedule closes it
+            schedule: Vec::new(),    // Constant emission_per_second
+            max_supply: params.max_supply, // Lifetime mint cap
+            daily_mint_limit: params.daily_mint_limit, // Per-window mint cap
+            total_minted: 0,         // Nothing minted yet
+            mint_window_start: now,  // First mint window opens now
+            window_minted: 0,        // Nothing minted in it yet
+            lock_tiers: Vec::new(),  // Only the default tier until the admin adds some
+            paused: 0,               // Every operation starts enabled
+            emergency: false,        // Pool starts in normal mode
+            custody: params.custody, // Fixed for the pool's lifetime
             rewards_bump: bumps.rewards_mint, // Store the rewards mint PDA bump
-            bump: bumps.config,               // Store this config account's PDA bump
+            bump: bumps.config,      // Store this config account's PDA bump
         });
 
-        Ok(()) // Return success
+        Ok(ConfigInitialized {
+            pool: self.config.key(),
+            collection,
+            pool_id,
+            admin: self.admin.key(),
+            points_per_stake: params.points_per_stake,
+            points_period: params.points_period,
+            max_stake: params.max_stake,
+            freeze_period: params.freeze_period,
+            reward_per_point: params.reward_per_point,
+            emission_per_second: params.emission_per_second,
+            max_supply: params.max_supply,
+            daily_mint_limit: params.daily_mint_limit,
+            custody: params.custody,
+            timestamp: now,
+        })
     }
 }
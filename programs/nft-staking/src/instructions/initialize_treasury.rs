@@ -0,0 +1,67 @@
+#![allow(unexpected_cfgs)] // Allow compiler warnings for unrecognized configuration flags
+
+use anchor_lang::prelude::*; // Import essential Anchor framework items
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+}; // Import token interface and associated token types
+
+use crate::error::ErrorCode; // Import custom error types
+use crate::events::TreasuryInitialized; // Event recording the new reward mode
+use crate::state::StakeConfig; // Import the global configuration structure
+
+// Account validation struct for switching a pool to treasury-funded rewards
+// Claims then transfer an existing token out of a config-owned vault instead of
+// minting the pool's own reward token, so total payouts are capped by deposits
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeTreasury<'info> {
+    #[account(mut)] // Account can be modified (pays for vault creation)
+    pub admin: Signer<'info>, // The current config admin
+
+    #[account(
+        mut, // Account will be modified (treasury mint is recorded)
+        seeds = [b"config".as_ref(), config.collection.as_ref(), config.pool_id.to_le_bytes().as_ref()], // Pool config PDA seeds
+        bump = config.bump, // Use stored bump from config
+        has_one = admin @ ErrorCode::Unauthorized, // Only the stored admin may change the reward mode
+        constraint = config.treasury_mint.is_none() @ ErrorCode::WrongRewardMode, // Treasury can only be set up once
+    )]
+    pub config: Account<'info, StakeConfig>, // Pool staking configuration
+
+    #[account(
+        mint::token_program = token_program, // Mint must belong to the given token program
+        constraint = treasury_mint.decimals > 0 @ ErrorCode::InvalidTreasuryMint, // A 0-decimal mint could be a stakeable NFT sharing the custody vault ATA
+    )]
+    pub treasury_mint: InterfaceAccount<'info, Mint>, // Existing token claims will pay out
+
+    #[account(
+        init_if_needed, // Anyone can create an ATA, so tolerate an existing empty vault
+        payer = admin, // Admin pays for vault creation
+        associated_token::mint = treasury_mint, // Vault holds the treasury token
+        associated_token::authority = config, // Config PDA owns the vault
+        associated_token::token_program = token_program, // ATA of the mint's token program
+    )]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>, // Pool treasury
+
+    /// Programs
+    pub system_program: Program<'info, System>, // Required for vault creation
+    pub token_program: Interface<'info, TokenInterface>, // SPL Token or Token-2022 program of the treasury mint
+    pub associated_token_program: Program<'info, AssociatedToken>, // Required for vault creation
+}
+
+// Implementation block containing the reward mode switch
+impl<'info> InitializeTreasury<'info> {
+    // Function to make the pool pay claims from its treasury from now on
+    // Points already credited are paid from the treasury too
+    pub fn initialize_treasury(&mut self) -> Result<TreasuryInitialized> {
+        self.config.treasury_mint = Some(self.treasury_mint.key()); // Claims now use claim_treasury
+
+        Ok(TreasuryInitialized {
+            pool: self.config.key(),
+            admin: self.admin.key(),
+            mint: self.treasury_mint.key(),
+            vault: self.treasury_vault.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        })
+    }
+}
@@ -2,19 +2,27 @@
 
 use anchor_lang::prelude::*; // Import essential Anchor framework items
 
-use crate::state::UserAccount; // Import the user account data structure
+use crate::events::UserInitialized; // Event recording the new user account
+use crate::state::{StakeConfig, UserAccount}; // Import the pool config and user account structures
 
 // Account validation struct for creating a user's staking account
-// This must be called before a user can stake any NFTs
+// This must be called once per pool before a user can stake any NFTs in it
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Initialize<'info> {
     #[account(mut)] // Account can be modified (will pay for account creation)
     pub user: Signer<'info>, // The user wallet creating their staking account
 
+    #[account(
+        seeds = [b"config".as_ref(), config.collection.as_ref(), config.pool_id.to_le_bytes().as_ref()], // Pool config PDA seeds
+        bump = config.bump // Use stored bump from config
+    )]
+    pub config: Account<'info, StakeConfig>, // Pool the user is joining
+
     #[account(
         init, // Create a new account
         payer = user, // User pays the rent for their own account creation
-        seeds = [b"user".as_ref(), user.key().as_ref()], // PDA using user's pubkey as seed
+        seeds = [b"user".as_ref(), config.key().as_ref(), user.key().as_ref()], // PDA using pool and user's pubkey as seeds
         bump, // Anchor finds the canonical bump seed automatically
         space = 8 + UserAccount::INIT_SPACE, // 8 bytes discriminator + UserAccount struct
     )]
@@ -25,7 +33,7 @@ pub struct Initialize<'info> {
 // Implementation block containing the instruction logic
 impl<'info> Initialize<'info> {
     // Function to initialize a user's staking account with default values
-    pub fn init_user(&mut self, bumps: &InitializeBumps) -> Result<()> {
+    pub fn init_user(&mut self, bumps: &InitializeBumps) -> Result<UserInitialized> {
         // Set the initial data for the user's staking account
         self.user_account.set_inner(UserAccount {
             points: 0,                // Start with zero reward points
@@ -33,6 +41,10 @@ impl<'info> Initialize<'info> {
             bump: bumps.user_account, // Store the PDA bump for future lookups
         });
 
-        Ok(()) // Return success
+        Ok(UserInitialized {
+            pool: self.config.key(),
+            owner: self.user.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        })
     }
 }
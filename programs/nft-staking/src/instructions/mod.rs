@@ -1,15 +1,61 @@
 // Instructions module - contains all the instruction handlers for the staking program
 // Each file represents a different operation users can perform
 
+pub mod accept_admin; // Completes a two-step admin transfer
 pub mod claim;
+pub mod claim_treasury; // Pays points out of a pool's treasury
+pub mod fund_treasury; // Deposits reward tokens into a pool's treasury
+pub mod get_pending_rewards; // Read-only view of a user's claimable points
 pub mod initialize_config; // Admin function to set up the global staking parameters
+pub mod initialize_treasury; // Admin function to switch a pool to treasury-funded rewards
 pub mod initialize_user_accounts; // Creates a user's personal staking account
+pub mod set_emergency; // Admin function to toggle emergency unstaking
+pub mod set_emission_schedule; // Admin function to bound and step an emission pool's rate
+pub mod set_lock_tiers; // Admin function to offer lock tiers with reward multipliers
+pub mod set_paused; // Admin function to pause individual operations
 pub mod stake; // Stakes an NFT and starts earning rewards
+pub mod stake_cnft; // Stakes a compressed NFT via Bubblegum delegate + freeze
+pub mod stake_core; // Stakes a Metaplex Core asset via a FreezeDelegate plugin
+pub mod stake_many; // Stakes several NFTs in one instruction
+pub mod stake_pnft; // Stakes a programmable NFT via Token Metadata delegate + lock
+pub mod stake_token22; // Stakes a Token-2022 NFT by freezing it with the pool's freeze authority
+pub mod stake_vault; // Stakes an NFT by moving it into the pool vault
+pub mod transfer_admin; // Nominates a new config admin
 pub mod unstake; // Unstakes an NFT and claims earned rewards // Claims accumulated reward points as tokens
+pub mod unstake_cnft; // Thaws a compressed NFT and revokes the stake delegate
+pub mod unstake_core; // Thaws a Metaplex Core asset and removes its FreezeDelegate plugin
+pub mod unstake_many; // Unstakes several NFTs in one instruction
+pub mod unstake_pnft; // Unlocks a programmable NFT and revokes the staking delegate
+pub mod unstake_token22; // Thaws a Token-2022 NFT
+pub mod unstake_vault; // Returns a vaulted NFT to its staker
+pub mod update_config; // Admin function to tune the staking parameters
 
 // Re-export all instruction structs and implementations
+pub use accept_admin::*;
 pub use claim::*;
+pub use claim_treasury::*;
+pub use fund_treasury::*;
+pub use get_pending_rewards::*;
 pub use initialize_config::*;
+pub use initialize_treasury::*;
 pub use initialize_user_accounts::*;
+pub use set_emergency::*;
+pub use set_emission_schedule::*;
+pub use set_lock_tiers::*;
+pub use set_paused::*;
 pub use stake::*;
+pub use stake_cnft::*;
+pub use stake_core::*;
+pub use stake_many::*;
+pub use stake_pnft::*;
+pub use stake_token22::*;
+pub use stake_vault::*;
+pub use transfer_admin::*;
 pub use unstake::*;
+pub use unstake_cnft::*;
+pub use unstake_core::*;
+pub use unstake_many::*;
+pub use unstake_pnft::*;
+pub use unstake_token22::*;
+pub use unstake_vault::*;
+pub use update_config::*;
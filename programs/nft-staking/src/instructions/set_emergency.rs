@@ -0,0 +1,39 @@
+#![allow(unexpected_cfgs)] // Allow compiler warnings for unrecognized configuration flags
+
+use anchor_lang::prelude::*; // Import essential Anchor framework items
+
+use crate::error::ErrorCode; // Import custom error types
+use crate::events::EmergencySet; // Event recording the new flag
+use crate::state::StakeConfig; // Import the pool configuration structure
+
+// Account validation struct for toggling emergency mode
+// While enabled, staking is disabled and unstaking always succeeds
+// (ignoring the freeze period and pause flags) but forfeits unsettled points
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetEmergency<'info> {
+    pub admin: Signer<'info>, // The current config admin
+
+    #[account(
+        mut, // Account will be modified (emergency flag is overwritten)
+        seeds = [b"config".as_ref(), config.collection.as_ref(), config.pool_id.to_le_bytes().as_ref()], // Pool config PDA seeds
+        bump = config.bump, // Use stored bump from config
+        has_one = admin @ ErrorCode::Unauthorized, // Only the stored admin may toggle emergency mode
+    )]
+    pub config: Account<'info, StakeConfig>, // Pool staking configuration
+}
+
+// Implementation block containing the emergency toggle logic
+impl<'info> SetEmergency<'info> {
+    // Function to enable or disable emergency mode
+    pub fn set_emergency(&mut self, emergency: bool) -> Result<EmergencySet> {
+        self.config.emergency = emergency; // Applies to every subsequent instruction
+
+        Ok(EmergencySet {
+            pool: self.config.key(),
+            admin: self.admin.key(),
+            emergency,
+            timestamp: Clock::get()?.unix_timestamp,
+        })
+    }
+}
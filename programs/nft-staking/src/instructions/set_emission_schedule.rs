@@ -0,0 +1,74 @@
+#![allow(unexpected_cfgs)] // Allow compiler warnings for unrecognized configuration flags
+
+use anchor_lang::prelude::*; // Import essential Anchor framework items
+
+use crate::constants::MAX_SCHEDULE_SEGMENTS; // Upper bound on stored rate changes
+use crate::error::ErrorCode; // Import custom error types
+use crate::events::ScheduleSet; // Event recording the new schedule
+use crate::state::{EmissionSegment, StakeConfig}; // Import the global configuration structure
+
+// Account validation struct for replacing an emission pool's schedule
+// The schedule bounds emission to [start_time, end_time) and lists the rate changes
+// (e.g. halvings) applied after `emission_per_second`
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetEmissionSchedule<'info> {
+    pub admin: Signer<'info>, // The current config admin
+
+    #[account(
+        mut, // Account will be modified (schedule is overwritten)
+        seeds = [b"config".as_ref(), config.collection.as_ref(), config.pool_id.to_le_bytes().as_ref()], // Pool config PDA seeds
+        bump = config.bump, // Use stored bump from config
+        has_one = admin @ ErrorCode::Unauthorized, // Only the stored admin may change the schedule
+    )]
+    pub config: Account<'info, StakeConfig>, // Pool staking configuration
+}
+
+// Full emission schedule; replaces the stored one
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct ScheduleParams {
+    pub start_time: i64,                // Nothing is emitted before this
+    pub end_time: Option<i64>,          // Nothing is emitted from this on (None = never)
+    pub segments: Vec<EmissionSegment>, // Rate changes, strictly increasing start_ts
+}
+
+// Implementation block containing the schedule update
+impl<'info> SetEmissionSchedule<'info> {
+    // Function to replace the pool's emission window and rate changes
+    // Emission up to now is settled under the old schedule, so only future time is affected
+    pub fn set_emission_schedule(&mut self, params: ScheduleParams) -> Result<ScheduleSet> {
+        // Flat-points pools have no shared emission to schedule
+        require!(self.config.shares_emission(), ErrorCode::InvalidSchedule);
+        require!(
+            params.segments.len() <= MAX_SCHEDULE_SEGMENTS,
+            ErrorCode::InvalidSchedule
+        );
+        // Segments are walked in order, so their starts must be strictly increasing
+        require!(
+            params
+                .segments
+                .windows(2)
+                .all(|pair| pair[0].start_ts < pair[1].start_ts),
+            ErrorCode::InvalidSchedule
+        );
+        if let Some(end_time) = params.end_time {
+            require!(end_time > params.start_time, ErrorCode::InvalidSchedule);
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        self.config.update_pool(now)?; // Time so far is emitted under the old schedule
+
+        self.config.start_time = params.start_time; // New emission window start
+        self.config.end_time = params.end_time; // New emission window end
+        self.config.schedule = params.segments; // New rate changes
+
+        Ok(ScheduleSet {
+            pool: self.config.key(),
+            admin: self.admin.key(),
+            start_time: self.config.start_time,
+            end_time: self.config.end_time,
+            segments: self.config.schedule.clone(),
+            timestamp: now,
+        })
+    }
+}
@@ -0,0 +1,48 @@
+#![allow(unexpected_cfgs)] // Allow compiler warnings for unrecognized configuration flags
+
+use anchor_lang::prelude::*; // Import essential Anchor framework items
+
+use crate::constants::{MAX_LOCK_TIERS, MULTIPLIER_BASE}; // Tier count bound and 1x multiplier
+use crate::error::ErrorCode; // Import custom error types
+use crate::events::LockTiersSet; // Event recording the new tiers
+use crate::state::{LockTier, StakeConfig}; // Import the global configuration structure
+
+// Account validation struct for replacing the lock tiers stakers can choose from
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetLockTiers<'info> {
+    pub admin: Signer<'info>, // The current config admin
+
+    #[account(
+        mut, // Account will be modified (tiers are overwritten)
+        seeds = [b"config".as_ref(), config.collection.as_ref(), config.pool_id.to_le_bytes().as_ref()], // Pool config PDA seeds
+        bump = config.bump, // Use stored bump from config
+        has_one = admin @ ErrorCode::Unauthorized, // Only the stored admin may change the tiers
+    )]
+    pub config: Account<'info, StakeConfig>, // Pool staking configuration
+}
+
+// Implementation block containing the tier update
+impl<'info> SetLockTiers<'info> {
+    // Function to replace the pool's lock tiers; `tiers[i]` becomes tier `i + 1`
+    // NFTs already staked keep the lock and multiplier they were staked with
+    pub fn set_lock_tiers(&mut self, tiers: Vec<LockTier>) -> Result<LockTiersSet> {
+        require!(tiers.len() <= MAX_LOCK_TIERS, ErrorCode::InvalidLockTier);
+        // Locking an NFT should never earn less than the default tier
+        require!(
+            tiers
+                .iter()
+                .all(|tier| tier.multiplier_bps >= MULTIPLIER_BASE),
+            ErrorCode::InvalidLockTier
+        );
+
+        self.config.lock_tiers = tiers; // Offered to new stakes from now on
+
+        Ok(LockTiersSet {
+            pool: self.config.key(),
+            admin: self.admin.key(),
+            tiers: self.config.lock_tiers.clone(),
+            timestamp: Clock::get()?.unix_timestamp,
+        })
+    }
+}
@@ -0,0 +1,39 @@
+#![allow(unexpected_cfgs)] // Allow compiler warnings for unrecognized configuration flags
+
+use anchor_lang::prelude::*; // Import essential Anchor framework items
+
+use crate::constants::PAUSE_ALL; // Mask of every valid pause flag
+use crate::error::ErrorCode; // Import custom error types
+use crate::events::PausedSet; // Event recording the new bitfield
+use crate::state::StakeConfig; // Import the pool configuration structure
+
+// Account validation struct for pausing individual pool operations
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    pub admin: Signer<'info>, // The current config admin
+
+    #[account(
+        mut, // Account will be modified (pause flags are overwritten)
+        seeds = [b"config".as_ref(), config.collection.as_ref(), config.pool_id.to_le_bytes().as_ref()], // Pool config PDA seeds
+        bump = config.bump, // Use stored bump from config
+        has_one = admin @ ErrorCode::Unauthorized, // Only the stored admin may pause
+    )]
+    pub config: Account<'info, StakeConfig>, // Pool staking configuration
+}
+
+// Implementation block containing the pause logic
+impl<'info> SetPaused<'info> {
+    // Function to replace the pause bitfield (PAUSE_STAKE | PAUSE_UNSTAKE | PAUSE_CLAIM)
+    pub fn set_paused(&mut self, paused: u8) -> Result<PausedSet> {
+        require!(paused & !PAUSE_ALL == 0, ErrorCode::InvalidPauseFlags); // Reject unknown bits
+        self.config.paused = paused; // Zero resumes every operation
+
+        Ok(PausedSet {
+            pool: self.config.key(),
+            admin: self.admin.key(),
+            paused,
+            timestamp: Clock::get()?.unix_timestamp,
+        })
+    }
+}
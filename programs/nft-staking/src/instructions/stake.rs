@@ -10,22 +10,24 @@ use anchor_spl::{
 use mpl_token_metadata::instructions::{
     FreezeDelegatedAccountCpi, FreezeDelegatedAccountCpiAccounts,
 };
-// Import program state structures
+// Import pause flags and program state structures
+use crate::constants::PAUSE_STAKE;
 use crate::state::stake_account::StakeAccount;
 use crate::state::stake_config::StakeConfig;
 use crate::state::user_accounts::UserAccount;
 // Import custom error types
 use crate::error::ErrorCode;
+use crate::events::Staked; // Event recording the stake
 
 // Account validation struct for staking an NFT
 // Defines all accounts needed and their validation constraints
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Stake<'info> {
     #[account(mut)] // Account can be modified (pays for stake account creation)
     pub user: Signer<'info>, // The user staking their NFT
 
     pub mint: Account<'info, Mint>, // The NFT mint being staked
-    pub collection_mint: Account<'info, Mint>, // The collection this NFT belongs to
 
     #[account(
         mut, // Account will be modified (approval will be set)
@@ -42,8 +44,17 @@ pub struct Stake<'info> {
         ],
         seeds::program = metadata_program.key(), // Use metadata program for PDA derivation
         bump, // Anchor finds the canonical bump automatically
-        constraint = metadata.collection.as_ref().unwrap().key.as_ref() == collection_mint.key().as_ref(), // Verify NFT belongs to expected collection
-        constraint = metadata.collection.as_ref().unwrap().verified == true, // Verify collection is verified by creator
+        constraint = metadata.collection.is_some() @ ErrorCode::MissingCollection, // NFT must belong to a collection
+        constraint = metadata
+            .collection
+            .as_ref()
+            .is_some_and(|collection| collection.key == config.collection)
+            @ ErrorCode::CollectionMismatch, // Verify NFT belongs to the pool's collection
+        constraint = metadata
+            .collection
+            .as_ref()
+            .is_some_and(|collection| collection.verified)
+            @ ErrorCode::CollectionNotVerified, // Verify collection is verified by creator
     )]
     pub metadata: Account<'info, MetadataAccount>, // NFT metadata account
 
@@ -60,10 +71,13 @@ pub struct Stake<'info> {
     pub edition: Account<'info, MasterEditionAccount>, // NFT master edition account (proves it's an NFT)
 
     #[account(
-        seeds = [b"config".as_ref()], // Global config PDA seed
-        bump = config.bump // Use stored bump from config
+        mut, // Account will be modified (pool reward index and staked count)
+        seeds = [b"config".as_ref(), config.collection.as_ref(), config.pool_id.to_le_bytes().as_ref()], // Pool config PDA seeds
+        bump = config.bump, // Use stored bump from config
+        constraint = !config.emergency && !config.is_paused(PAUSE_STAKE) @ ErrorCode::PoolPaused, // Staking must be enabled
+        constraint = !config.custody @ ErrorCode::WrongCustodyMode, // Pool freezes NFTs in place
     )]
-    pub config: Account<'info, StakeConfig>, // Global staking configuration
+    pub config: Account<'info, StakeConfig>, // Pool staking configuration
 
     #[account(
         init, // Create new stake record for this NFT
@@ -76,7 +90,7 @@ pub struct Stake<'info> {
 
     #[account(
         mut, // Account will be modified (amount_staked will increase)
-        seeds = [b"user".as_ref(), user.key().as_ref()], // User's staking account PDA
+        seeds = [b"user".as_ref(), config.key().as_ref(), user.key().as_ref()], // User's staking account PDA for this pool
         bump = user_account.bump, // Use stored bump from user account
     )]
     pub user_account: Account<'info, UserAccount>, // User's overall staking statistics
@@ -90,20 +104,20 @@ pub struct Stake<'info> {
 // Implementation block containing the staking logic
 impl<'info> Stake<'info> {
     // Function to stake an NFT and start earning rewards
-    pub fn stake(&mut self, bumps: &StakeBumps) -> Result<()> {
-        // Verify user hasn't exceeded their staking limit
-        require!(
-            self.user_account.amount_staked < self.config.max_stake,
-            ErrorCode::MaxStake
-        );
+    pub fn stake(&mut self, tier: u8, bumps: &StakeBumps) -> Result<Staked> {
+        // Count the NFT against the user's staking limit
+        self.user_account.record_stake(&self.config)?;
 
         // Create the stake record with current timestamp
-        self.stake_account.set_inner(StakeAccount {
-            owner: self.user.key(),                  // Store who staked this NFT
-            mint: self.mint.key(),                   // Store which NFT was staked
-            staked_at: Clock::get()?.unix_timestamp, // Store when it was staked (for freeze period)
-            bump: bumps.stake_account,               // Store PDA bump for future lookups
-        });
+        let now = Clock::get()?.unix_timestamp;
+        self.stake_account.set_inner(StakeAccount::new(
+            &mut self.config,    // Pool whose staked count and reward index are updated
+            self.user.key(),     // Store who staked this NFT
+            self.mint.key(),     // Store which NFT was staked
+            tier,                // Lock tier and multiplier the staker chose
+            now,                 // Store when it was staked (for freeze period)
+            bumps.stake_account, // Store PDA bump for future lookups
+        )?);
 
         // Approve the stake account as delegate for the NFT (allows program to control it)
         let cpi_program: AccountInfo<'_> = self.token_program.to_account_info();
@@ -145,9 +159,13 @@ impl<'info> Stake<'info> {
         )
         .invoke_signed(signer_seeds)?; // Sign with stake account PDA
 
-        // Update user's staking statistics
-        self.user_account.amount_staked += 1; // Increment their staked NFT count
-
-        Ok(()) // Return success
+        Ok(Staked {
+            pool: self.config.key(),
+            owner: self.user.key(),
+            mint: self.stake_account.mint,
+            tier,
+            amount_staked: self.user_account.amount_staked,
+            timestamp: now,
+        })
     }
 }
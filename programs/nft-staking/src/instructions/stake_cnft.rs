@@ -0,0 +1,171 @@
+#![allow(unexpected_cfgs)] // Allow compiler warnings for unrecognized configuration flags
+
+use anchor_lang::prelude::*; // Import essential Anchor framework items
+                             // Import Bubblegum types for compressed NFT handling
+use mpl_bubblegum::{
+    hash::hash_collection_option,
+    instructions::{
+        DelegateAndFreezeV2Cpi, DelegateAndFreezeV2CpiAccounts, DelegateAndFreezeV2InstructionArgs,
+    },
+    utils::get_asset_id,
+};
+// Import pause flags, Bubblegum program ids and program state structures
+use crate::constants::{MPL_ACCOUNT_COMPRESSION_ID, MPL_NOOP_ID, PAUSE_STAKE};
+use crate::state::stake_account::StakeAccount;
+use crate::state::stake_config::StakeConfig;
+use crate::state::user_accounts::UserAccount;
+// Import custom error types
+use crate::error::ErrorCode;
+use crate::events::Staked; // Event recording the stake
+
+// Account validation struct for staking a compressed NFT (Bubblegum V2 tree)
+// The leaf is delegated to and frozen by the stake PDA; the Merkle proof
+// nodes are passed as remaining accounts and checked by Bubblegum
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(leaf: CnftLeaf)]
+pub struct StakeCnft<'info> {
+    #[account(mut)] // Account can be modified (pays for stake account creation)
+    pub user: Signer<'info>, // The leaf owner staking their cNFT
+
+    /// CHECK: Tree config PDA, validated by seeds and by Bubblegum during the CPI
+    #[account(
+        mut, // Account will be modified by Bubblegum
+        seeds = [merkle_tree.key().as_ref()], // Tree config is derived from the tree address
+        seeds::program = mpl_bubblegum::ID, // Under the Bubblegum program
+        bump, // Anchor finds the canonical bump automatically
+    )]
+    pub tree_config: UncheckedAccount<'info>, // Bubblegum tree config
+
+    /// CHECK: Merkle tree, validated by the compression program during the CPI
+    #[account(mut)] // Account will be modified (leaf is replaced)
+    pub merkle_tree: UncheckedAccount<'info>, // Tree holding the cNFT leaf
+
+    #[account(
+        mut, // Account will be modified (pool reward index and staked count)
+        seeds = [b"config".as_ref(), config.collection.as_ref(), config.pool_id.to_le_bytes().as_ref()], // Pool config PDA seeds
+        bump = config.bump, // Use stored bump from config
+        constraint = !config.emergency && !config.is_paused(PAUSE_STAKE) @ ErrorCode::PoolPaused, // Staking must be enabled
+        constraint = !config.custody @ ErrorCode::WrongCustodyMode, // Pool freezes NFTs in place
+    )]
+    pub config: Account<'info, StakeConfig>, // Pool staking configuration
+
+    #[account(
+        init, // Create new stake record for this cNFT
+        payer = user, // User pays for stake account creation
+        space = 8 + StakeAccount::INIT_SPACE, // 8 bytes discriminator + struct size
+        seeds = [
+            b"stake".as_ref(), // Stake PDA seed
+            get_asset_id(&merkle_tree.key(), leaf.nonce).as_ref(), // Asset id takes the mint's place
+            config.key().as_ref() // Config address
+        ],
+        bump, // Anchor finds the canonical bump automatically
+    )]
+    pub stake_account: Account<'info, StakeAccount>, // Individual stake record being created
+
+    #[account(
+        mut, // Account will be modified (amount_staked will increase)
+        seeds = [b"user".as_ref(), config.key().as_ref(), user.key().as_ref()], // User's staking account PDA for this pool
+        bump = user_account.bump, // Use stored bump from user account
+    )]
+    pub user_account: Account<'info, UserAccount>, // User's overall staking statistics
+
+    /// CHECK: Bubblegum program, checked by address
+    #[account(address = mpl_bubblegum::ID)]
+    pub bubblegum_program: UncheckedAccount<'info>, // For delegate and freeze
+
+    /// CHECK: Noop program used by V2 trees for logging, checked by address
+    #[account(address = MPL_NOOP_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+
+    /// CHECK: Account compression program used by V2 trees, checked by address
+    #[account(address = MPL_ACCOUNT_COMPRESSION_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>, // For account creation
+}
+
+// Current leaf contents, as returned by the DAS API alongside the proof
+// The collection hash is not supplied: it is derived from the pool collection,
+// so a leaf from any other collection fails proof verification
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CnftLeaf {
+    pub root: [u8; 32],                    // Current Merkle root
+    pub data_hash: [u8; 32],               // Hash of the cNFT metadata
+    pub creator_hash: [u8; 32],            // Hash of the cNFT creators
+    pub asset_data_hash: Option<[u8; 32]>, // Hash of the asset data, if any
+    pub flags: Option<u8>,                 // Current leaf flags
+    pub nonce: u64,                        // Leaf nonce (derives the asset id)
+    pub index: u32,                        // Leaf index in the tree
+}
+
+// Implementation block containing the cNFT staking logic
+impl<'info> StakeCnft<'info> {
+    // Function to stake a cNFT and start earning rewards
+    // `proof` are the Merkle proof nodes for the leaf
+    pub fn stake_cnft(
+        &mut self,
+        leaf: CnftLeaf,
+        tier: u8,
+        proof: &'info [AccountInfo<'info>],
+        bumps: &StakeCnftBumps,
+    ) -> Result<Staked> {
+        // Count the cNFT against the user's staking limit
+        self.user_account.record_stake(&self.config)?;
+
+        // Create the stake record keyed by asset id
+        let now = Clock::get()?.unix_timestamp;
+        self.stake_account.set_inner(StakeAccount::new(
+            &mut self.config, // Pool whose staked count and reward index are updated
+            self.user.key(),  // Store who staked this cNFT
+            get_asset_id(&self.merkle_tree.key(), leaf.nonce), // Store which cNFT was staked
+            tier,             // Lock tier and multiplier the staker chose
+            now,              // Store when it was staked (for freeze period)
+            bumps.stake_account, // Store PDA bump for future lookups
+        )?);
+
+        // Leaf must belong to the pool's collection for the proof to verify
+        let collection_hash = hash_collection_option(Some(self.config.collection))?;
+
+        // Proof nodes are read-only, non-signer accounts
+        let proof: Vec<(&AccountInfo<'info>, bool, bool)> =
+            proof.iter().map(|node| (node, false, false)).collect();
+
+        // Delegate the leaf to the stake account and freeze it (signed by the owner)
+        let user = &self.user.to_account_info();
+        DelegateAndFreezeV2Cpi::new(
+            &self.bubblegum_program.to_account_info(),
+            DelegateAndFreezeV2CpiAccounts {
+                tree_config: &self.tree_config.to_account_info(), // Bubblegum tree config
+                payer: user,                                      // User pays any fees
+                leaf_owner: Some(user),                           // Owner authorizes the delegation
+                previous_leaf_delegate: None, // Leaf must not already be delegated
+                new_leaf_delegate: &self.stake_account.to_account_info(), // Stake account becomes delegate
+                merkle_tree: &self.merkle_tree.to_account_info(),         // Tree holding the leaf
+                log_wrapper: &self.log_wrapper.to_account_info(),         // Noop program
+                compression_program: &self.compression_program.to_account_info(), // Compression program
+                system_program: &self.system_program.to_account_info(), // Solana system program
+            },
+            DelegateAndFreezeV2InstructionArgs {
+                root: leaf.root,                        // Root the proof is checked against
+                data_hash: leaf.data_hash,              // Leaf metadata hash
+                creator_hash: leaf.creator_hash,        // Leaf creators hash
+                collection_hash: Some(collection_hash), // Pool collection
+                asset_data_hash: leaf.asset_data_hash,  // Leaf asset data hash
+                flags: leaf.flags,                      // Leaf flags
+                nonce: leaf.nonce,                      // Leaf nonce
+                index: leaf.index,                      // Leaf index
+            },
+        )
+        .invoke_with_remaining_accounts(&proof)?;
+
+        Ok(Staked {
+            pool: self.config.key(),
+            owner: self.user.key(),
+            mint: self.stake_account.mint,
+            tier,
+            amount_staked: self.user_account.amount_staked,
+            timestamp: now,
+        })
+    }
+}
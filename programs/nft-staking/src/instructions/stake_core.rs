@@ -0,0 +1,138 @@
+#![allow(unexpected_cfgs)] // Allow compiler warnings for unrecognized configuration flags
+
+use anchor_lang::prelude::*; // Import essential Anchor framework items
+                             // Import Metaplex Core types for asset handling
+use mpl_core::{
+    accounts::BaseAssetV1,
+    instructions::{AddPluginV1Cpi, AddPluginV1CpiAccounts, AddPluginV1InstructionArgs},
+    types::{FreezeDelegate, Key as CoreKey, Plugin, PluginAuthority, UpdateAuthority},
+};
+// Import pause flags and program state structures
+use crate::constants::PAUSE_STAKE;
+use crate::state::stake_account::StakeAccount;
+use crate::state::stake_config::StakeConfig;
+use crate::state::user_accounts::UserAccount;
+// Import custom error types
+use crate::error::ErrorCode;
+use crate::events::Staked; // Event recording the stake
+
+// Account validation struct for staking a Metaplex Core asset
+// The owner adds a frozen FreezeDelegate plugin whose authority is the stake PDA,
+// so only the program can thaw the asset again
+#[event_cpi]
+#[derive(Accounts)]
+pub struct StakeCore<'info> {
+    #[account(mut)] // Account can be modified (pays for stake account and plugin space)
+    pub user: Signer<'info>, // The user staking their asset
+
+    /// CHECK: Core asset, owner checked here and contents validated in the handler
+    #[account(
+        mut, // Account will be modified (FreezeDelegate plugin is added)
+        owner = mpl_core::ID, // Must be a Metaplex Core account
+    )]
+    pub asset: UncheckedAccount<'info>, // The Core asset being staked
+
+    /// CHECK: Core collection, validated by address and owner
+    #[account(
+        mut, // Account will be modified (Core updates collection bookkeeping)
+        address = config.collection, // Must be the pool's collection
+        owner = mpl_core::ID, // Must be a Metaplex Core account
+    )]
+    pub collection: UncheckedAccount<'info>, // Core collection the asset belongs to
+
+    #[account(
+        mut, // Account will be modified (pool reward index and staked count)
+        seeds = [b"config".as_ref(), config.collection.as_ref(), config.pool_id.to_le_bytes().as_ref()], // Pool config PDA seeds
+        bump = config.bump, // Use stored bump from config
+        constraint = !config.emergency && !config.is_paused(PAUSE_STAKE) @ ErrorCode::PoolPaused, // Staking must be enabled
+        constraint = !config.custody @ ErrorCode::WrongCustodyMode, // Pool freezes NFTs in place
+    )]
+    pub config: Account<'info, StakeConfig>, // Pool staking configuration
+
+    #[account(
+        init, // Create new stake record for this asset
+        payer = user, // User pays for stake account creation
+        space = 8 + StakeAccount::INIT_SPACE, // 8 bytes discriminator + struct size
+        seeds = [b"stake".as_ref(), asset.key().as_ref(), config.key().as_ref()], // Unique PDA per asset per config
+        bump, // Anchor finds the canonical bump automatically
+    )]
+    pub stake_account: Account<'info, StakeAccount>, // Individual stake record being created
+
+    #[account(
+        mut, // Account will be modified (amount_staked will increase)
+        seeds = [b"user".as_ref(), config.key().as_ref(), user.key().as_ref()], // User's staking account PDA for this pool
+        bump = user_account.bump, // Use stored bump from user account
+    )]
+    pub user_account: Account<'info, UserAccount>, // User's overall staking statistics
+
+    /// CHECK: Metaplex Core program, checked by address
+    #[account(address = mpl_core::ID)]
+    pub core_program: UncheckedAccount<'info>, // For plugin operations
+
+    pub system_program: Program<'info, System>, // For account creation
+}
+
+// Implementation block containing the Core staking logic
+impl<'info> StakeCore<'info> {
+    // Function to stake a Core asset and start earning rewards
+    pub fn stake_core(&mut self, tier: u8, bumps: &StakeCoreBumps) -> Result<Staked> {
+        // Only the owner can stake, and only assets of the pool's Core collection
+        let asset = load_core_asset(&self.asset)?;
+        require_keys_eq!(asset.owner, self.user.key(), ErrorCode::WrongOwner);
+        require!(
+            asset.update_authority == UpdateAuthority::Collection(self.config.collection),
+            ErrorCode::CollectionMismatch
+        );
+
+        // Count the asset against the user's staking limit
+        self.user_account.record_stake(&self.config)?;
+
+        // Create the stake record with current timestamp (the asset address plays the mint's role)
+        let now = Clock::get()?.unix_timestamp;
+        self.stake_account.set_inner(StakeAccount::new(
+            &mut self.config,    // Pool whose staked count and reward index are updated
+            self.user.key(),     // Store who staked this asset
+            self.asset.key(),    // Store which asset was staked
+            tier,                // Lock tier and multiplier the staker chose
+            now,                 // Store when it was staked (for freeze period)
+            bumps.stake_account, // Store PDA bump for future lookups
+        )?);
+
+        // Freeze the asset with the stake account as plugin authority (signed by the owner)
+        AddPluginV1Cpi::new(
+            &self.core_program.to_account_info(),
+            AddPluginV1CpiAccounts {
+                asset: &self.asset.to_account_info(), // The asset being frozen
+                collection: Some(&self.collection.to_account_info()), // Its collection
+                payer: &self.user.to_account_info(),  // User pays for plugin space
+                authority: Some(&self.user.to_account_info()), // Owner adds owner-managed plugins
+                system_program: &self.system_program.to_account_info(), // For reallocation
+                log_wrapper: None,                    // No noop logging
+            },
+            AddPluginV1InstructionArgs {
+                plugin: Plugin::FreezeDelegate(FreezeDelegate { frozen: true }), // Frozen from the start
+                init_authority: Some(PluginAuthority::Address {
+                    address: self.stake_account.key(), // Only the stake PDA can thaw it
+                }),
+            },
+        )
+        .invoke()?;
+
+        Ok(Staked {
+            pool: self.config.key(),
+            owner: self.user.key(),
+            mint: self.stake_account.mint,
+            tier,
+            amount_staked: self.user_account.amount_staked,
+            timestamp: now,
+        })
+    }
+}
+
+// Deserialize a Core asset's base data, rejecting anything that is not an asset account
+pub(crate) fn load_core_asset(asset: &AccountInfo) -> Result<BaseAssetV1> {
+    let asset = BaseAssetV1::from_bytes(&asset.try_borrow_data()?)
+        .map_err(|_| ErrorCode::InvalidCoreAsset)?;
+    require!(asset.key == CoreKey::AssetV1, ErrorCode::InvalidCoreAsset); // Collections share the owner program
+    Ok(asset)
+}
@@ -0,0 +1,271 @@
+#![allow(unexpected_cfgs)] // Allow compiler warnings for unrecognized configuration flags
+
+use anchor_lang::prelude::*; // Import essential Anchor framework items
+use anchor_lang::system_program::{
+    allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer,
+}; // For creating stake records
+
+// Import SPL Token and Metadata program types for NFT handling
+use anchor_spl::{
+    associated_token::get_associated_token_address,
+    metadata::{mpl_token_metadata, Metadata, MetadataAccount},
+    token::{approve, Approve, Mint, Token, TokenAccount},
+};
+// Import Metaplex instruction for freezing delegated NFTs
+use mpl_token_metadata::instructions::{
+    FreezeDelegatedAccountCpi, FreezeDelegatedAccountCpiAccounts,
+};
+// Import pause flags and program state structures
+use crate::constants::PAUSE_STAKE;
+use crate::state::stake_account::StakeAccount;
+use crate::state::stake_config::StakeConfig;
+use crate::state::user_accounts::UserAccount;
+// Import custom error types
+use crate::error::ErrorCode;
+use crate::events::Staked; // Event recording each stake
+
+// Accounts per NFT in `remaining_accounts`: mint, mint_ata, metadata, edition, stake_account
+pub const STAKE_MANY_GROUP: usize = 5;
+
+// Account validation struct for staking several NFTs in one instruction
+// Shared accounts are checked once here; each NFT's accounts arrive as a
+// group in `remaining_accounts` and get the same checks as `Stake`
+#[event_cpi]
+#[derive(Accounts)]
+pub struct StakeMany<'info> {
+    #[account(mut)] // Account can be modified (pays for stake account creation)
+    pub user: Signer<'info>, // The user staking their NFTs
+
+    #[account(
+        mut, // Account will be modified (pool reward index and staked count)
+        seeds = [b"config".as_ref(), config.collection.as_ref(), config.pool_id.to_le_bytes().as_ref()], // Pool config PDA seeds
+        bump = config.bump, // Use stored bump from config
+        constraint = !config.emergency && !config.is_paused(PAUSE_STAKE) @ ErrorCode::PoolPaused, // Staking must be enabled
+        constraint = !config.custody @ ErrorCode::WrongCustodyMode, // Pool freezes NFTs in place
+    )]
+    pub config: Account<'info, StakeConfig>, // Pool staking configuration
+
+    #[account(
+        mut, // Account will be modified (amount_staked will increase)
+        seeds = [b"user".as_ref(), config.key().as_ref(), user.key().as_ref()], // User's staking account PDA for this pool
+        bump = user_account.bump, // Use stored bump from user account
+    )]
+    pub user_account: Account<'info, UserAccount>, // User's overall staking statistics
+
+    // Required Solana programs
+    pub system_program: Program<'info, System>, // For stake account creation
+    pub token_program: Program<'info, Token>,   // For token operations
+    pub metadata_program: Program<'info, Metadata>, // For NFT freeze operations
+}
+
+// Implementation block containing the batch staking logic
+impl<'info> StakeMany<'info> {
+    // Function to stake every NFT group in `nfts` in lock tier `tier`; any failure reverts the whole batch
+    // Returns one event per NFT, in batch order
+    pub fn stake_many(
+        &mut self,
+        tier: u8,
+        nfts: &'info [AccountInfo<'info>],
+    ) -> Result<Vec<Staked>> {
+        // Accounts must split evenly into non-empty per-NFT groups
+        require!(
+            !nfts.is_empty() && nfts.len().is_multiple_of(STAKE_MANY_GROUP),
+            ErrorCode::InvalidBatch
+        );
+        let count =
+            u8::try_from(nfts.len() / STAKE_MANY_GROUP).map_err(|_| ErrorCode::InvalidBatch)?;
+
+        // Count the whole batch against the user's staking limit in one update
+        let staked_before = self.user_account.amount_staked;
+        self.user_account.record_stakes(&self.config, count)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let mut events = Vec::with_capacity(count as usize);
+        for (index, group) in nfts.chunks_exact(STAKE_MANY_GROUP).enumerate() {
+            let mint = self.stake_one(group, tier, now).inspect_err(|_| {
+                msg!("stake_many: NFT at index {} failed", index); // Point the client at the offending NFT
+            })?;
+            events.push(Staked {
+                pool: self.config.key(),
+                owner: self.user.key(),
+                mint,
+                tier,
+                amount_staked: staked_before + index as u8 + 1, // Count as if staked one by one
+                timestamp: now,
+            });
+        }
+
+        Ok(events)
+    }
+
+    // Function to create a program-owned stake record at the `stake_account` PDA, like Anchor's `init`
+    // The address is public, so anyone may have sent it lamports already; `create_account`
+    // would then fail, so a funded PDA is topped up, allocated and assigned instead
+    fn create_stake_record(
+        &self,
+        stake_account: &AccountInfo<'info>,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let space = 8 + StakeAccount::INIT_SPACE; // 8 bytes discriminator + struct size
+        let rent = Rent::get()?.minimum_balance(space);
+        let system_program = self.system_program.to_account_info();
+
+        let lamports = stake_account.lamports();
+        if lamports == 0 {
+            return create_account(
+                CpiContext::new_with_signer(
+                    system_program,
+                    CreateAccount {
+                        from: self.user.to_account_info(), // User pays for the stake record
+                        to: stake_account.clone(),         // The stake PDA
+                    },
+                    signer_seeds,
+                ),
+                rent,
+                space as u64,
+                &crate::ID,
+            );
+        }
+
+        // Pre-funded PDA: pay only what rent exemption still needs
+        let top_up = rent.saturating_sub(lamports);
+        if top_up > 0 {
+            transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    Transfer {
+                        from: self.user.to_account_info(), // User pays the difference
+                        to: stake_account.clone(),         // The stake PDA
+                    },
+                ),
+                top_up,
+            )?;
+        }
+        // Fails if the PDA already holds data, i.e. the NFT is already staked
+        allocate(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                Allocate {
+                    account_to_allocate: stake_account.clone(),
+                },
+                signer_seeds,
+            ),
+            space as u64,
+        )?;
+        assign(
+            CpiContext::new_with_signer(
+                system_program,
+                Assign {
+                    account_to_assign: stake_account.clone(),
+                },
+                signer_seeds,
+            ),
+            &crate::ID,
+        )
+    }
+
+    // Function to validate one NFT group and freeze it like `Stake`, returning its mint
+    fn stake_one(
+        &mut self,
+        group: &'info [AccountInfo<'info>],
+        tier: u8,
+        now: i64,
+    ) -> Result<Pubkey> {
+        let [mint, mint_ata, metadata, edition, stake_account] = group else {
+            return err!(ErrorCode::InvalidBatch);
+        };
+        let metadata_program_id = mpl_token_metadata::ID;
+        let config_key = self.config.key();
+
+        // Mint and the user's ATA for it
+        Account::<Mint>::try_from(mint)?; // Must be an SPL Token mint
+        let token = Account::<TokenAccount>::try_from(mint_ata)?;
+        require_keys_eq!(
+            mint_ata.key(),
+            get_associated_token_address(&self.user.key(), &mint.key()),
+            ErrorCode::InvalidBatch
+        );
+        require_keys_eq!(token.mint, mint.key(), ErrorCode::InvalidBatch);
+
+        // Metadata must be the mint's PDA and name the pool's verified collection
+        let (metadata_key, _) = Pubkey::find_program_address(
+            &[b"metadata", metadata_program_id.as_ref(), mint.key.as_ref()],
+            &metadata_program_id,
+        );
+        require_keys_eq!(metadata.key(), metadata_key, ErrorCode::InvalidBatch);
+        let metadata = Account::<MetadataAccount>::try_from(metadata)?;
+        let collection = metadata
+            .collection
+            .as_ref()
+            .ok_or(ErrorCode::MissingCollection)?;
+        require_keys_eq!(
+            collection.key,
+            self.config.collection,
+            ErrorCode::CollectionMismatch
+        );
+        require!(collection.verified, ErrorCode::CollectionNotVerified);
+
+        // Master edition must be the mint's edition PDA
+        let (edition_key, _) = Pubkey::find_program_address(
+            &[
+                b"metadata",
+                metadata_program_id.as_ref(),
+                mint.key.as_ref(),
+                b"edition",
+            ],
+            &metadata_program_id,
+        );
+        require_keys_eq!(edition.key(), edition_key, ErrorCode::InvalidBatch);
+
+        // Stake record must be the canonical stake PDA for this NFT and pool
+        let (stake_key, bump) = Pubkey::find_program_address(
+            &[b"stake", mint.key.as_ref(), config_key.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(
+            stake_account.key(),
+            stake_key,
+            ErrorCode::InvalidStakeAccount
+        );
+        let seeds: &[&[u8]; 4] = &[b"stake", mint.key.as_ref(), config_key.as_ref(), &[bump]];
+        let signer_seeds = &[&seeds[..]]; // Format for CPI signing
+
+        // Create the stake record (fails if this NFT is already staked)
+        self.create_stake_record(stake_account, signer_seeds)?;
+        StakeAccount::new(
+            &mut self.config,
+            self.user.key(),
+            mint.key(),
+            tier,
+            now,
+            bump,
+        )?
+        .try_serialize(&mut &mut stake_account.try_borrow_mut_data()?[..])?;
+
+        // Approve the stake account as delegate for the NFT
+        let cpi_accounts = Approve {
+            to: mint_ata.clone(),                   // The token account holding the NFT
+            delegate: stake_account.clone(),        // The stake account becomes delegate
+            authority: self.user.to_account_info(), // User authorizes this delegation
+        };
+        approve(
+            CpiContext::new(self.token_program.to_account_info(), cpi_accounts),
+            1, // Exactly 1 token (the NFT)
+        )?;
+
+        // Freeze the NFT so it cannot be transferred while staked
+        FreezeDelegatedAccountCpi::new(
+            &self.metadata_program.to_account_info(),
+            FreezeDelegatedAccountCpiAccounts {
+                delegate: stake_account, // Stake account that now controls the NFT
+                token_account: mint_ata, // Token account holding the NFT
+                edition,                 // Master edition account
+                mint,                    // NFT mint
+                token_program: &self.token_program.to_account_info(), // SPL Token program
+            },
+        )
+        .invoke_signed(signer_seeds)?; // Sign with stake account PDA
+
+        Ok(mint.key())
+    }
+}
@@ -0,0 +1,244 @@
+#![allow(unexpected_cfgs)] // Allow compiler warnings for unrecognized configuration flags
+
+use anchor_lang::prelude::*; // Import essential Anchor framework items
+use anchor_lang::solana_program::sysvar; // For the instructions sysvar address
+                                         // Import SPL Token and Metadata program types for NFT handling
+use anchor_spl::{
+    metadata::{mpl_token_metadata, MasterEditionAccount, Metadata, MetadataAccount},
+    token::{Mint, Token, TokenAccount},
+};
+// Import Metaplex instructions for delegating and locking programmable NFTs
+use mpl_token_metadata::{
+    instructions::{
+        DelegateStakingV1Cpi, DelegateStakingV1CpiAccounts, DelegateStakingV1InstructionArgs,
+        LockV1Cpi, LockV1CpiAccounts, LockV1InstructionArgs,
+    },
+    types::TokenStandard,
+};
+// Import pause flags and program state structures
+use crate::constants::PAUSE_STAKE;
+use crate::state::stake_account::StakeAccount;
+use crate::state::stake_config::StakeConfig;
+use crate::state::user_accounts::UserAccount;
+// Import custom error types
+use crate::error::ErrorCode;
+use crate::events::Staked; // Event recording the stake
+
+// Account validation struct for staking a programmable NFT (pNFT)
+// pNFT token accounts are always frozen by Token Metadata, so instead of
+// FreezeDelegatedAccount the stake PDA becomes the staking delegate and locks the token
+#[event_cpi]
+#[derive(Accounts)]
+pub struct StakePnft<'info> {
+    #[account(mut)] // Account can be modified (pays for stake account and token record updates)
+    pub user: Signer<'info>, // The user staking their pNFT
+
+    pub mint: Account<'info, Mint>, // The pNFT mint being staked
+
+    #[account(
+        mut, // Account will be modified (delegate is set by Token Metadata)
+        associated_token::mint = mint, // Must be ATA for the specific pNFT mint
+        associated_token::authority = user, // Must be owned by the user
+    )]
+    pub mint_ata: Account<'info, TokenAccount>, // User's token account holding the pNFT
+
+    #[account(
+        mut, // Account will be modified (Token Metadata updates it during delegation)
+        seeds = [
+            b"metadata", // Metaplex metadata PDA seed
+            metadata_program.key().as_ref(), // Metadata program ID
+            mint.key().as_ref() // pNFT mint address
+        ],
+        seeds::program = metadata_program.key(), // Use metadata program for PDA derivation
+        bump, // Anchor finds the canonical bump automatically
+        constraint = metadata.token_standard == Some(TokenStandard::ProgrammableNonFungible)
+            @ ErrorCode::NotProgrammable, // Only pNFTs use this path
+        constraint = metadata.collection.is_some() @ ErrorCode::MissingCollection, // pNFT must belong to a collection
+        constraint = metadata
+            .collection
+            .as_ref()
+            .is_some_and(|collection| collection.key == config.collection)
+            @ ErrorCode::CollectionMismatch, // Verify pNFT belongs to the pool's collection
+        constraint = metadata
+            .collection
+            .as_ref()
+            .is_some_and(|collection| collection.verified)
+            @ ErrorCode::CollectionNotVerified, // Verify collection is verified by creator
+    )]
+    pub metadata: Account<'info, MetadataAccount>, // pNFT metadata account
+
+    #[account(
+        seeds = [
+            b"metadata", // Metaplex metadata PDA seed
+            metadata_program.key().as_ref(), // Metadata program ID
+            mint.key().as_ref(), // pNFT mint address
+            b"edition" // Master edition seed
+        ],
+        seeds::program = metadata_program.key(), // Use metadata program for PDA derivation
+        bump, // Anchor finds the canonical bump automatically
+    )]
+    pub edition: Account<'info, MasterEditionAccount>, // pNFT master edition account
+
+    /// CHECK: Token record PDA, validated by seeds and by Token Metadata during the CPI
+    #[account(
+        mut, // Account will be modified (delegate and lock state are recorded here)
+        seeds = [
+            b"metadata", // Metaplex metadata PDA seed
+            metadata_program.key().as_ref(), // Metadata program ID
+            mint.key().as_ref(), // pNFT mint address
+            b"token_record", // Token record seed
+            mint_ata.key().as_ref() // Token account the record belongs to
+        ],
+        seeds::program = metadata_program.key(), // Use metadata program for PDA derivation
+        bump, // Anchor finds the canonical bump automatically
+    )]
+    pub token_record: UncheckedAccount<'info>, // pNFT token record
+
+    #[account(
+        mut, // Account will be modified (pool reward index and staked count)
+        seeds = [b"config".as_ref(), config.collection.as_ref(), config.pool_id.to_le_bytes().as_ref()], // Pool config PDA seeds
+        bump = config.bump, // Use stored bump from config
+        constraint = !config.emergency && !config.is_paused(PAUSE_STAKE) @ ErrorCode::PoolPaused, // Staking must be enabled
+        constraint = !config.custody @ ErrorCode::WrongCustodyMode, // Pool freezes NFTs in place
+    )]
+    pub config: Account<'info, StakeConfig>, // Pool staking configuration
+
+    #[account(
+        init, // Create new stake record for this pNFT
+        payer = user, // User pays for stake account creation
+        space = 8 + StakeAccount::INIT_SPACE, // 8 bytes discriminator + struct size
+        seeds = [b"stake".as_ref(), mint.key().as_ref(), config.key().as_ref()], // Unique PDA per NFT per config
+        bump, // Anchor finds the canonical bump automatically
+    )]
+    pub stake_account: Account<'info, StakeAccount>, // Individual stake record being created
+
+    #[account(
+        mut, // Account will be modified (amount_staked will increase)
+        seeds = [b"user".as_ref(), config.key().as_ref(), user.key().as_ref()], // User's staking account PDA for this pool
+        bump = user_account.bump, // Use stored bump from user account
+    )]
+    pub user_account: Account<'info, UserAccount>, // User's overall staking statistics
+
+    /// CHECK: Rule set program, validated by Token Metadata against the pNFT's programmable config
+    pub authorization_rules_program: Option<UncheckedAccount<'info>>,
+    /// CHECK: Rule set account, validated by Token Metadata against the pNFT's programmable config
+    pub authorization_rules: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Instructions sysvar, required by Token Metadata for rule set evaluation
+    #[account(address = sysvar::instructions::ID)]
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    // Required Solana programs
+    pub system_program: Program<'info, System>, // For account creation
+    pub token_program: Program<'info, Token>,   // For token operations
+    pub metadata_program: Program<'info, Metadata>, // For pNFT delegate and lock operations
+}
+
+// Implementation block containing the pNFT staking logic
+impl<'info> StakePnft<'info> {
+    // Function to stake a pNFT and start earning rewards
+    pub fn stake_pnft(&mut self, tier: u8, bumps: &StakePnftBumps) -> Result<Staked> {
+        // Count the pNFT against the user's staking limit
+        self.user_account.record_stake(&self.config)?;
+
+        // Create the stake record with current timestamp
+        let now = Clock::get()?.unix_timestamp;
+        self.stake_account.set_inner(StakeAccount::new(
+            &mut self.config,    // Pool whose staked count and reward index are updated
+            self.user.key(),     // Store who staked this pNFT
+            self.mint.key(),     // Store which pNFT was staked
+            tier,                // Lock tier and multiplier the staker chose
+            now,                 // Store when it was staked (for freeze period)
+            bumps.stake_account, // Store PDA bump for future lookups
+        )?);
+
+        // Prepare accounts shared by the delegate and lock CPIs
+        let stake_account = &self.stake_account.to_account_info();
+        let user = &self.user.to_account_info();
+        let token = &self.mint_ata.to_account_info();
+        let mint = &self.mint.to_account_info();
+        let metadata = &self.metadata.to_account_info();
+        let edition = &self.edition.to_account_info();
+        let token_record = &self.token_record.to_account_info();
+        let system_program = &self.system_program.to_account_info();
+        let sysvar_instructions = &self.sysvar_instructions.to_account_info();
+        let token_program = &self.token_program.to_account_info();
+        let metadata_program = &self.metadata_program.to_account_info();
+        let authorization_rules_program = self
+            .authorization_rules_program
+            .as_ref()
+            .map(|program| program.to_account_info());
+        let authorization_rules = self
+            .authorization_rules
+            .as_ref()
+            .map(|rules| rules.to_account_info());
+
+        // Make the stake account the pNFT's staking delegate (signed by the owner)
+        DelegateStakingV1Cpi::new(
+            metadata_program,
+            DelegateStakingV1CpiAccounts {
+                delegate_record: None,            // Token delegates live in the token record
+                delegate: stake_account,          // The stake account becomes delegate
+                metadata,                         // pNFT metadata
+                master_edition: Some(edition),    // pNFT master edition
+                token_record: Some(token_record), // Token record tracking the delegate
+                mint,                             // pNFT mint
+                token,                            // Token account holding the pNFT
+                authority: user,                  // User authorizes this delegation
+                payer: user,                      // User pays for any record updates
+                system_program,                   // Solana system program
+                sysvar_instructions,              // Instructions sysvar
+                spl_token_program: Some(token_program), // SPL Token program
+                authorization_rules_program: authorization_rules_program.as_ref(), // Rule set program
+                authorization_rules: authorization_rules.as_ref(), // Rule set account
+            },
+            DelegateStakingV1InstructionArgs {
+                amount: 1,                // Delegate exactly 1 token (the pNFT)
+                authorization_data: None, // No rule set payload needed
+            },
+        )
+        .invoke()?;
+
+        // Generate PDA signer seeds for the stake account to sign on behalf of the program
+        let seeds: &[&[u8]; 4] = &[
+            b"stake",                                   // Stake PDA seed
+            self.mint.to_account_info().key.as_ref(),   // pNFT mint address
+            self.config.to_account_info().key.as_ref(), // Config address
+            &[self.stake_account.bump],                 // PDA bump
+        ];
+        let signer_seeds = &[&seeds[..]]; // Format for CPI signing
+
+        // Lock the pNFT so it cannot be transferred while staked
+        LockV1Cpi::new(
+            metadata_program,
+            LockV1CpiAccounts {
+                authority: stake_account,               // Staking delegate locks the token
+                token_owner: Some(user),                // Owner of the token account
+                token,                                  // Token account holding the pNFT
+                mint,                                   // pNFT mint
+                metadata,                               // pNFT metadata
+                edition: Some(edition),                 // pNFT master edition
+                token_record: Some(token_record),       // Token record tracking the lock
+                payer: user,                            // User pays for any record updates
+                system_program,                         // Solana system program
+                sysvar_instructions,                    // Instructions sysvar
+                spl_token_program: Some(token_program), // SPL Token program
+                authorization_rules_program: authorization_rules_program.as_ref(), // Rule set program
+                authorization_rules: authorization_rules.as_ref(), // Rule set account
+            },
+            LockV1InstructionArgs {
+                authorization_data: None, // No rule set payload needed
+            },
+        )
+        .invoke_signed(signer_seeds)?; // Sign with stake account PDA
+
+        Ok(Staked {
+            pool: self.config.key(),
+            owner: self.user.key(),
+            mint: self.stake_account.mint,
+            tier,
+            amount_staked: self.user_account.amount_staked,
+            timestamp: now,
+        })
+    }
+}
@@ -0,0 +1,157 @@
+#![allow(unexpected_cfgs)] // Allow compiler warnings for unrecognized configuration flags
+
+use anchor_lang::prelude::*; // Import essential Anchor framework items
+                             // Import Token-2022 interface types and extension helpers
+use anchor_spl::token_interface::{
+    freeze_account, get_mint_extension_data,
+    spl_token_2022::extension::group_member_pointer::GroupMemberPointer, FreezeAccount, Mint,
+    TokenAccount, TokenInterface,
+};
+use spl_token_group_interface::state::TokenGroupMember; // Group member extension data
+
+// Import pause flags and program state structures
+use crate::constants::PAUSE_STAKE;
+use crate::state::stake_account::StakeAccount;
+use crate::state::stake_config::StakeConfig;
+use crate::state::user_accounts::UserAccount;
+// Import custom error types
+use crate::error::ErrorCode;
+use crate::events::Staked; // Event recording the stake
+
+// Account validation struct for staking a Token-2022 NFT
+// The program's freeze PDA freezes the user's token account in place (freeze authority rules: README "Stake Token-2022 NFT")
+#[event_cpi]
+#[derive(Accounts)]
+pub struct StakeToken22<'info> {
+    #[account(mut)] // Account can be modified (pays for stake account creation)
+    pub user: Signer<'info>, // The user staking their NFT
+
+    /// CHECK: Program-wide PDA with no data, only signs freeze and thaw CPIs
+    #[account(
+        seeds = [b"freeze".as_ref()], // Shared by every pool
+        bump, // Anchor finds the canonical bump automatically
+    )]
+    pub freeze_authority: UncheckedAccount<'info>, // Freeze authority of stakeable Token-2022 mints
+
+    #[account(
+        mint::token_program = token_program, // Mint must belong to the given token program
+        constraint = mint.decimals == 0 && mint.supply == 1 @ ErrorCode::InvalidNftMint, // Must be a 1-of-1 mint
+        constraint = Option::<Pubkey>::from(mint.freeze_authority) == Some(freeze_authority.key())
+            @ ErrorCode::InvalidFreezeAuthority, // Only the program can freeze and thaw it
+    )]
+    pub mint: InterfaceAccount<'info, Mint>, // The NFT mint being staked
+
+    #[account(
+        mut, // Account will be modified (frozen while staked)
+        associated_token::mint = mint, // Must be ATA for the specific NFT mint
+        associated_token::authority = user, // Must be owned by the user
+        associated_token::token_program = token_program, // Token-2022 ATA
+        constraint = mint_ata.amount == 1 @ ErrorCode::NftNotHeld, // User must hold the NFT
+    )]
+    pub mint_ata: InterfaceAccount<'info, TokenAccount>, // User's token account holding the NFT
+
+    #[account(
+        mut, // Account will be modified (pool reward index and staked count)
+        seeds = [b"config".as_ref(), config.collection.as_ref(), config.pool_id.to_le_bytes().as_ref()], // Pool config PDA seeds
+        bump = config.bump, // Use stored bump from config
+        constraint = !config.emergency && !config.is_paused(PAUSE_STAKE) @ ErrorCode::PoolPaused, // Staking must be enabled
+        constraint = !config.custody @ ErrorCode::WrongCustodyMode, // Pool freezes NFTs in place
+    )]
+    pub config: Account<'info, StakeConfig>, // Pool staking configuration
+
+    #[account(
+        init, // Create new stake record for this NFT
+        payer = user, // User pays for stake account creation
+        space = 8 + StakeAccount::INIT_SPACE, // 8 bytes discriminator + struct size
+        seeds = [b"stake".as_ref(), mint.key().as_ref(), config.key().as_ref()], // Unique PDA per NFT per config
+        bump, // Anchor finds the canonical bump automatically
+    )]
+    pub stake_account: Account<'info, StakeAccount>, // Individual stake record being created
+
+    #[account(
+        mut, // Account will be modified (amount_staked will increase)
+        seeds = [b"user".as_ref(), config.key().as_ref(), user.key().as_ref()], // User's staking account PDA for this pool
+        bump = user_account.bump, // Use stored bump from user account
+    )]
+    pub user_account: Account<'info, UserAccount>, // User's overall staking statistics
+
+    // Required Solana programs
+    pub system_program: Program<'info, System>, // For account creation
+    pub token_program: Interface<'info, TokenInterface>, // Token-2022 program for freezing
+}
+
+// Implementation block containing the Token-2022 staking logic
+impl<'info> StakeToken22<'info> {
+    // Function to stake a Token-2022 NFT and start earning rewards
+    pub fn stake_token22(&mut self, tier: u8, bumps: &StakeToken22Bumps) -> Result<Staked> {
+        // Verify the NFT is a member of the pool's token group
+        self.verify_group_member()?;
+
+        // Count the NFT against the user's staking limit
+        self.user_account.record_stake(&self.config)?;
+
+        // Create the stake record with current timestamp
+        let now = Clock::get()?.unix_timestamp;
+        self.stake_account.set_inner(StakeAccount::new(
+            &mut self.config,    // Pool whose staked count and reward index are updated
+            self.user.key(),     // Store who staked this NFT
+            self.mint.key(),     // Store which NFT was staked
+            tier,                // Lock tier and multiplier the staker chose
+            now,                 // Store when it was staked (for freeze period)
+            bumps.stake_account, // Store PDA bump for future lookups
+        )?);
+
+        // Freeze PDA seeds for signing as the mint's freeze authority
+        let seeds: &[&[u8]] = &[
+            b"freeze",                 // Freeze PDA seed
+            &[bumps.freeze_authority], // PDA bump
+        ];
+        let signer = &[seeds]; // Format for CPI signing
+
+        // Freeze the user's token account so the NFT cannot move while staked
+        // (an NFT already frozen by another pool's stake fails here)
+        let cpi_accounts = FreezeAccount {
+            account: self.mint_ata.to_account_info(), // The token account holding the NFT
+            mint: self.mint.to_account_info(),        // The NFT mint
+            authority: self.freeze_authority.to_account_info(), // Freeze PDA is the freeze authority
+        };
+        let cpi_ctx =
+            CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, signer);
+        freeze_account(cpi_ctx)?;
+
+        Ok(Staked {
+            pool: self.config.key(),
+            owner: self.user.key(),
+            mint: self.stake_account.mint,
+            tier,
+            amount_staked: self.user_account.amount_staked,
+            timestamp: now,
+        })
+    }
+
+    // Function to check the mint's group member extension against the pool collection
+    // The member data must live on the mint itself, where only the group authority can write it
+    fn verify_group_member(&self) -> Result<()> {
+        let mint = self.mint.to_account_info();
+
+        // The group member pointer must point back at the mint
+        let pointer = get_mint_extension_data::<GroupMemberPointer>(&mint)
+            .map_err(|_| ErrorCode::MissingCollection)?;
+        require!(
+            Option::<Pubkey>::from(pointer.member_address) == Some(mint.key()),
+            ErrorCode::MissingCollection
+        );
+
+        // The member extension must name this mint and the pool's group
+        let member = get_mint_extension_data::<TokenGroupMember>(&mint)
+            .map_err(|_| ErrorCode::MissingCollection)?;
+        require_keys_eq!(member.mint, mint.key(), ErrorCode::CollectionMismatch);
+        require_keys_eq!(
+            member.group,
+            self.config.collection,
+            ErrorCode::CollectionMismatch
+        );
+
+        Ok(())
+    }
+}
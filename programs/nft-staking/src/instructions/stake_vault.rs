@@ -0,0 +1,142 @@
+#![allow(unexpected_cfgs)] // Allow compiler warnings for unrecognized configuration flags
+
+use anchor_lang::prelude::*; // Import essential Anchor framework items
+                             // Import token interface, associated token and Metadata program types
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{Metadata, MetadataAccount},
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+// Import pause flags and program state structures
+use crate::constants::PAUSE_STAKE;
+use crate::state::stake_account::StakeAccount;
+use crate::state::stake_config::StakeConfig;
+use crate::state::user_accounts::UserAccount;
+// Import custom error types
+use crate::error::ErrorCode;
+use crate::events::Staked; // Event recording the stake
+
+// Account validation struct for staking an NFT into the pool's vault
+// Custody pools move the NFT into an ATA owned by the config PDA instead of
+// freezing it in place, so no master edition or freeze authority is needed
+#[event_cpi]
+#[derive(Accounts)]
+pub struct StakeVault<'info> {
+    #[account(mut)] // Account can be modified (pays for stake account and vault creation)
+    pub user: Signer<'info>, // The user staking their NFT
+
+    #[account(
+        mint::token_program = token_program, // Mint must belong to the given token program
+        constraint = mint.decimals == 0 && mint.supply == 1 @ ErrorCode::InvalidNftMint, // Must be a 1-of-1 mint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>, // The NFT mint being staked
+
+    #[account(
+        mut, // Account will be modified (NFT is transferred out)
+        associated_token::mint = mint, // Must be ATA for the specific NFT mint
+        associated_token::authority = user, // Must be owned by the user
+        associated_token::token_program = token_program, // ATA of the mint's token program
+    )]
+    pub mint_ata: InterfaceAccount<'info, TokenAccount>, // User's token account holding the NFT
+
+    #[account(
+        seeds = [
+            b"metadata", // Metaplex metadata PDA seed
+            metadata_program.key().as_ref(), // Metadata program ID
+            mint.key().as_ref() // NFT mint address
+        ],
+        seeds::program = metadata_program.key(), // Use metadata program for PDA derivation
+        bump, // Anchor finds the canonical bump automatically
+        constraint = metadata.collection.is_some() @ ErrorCode::MissingCollection, // NFT must belong to a collection
+        constraint = metadata
+            .collection
+            .as_ref()
+            .is_some_and(|collection| collection.key == config.collection)
+            @ ErrorCode::CollectionMismatch, // Verify NFT belongs to the pool's collection
+        constraint = metadata
+            .collection
+            .as_ref()
+            .is_some_and(|collection| collection.verified)
+            @ ErrorCode::CollectionNotVerified, // Verify collection is verified by creator
+    )]
+    pub metadata: Account<'info, MetadataAccount>, // NFT metadata account
+
+    #[account(
+        mut, // Account will be modified (pool reward index and staked count)
+        seeds = [b"config".as_ref(), config.collection.as_ref(), config.pool_id.to_le_bytes().as_ref()], // Pool config PDA seeds
+        bump = config.bump, // Use stored bump from config
+        constraint = !config.emergency && !config.is_paused(PAUSE_STAKE) @ ErrorCode::PoolPaused, // Staking must be enabled
+        constraint = config.custody @ ErrorCode::WrongCustodyMode, // Pool holds NFTs in its vault
+    )]
+    pub config: Account<'info, StakeConfig>, // Pool staking configuration
+
+    #[account(
+        init_if_needed, // Anyone can create an ATA, so tolerate an existing empty vault
+        payer = user, // User pays for vault creation
+        associated_token::mint = mint, // Vault holds this NFT
+        associated_token::authority = config, // Config PDA owns the vault
+        associated_token::token_program = token_program, // ATA of the mint's token program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>, // Pool vault for this NFT
+
+    #[account(
+        init, // Create new stake record for this NFT
+        payer = user, // User pays for stake account creation
+        space = 8 + StakeAccount::INIT_SPACE, // 8 bytes discriminator + struct size
+        seeds = [b"stake".as_ref(), mint.key().as_ref(), config.key().as_ref()], // Unique PDA per NFT per config
+        bump, // Anchor finds the canonical bump automatically
+    )]
+    pub stake_account: Account<'info, StakeAccount>, // Individual stake record being created
+
+    #[account(
+        mut, // Account will be modified (amount_staked will increase)
+        seeds = [b"user".as_ref(), config.key().as_ref(), user.key().as_ref()], // User's staking account PDA for this pool
+        bump = user_account.bump, // Use stored bump from user account
+    )]
+    pub user_account: Account<'info, UserAccount>, // User's overall staking statistics
+
+    // Required Solana programs
+    pub system_program: Program<'info, System>, // For account creation
+    pub token_program: Interface<'info, TokenInterface>, // For the NFT transfer
+    pub associated_token_program: Program<'info, AssociatedToken>, // For vault creation
+    pub metadata_program: Program<'info, Metadata>, // For metadata PDA derivation
+}
+
+// Implementation block containing the vault staking logic
+impl<'info> StakeVault<'info> {
+    // Function to stake an NFT into the vault and start earning rewards
+    pub fn stake_vault(&mut self, tier: u8, bumps: &StakeVaultBumps) -> Result<Staked> {
+        // Count the NFT against the user's staking limit
+        self.user_account.record_stake(&self.config)?;
+
+        // Create the stake record with current timestamp
+        let now = Clock::get()?.unix_timestamp;
+        self.stake_account.set_inner(StakeAccount::new(
+            &mut self.config,    // Pool whose staked count and reward index are updated
+            self.user.key(),     // Store who staked this NFT
+            self.mint.key(),     // Store which NFT was staked
+            tier,                // Lock tier and multiplier the staker chose
+            now,                 // Store when it was staked (for freeze period)
+            bumps.stake_account, // Store PDA bump for future lookups
+        )?);
+
+        // Move the NFT from the user's ATA into the pool vault
+        let cpi_accounts = TransferChecked {
+            from: self.mint_ata.to_account_info(),  // User's token account
+            mint: self.mint.to_account_info(),      // The NFT mint
+            to: self.vault.to_account_info(),       // Pool vault
+            authority: self.user.to_account_info(), // User authorizes the transfer
+        };
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+        transfer_checked(cpi_ctx, 1, self.mint.decimals)?; // Exactly 1 token (the NFT)
+
+        Ok(Staked {
+            pool: self.config.key(),
+            owner: self.user.key(),
+            mint: self.stake_account.mint,
+            tier,
+            amount_staked: self.user_account.amount_staked,
+            timestamp: now,
+        })
+    }
+}
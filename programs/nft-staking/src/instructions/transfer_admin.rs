@@ -0,0 +1,38 @@
+#![allow(unexpected_cfgs)] // Allow compiler warnings for unrecognized configuration flags
+
+use anchor_lang::prelude::*; // Import essential Anchor framework items
+
+use crate::error::ErrorCode; // Import custom error types
+use crate::events::AdminTransferStarted; // Event recording the nomination
+use crate::state::StakeConfig; // Import the global configuration structure
+
+// Account validation struct for offering the admin role to a new wallet
+// The transfer only completes once the new admin calls `accept_admin`
+#[event_cpi]
+#[derive(Accounts)]
+pub struct TransferAdmin<'info> {
+    pub admin: Signer<'info>, // The current config admin
+
+    #[account(
+        mut, // Account will be modified (pending admin is recorded)
+        seeds = [b"config".as_ref(), config.collection.as_ref(), config.pool_id.to_le_bytes().as_ref()], // Pool config PDA seeds
+        bump = config.bump, // Use stored bump from config
+        has_one = admin @ ErrorCode::Unauthorized, // Only the stored admin may transfer
+    )]
+    pub config: Account<'info, StakeConfig>, // Pool staking configuration
+}
+
+// Implementation block containing the transfer logic
+impl<'info> TransferAdmin<'info> {
+    // Function to nominate a new admin (replaces any earlier nomination)
+    pub fn transfer_admin(&mut self, new_admin: Pubkey) -> Result<AdminTransferStarted> {
+        self.config.pending_admin = Some(new_admin); // Recorded until accepted
+
+        Ok(AdminTransferStarted {
+            pool: self.config.key(),
+            admin: self.admin.key(),
+            pending_admin: new_admin,
+            timestamp: Clock::get()?.unix_timestamp,
+        })
+    }
+}
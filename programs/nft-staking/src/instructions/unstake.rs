@@ -1,123 +1,140 @@
 #![allow(unexpected_cfgs)] // Allow compiler warnings for unrecognized configuration flags
 
-// Import custom error types and state structures
+// Import pause flags, custom error types and state structures
+use crate::constants::PAUSE_UNSTAKE;
 use crate::error::ErrorCode;
+use crate::events::Unstaked; // Event recording the unstake
 use crate::state::*;
-// Import essential Anchor and SPL Token types
+// Import essential Anchor, SPL Token and Metadata program types
 use anchor_lang::prelude::*;
 use anchor_spl::{
-    associated_token::AssociatedToken, // For associated token account operations
-    token::{transfer, Mint, Token, TokenAccount, Transfer}, // For token transfer operations
+    metadata::{mpl_token_metadata, MasterEditionAccount, Metadata}, // For NFT edition and thaw CPI
+    token::{revoke, Mint, Revoke, Token, TokenAccount}, // For removing the stake delegate
 };
+// Import Metaplex instruction for thawing delegated NFTs
+use mpl_token_metadata::instructions::{ThawDelegatedAccountCpi, ThawDelegatedAccountCpiAccounts};
 
 // Account validation struct for unstaking an NFT
-// Defines all accounts needed and their validation constraints
+// Mirrors the accounts used by `Stake` so the freeze can be reversed in place
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Unstake<'info> {
     /// User unstaking their NFT
     #[account(mut)] // Account can be modified (receives rent from closed stake account)
     pub user: Signer<'info>, // The user unstaking their NFT
 
-    /// User account tracking staked amount and points
+    /// NFT mint being unstaked
+    pub mint: Account<'info, Mint>, // The NFT mint being unstaked
+
+    /// User's token account holding the frozen NFT
     #[account(
-        mut, // Account will be modified (amount_staked decreases, points increase)
-        seeds = [b"user", user.key.as_ref()], // User's staking account PDA
-        bump = user_account.bump // Use stored bump from user account
+        mut, // Account will be modified (thawed and delegate revoked)
+        associated_token::mint = mint, // Must be ATA for the specific NFT mint
+        associated_token::authority = user, // Must be owned by the user
     )]
-    pub user_account: Account<'info, UserAccount>, // User's overall staking statistics
+    pub mint_ata: Account<'info, TokenAccount>, // User's token account holding the NFT
 
-    /// Global staking config
+    /// Master edition of the NFT (required by the thaw CPI)
     #[account(
-        mut, // Account might be modified (though not in current implementation)
-        seeds = [b"config"], // Global config PDA seed
-        bump = config.bump // Use stored bump from config
+        seeds = [
+            b"metadata", // Metaplex metadata PDA seed
+            metadata_program.key().as_ref(), // Metadata program ID
+            mint.key().as_ref(), // NFT mint address
+            b"edition" // Master edition seed
+        ],
+        seeds::program = metadata_program.key(), // Use metadata program for PDA derivation
+        bump, // Anchor finds the canonical bump automatically
     )]
-    pub config: Account<'info, StakeConfig>, // Global staking configuration
+    pub edition: Account<'info, MasterEditionAccount>, // NFT master edition account
 
-    /// NFT mint being unstaked
-    pub nft_mint: Account<'info, Mint>, // The NFT mint being unstaked
+    /// Pool staking config
+    #[account(
+        mut, // Account will be modified (pool reward index and staked count)
+        seeds = [b"config".as_ref(), config.collection.as_ref(), config.pool_id.to_le_bytes().as_ref()], // Pool config PDA seeds
+        bump = config.bump, // Use stored bump from config
+        constraint = config.emergency || !config.is_paused(PAUSE_UNSTAKE) @ ErrorCode::PoolPaused, // Emergency mode always allows unstaking
+    )]
+    pub config: Account<'info, StakeConfig>, // Pool staking configuration
 
     /// Stake record for this NFT, to be closed after unstaking
     #[account(
         mut, // Account will be modified (closed and rent returned)
-        seeds = [b"stake", user.key.as_ref(), nft_mint.key().as_ref()], // Stake account PDA
+        seeds = [b"stake".as_ref(), mint.key().as_ref(), config.key().as_ref()], // Same PDA used by `Stake`
         bump = stake_account.bump, // Use stored bump from stake account
-        close = user  // Return rent to user when account is closed
+        constraint = stake_account.owner == user.key() @ ErrorCode::WrongOwner, // Only the original staker can unstake
+        close = user // Return rent to user when account is closed
     )]
     pub stake_account: Account<'info, StakeAccount>, // Individual stake record being closed
 
-    /// Vault holding the staked NFT
-    #[account(
-        mut, // Account will be modified (NFT will be transferred out)
-        seeds = [b"vault", nft_mint.key().as_ref()], // Vault PDA for this specific NFT
-        bump, // Anchor finds the canonical bump automatically
-    )]
-    pub vault_ata: Account<'info, TokenAccount>, // Token account that held the staked NFT
-
-    /// User's token account to receive NFT
+    /// User account tracking staked amount and points
     #[account(
-        mut, // Account will be modified (receives the unstaked NFT)
-        associated_token::mint = nft_mint, // Must be ATA for the specific NFT mint
-        associated_token::authority = user, // Must be owned by the user
+        mut, // Account will be modified (amount_staked decreases, points increase)
+        seeds = [b"user".as_ref(), config.key().as_ref(), user.key().as_ref()], // User's staking account PDA for this pool
+        bump = user_account.bump // Use stored bump from user account
     )]
-    pub user_nft_ata: Account<'info, TokenAccount>, // User's token account to receive NFT back
+    pub user_account: Account<'info, UserAccount>, // User's overall staking statistics
 
     /// Programs
-    pub token_program: Program<'info, Token>, // SPL Token program for transfers
-    pub associated_token_program: Program<'info, AssociatedToken>, // For ATA operations
-    pub system_program: Program<'info, System>,                    // For account operations
-    pub rent: Sysvar<'info, Rent>,                                 // Rent sysvar for calculations
-    pub clock: Sysvar<'info, Clock>, // Clock sysvar for timestamp verification
+    pub token_program: Program<'info, Token>, // SPL Token program for revoking the delegate
+    pub metadata_program: Program<'info, Metadata>, // For thawing the NFT
 }
 
 // Implementation block containing the unstaking logic
 impl<'info> Unstake<'info> {
-    // Function to unstake an NFT and claim earned rewards
-    pub fn unstake(&mut self) -> Result<()> {
-        // Check that the freeze period has passed
+    // Function to unstake an NFT and credit the points it accrued
+    pub fn unstake(&mut self) -> Result<Unstaked> {
+        // Check the freeze period (skipped in emergency mode) and settle this NFT's points
         let now = Clock::get()?.unix_timestamp; // Get current timestamp
-        require!(
-            now - self.stake_account.staked_at >= self.config.freeze_period as i64, // Check if enough time has passed
-            ErrorCode::TimeNotElapsed // Error if freeze period not over
-        );
-
-        // Ensure user has at least one NFT staked
-        require!(
-            self.user_account.amount_staked > 0, // Verify user has staked NFTs
-            ErrorCode::MaxStake                  // Reusing MaxStake error for this validation
-        );
-
-        // Decrease the user's staked NFT count
-        self.user_account.amount_staked = self
-            .user_account
-            .amount_staked
-            .checked_sub(1) // Safely subtract 1 to prevent underflow
-            .ok_or(ErrorCode::Underflow)?; // Return error if underflow would occur
-
-        // Increase user's reward points (this NFT's reward)
-        self.user_account.points = self
-            .user_account
-            .points
-            .checked_add(self.config.points_per_stake as u32) // Safely add reward points
-            .ok_or(ErrorCode::Overflow)?; // Return error if overflow would occur
-
-        // Generate signer seeds for config PDA
-        let seeds: &[&[u8]] = &[b"config", &[self.config.bump]]; // Config PDA seeds
-        let signer: &[&[&[u8]]; 1] = &[seeds]; // Format for CPI signing
-
-        // Transfer the NFT token from vault ATA back to user's wallet
-        let cpi_accounts = Transfer {
-            from: self.vault_ata.to_account_info(), // Source: vault holding the NFT
-            to: self.user_nft_ata.to_account_info(), // Destination: user's token account
-            authority: self.config.to_account_info(), // Authority: config PDA (vault owner)
+        let earned = self.stake_account.finish(&mut self.config, now)?;
+        self.user_account.record_unstake(earned)?; // Decrease staked count and credit points
+
+        // Generate PDA signer seeds for the stake account (it is the freeze delegate)
+        let seeds: &[&[u8]; 4] = &[
+            b"stake",                                   // Stake PDA seed
+            self.mint.to_account_info().key.as_ref(),   // NFT mint address
+            self.config.to_account_info().key.as_ref(), // Config address
+            &[self.stake_account.bump],                 // PDA bump
+        ];
+        let signer_seeds = &[&seeds[..]]; // Format for CPI signing
+
+        // Prepare accounts for thawing the NFT
+        let delegate = &self.stake_account.to_account_info();
+        let token_account = &self.mint_ata.to_account_info();
+        let edition = &self.edition.to_account_info();
+        let mint = &self.mint.to_account_info();
+        let token_program = &self.token_program.to_account_info();
+        let metadata_program = &self.metadata_program.to_account_info();
+
+        // Thaw the NFT so the user can transfer it again
+        ThawDelegatedAccountCpi::new(
+            metadata_program,
+            ThawDelegatedAccountCpiAccounts {
+                delegate,      // Stake account that controls the NFT
+                token_account, // Token account holding the NFT
+                edition,       // Master edition account
+                mint,          // NFT mint
+                token_program, // SPL Token program
+            },
+        )
+        .invoke_signed(signer_seeds)?; // Sign with stake account PDA
+
+        // Revoke the stake account's delegation over the NFT
+        let cpi_program = self.token_program.to_account_info();
+        let cpi_accounts = Revoke {
+            source: self.mint_ata.to_account_info(), // The token account holding the NFT
+            authority: self.user.to_account_info(),  // User removes the delegate
         };
-
-        let cpi_ctx =
-            CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, signer);
-
-        // Only 1 NFT is transferred
-        transfer(cpi_ctx, 1)?; // Transfer exactly 1 token (the NFT)
-
-        Ok(()) // Return success (stake account automatically closed due to close constraint)
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        revoke(cpi_ctx)?;
+
+        // Stake account is closed automatically due to the close constraint
+        Ok(Unstaked {
+            pool: self.config.key(),
+            owner: self.user.key(),
+            mint: self.stake_account.mint,
+            points: earned,
+            amount_staked: self.user_account.amount_staked,
+            timestamp: now,
+        })
     }
 }
@@ -0,0 +1,157 @@
+#![allow(unexpected_cfgs)] // Allow compiler warnings for unrecognized configuration flags
+
+// Import pause flags, Bubblegum program ids, custom error types and state structures
+use crate::constants::{MPL_ACCOUNT_COMPRESSION_ID, MPL_NOOP_ID, PAUSE_UNSTAKE};
+use crate::error::ErrorCode;
+use crate::events::Unstaked; // Event recording the unstake
+use crate::state::*;
+
+use super::stake_cnft::CnftLeaf; // Leaf contents shared with `StakeCnft`
+
+// Import essential Anchor and Bubblegum types
+use anchor_lang::prelude::*;
+use mpl_bubblegum::{
+    hash::hash_collection_option,
+    instructions::{
+        ThawAndRevokeV2Cpi, ThawAndRevokeV2CpiAccounts, ThawAndRevokeV2InstructionArgs,
+    },
+    utils::get_asset_id,
+};
+
+// Account validation struct for unstaking a compressed NFT
+// Reverses `StakeCnft`: the stake PDA thaws the leaf and revokes its own delegation
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(leaf: CnftLeaf)]
+pub struct UnstakeCnft<'info> {
+    /// Leaf owner unstaking their cNFT
+    #[account(mut)] // Account can be modified (receives rent from closed stake account)
+    pub user: Signer<'info>, // The user unstaking their cNFT
+
+    /// CHECK: Tree config PDA, validated by seeds and by Bubblegum during the CPI
+    #[account(
+        mut, // Account will be modified by Bubblegum
+        seeds = [merkle_tree.key().as_ref()], // Tree config is derived from the tree address
+        seeds::program = mpl_bubblegum::ID, // Under the Bubblegum program
+        bump, // Anchor finds the canonical bump automatically
+    )]
+    pub tree_config: UncheckedAccount<'info>, // Bubblegum tree config
+
+    /// CHECK: Merkle tree, validated by the compression program during the CPI
+    #[account(mut)] // Account will be modified (leaf is replaced)
+    pub merkle_tree: UncheckedAccount<'info>, // Tree holding the cNFT leaf
+
+    /// Pool staking config
+    #[account(
+        mut, // Account will be modified (pool reward index and staked count)
+        seeds = [b"config".as_ref(), config.collection.as_ref(), config.pool_id.to_le_bytes().as_ref()], // Pool config PDA seeds
+        bump = config.bump, // Use stored bump from config
+        constraint = config.emergency || !config.is_paused(PAUSE_UNSTAKE) @ ErrorCode::PoolPaused, // Emergency mode always allows unstaking
+    )]
+    pub config: Account<'info, StakeConfig>, // Pool staking configuration
+
+    /// Stake record for this cNFT, to be closed after unstaking
+    #[account(
+        mut, // Account will be modified (closed and rent returned)
+        seeds = [
+            b"stake".as_ref(), // Stake PDA seed
+            get_asset_id(&merkle_tree.key(), leaf.nonce).as_ref(), // Same asset id used by `StakeCnft`
+            config.key().as_ref() // Config address
+        ],
+        bump = stake_account.bump, // Use stored bump from stake account
+        constraint = stake_account.owner == user.key() @ ErrorCode::WrongOwner, // Only the original staker can unstake
+        close = user // Return rent to user when account is closed
+    )]
+    pub stake_account: Account<'info, StakeAccount>, // Individual stake record being closed
+
+    /// User account tracking staked amount and points
+    #[account(
+        mut, // Account will be modified (amount_staked decreases, points increase)
+        seeds = [b"user".as_ref(), config.key().as_ref(), user.key().as_ref()], // User's staking account PDA for this pool
+        bump = user_account.bump // Use stored bump from user account
+    )]
+    pub user_account: Account<'info, UserAccount>, // User's overall staking statistics
+
+    /// CHECK: Bubblegum program, checked by address
+    #[account(address = mpl_bubblegum::ID)]
+    pub bubblegum_program: UncheckedAccount<'info>, // For thaw and revoke
+
+    /// CHECK: Noop program used by V2 trees for logging, checked by address
+    #[account(address = MPL_NOOP_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+
+    /// CHECK: Account compression program used by V2 trees, checked by address
+    #[account(address = MPL_ACCOUNT_COMPRESSION_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>, // Required by Bubblegum CPIs
+}
+
+// Implementation block containing the cNFT unstaking logic
+impl<'info> UnstakeCnft<'info> {
+    // Function to unstake a cNFT and credit the points it accrued
+    // `proof` are the Merkle proof nodes for the leaf
+    pub fn unstake_cnft(
+        &mut self,
+        leaf: CnftLeaf,
+        proof: &'info [AccountInfo<'info>],
+    ) -> Result<Unstaked> {
+        // Check the freeze period (skipped in emergency mode) and settle this cNFT's points
+        let now = Clock::get()?.unix_timestamp; // Get current timestamp
+        let earned = self.stake_account.finish(&mut self.config, now)?;
+        self.user_account.record_unstake(earned)?; // Decrease staked count and credit points
+
+        // Generate PDA signer seeds for the stake account (it is the leaf delegate)
+        let seeds: &[&[u8]; 4] = &[
+            b"stake",                                   // Stake PDA seed
+            self.stake_account.mint.as_ref(),           // Asset id
+            self.config.to_account_info().key.as_ref(), // Config address
+            &[self.stake_account.bump],                 // PDA bump
+        ];
+        let signer_seeds = &[&seeds[..]]; // Format for CPI signing
+
+        // Leaf was staked into the pool's collection
+        let collection_hash = hash_collection_option(Some(self.config.collection))?;
+
+        // Proof nodes are read-only, non-signer accounts
+        let proof: Vec<(&AccountInfo<'info>, bool, bool)> =
+            proof.iter().map(|node| (node, false, false)).collect();
+
+        // Thaw the leaf and revoke the stake account's delegation
+        let user = &self.user.to_account_info();
+        ThawAndRevokeV2Cpi::new(
+            &self.bubblegum_program.to_account_info(),
+            ThawAndRevokeV2CpiAccounts {
+                tree_config: &self.tree_config.to_account_info(), // Bubblegum tree config
+                payer: user,                                      // User pays any fees
+                leaf_delegate: Some(&self.stake_account.to_account_info()), // Stake account thaws the leaf
+                leaf_owner: user,                                           // Owner keeps the leaf
+                merkle_tree: &self.merkle_tree.to_account_info(),           // Tree holding the leaf
+                log_wrapper: &self.log_wrapper.to_account_info(),           // Noop program
+                compression_program: &self.compression_program.to_account_info(), // Compression program
+                system_program: &self.system_program.to_account_info(), // Solana system program
+            },
+            ThawAndRevokeV2InstructionArgs {
+                root: leaf.root,                        // Root the proof is checked against
+                data_hash: leaf.data_hash,              // Leaf metadata hash
+                creator_hash: leaf.creator_hash,        // Leaf creators hash
+                collection_hash: Some(collection_hash), // Pool collection
+                asset_data_hash: leaf.asset_data_hash,  // Leaf asset data hash
+                flags: leaf.flags,                      // Leaf flags (frozen by the stake account)
+                nonce: leaf.nonce,                      // Leaf nonce
+                index: leaf.index,                      // Leaf index
+            },
+        )
+        .invoke_signed_with_remaining_accounts(signer_seeds, &proof)?; // Sign with stake account PDA
+
+        // Stake account is closed automatically due to the close constraint
+        Ok(Unstaked {
+            pool: self.config.key(),
+            owner: self.user.key(),
+            mint: self.stake_account.mint,
+            points: earned,
+            amount_staked: self.user_account.amount_staked,
+            timestamp: now,
+        })
+    }
+}
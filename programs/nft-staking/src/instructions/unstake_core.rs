@@ -0,0 +1,153 @@
+#![allow(unexpected_cfgs)] // Allow compiler warnings for unrecognized configuration flags
+
+// Import pause flags, custom error types and state structures
+use crate::constants::PAUSE_UNSTAKE;
+use crate::error::ErrorCode;
+use crate::events::Unstaked; // Event recording the unstake
+use crate::state::*;
+
+use super::stake_core::load_core_asset; // Shared Core asset validation
+
+// Import essential Anchor and Metaplex Core types
+use anchor_lang::prelude::*;
+use mpl_core::{
+    instructions::{
+        RemovePluginV1Cpi, RemovePluginV1CpiAccounts, RemovePluginV1InstructionArgs,
+        UpdatePluginV1Cpi, UpdatePluginV1CpiAccounts, UpdatePluginV1InstructionArgs,
+    },
+    types::{FreezeDelegate, Plugin, PluginType},
+};
+
+// Account validation struct for unstaking a Metaplex Core asset
+// Reverses `StakeCore`: the stake PDA thaws the asset and the owner removes the plugin
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UnstakeCore<'info> {
+    /// User unstaking their asset
+    #[account(mut)] // Account can be modified (receives rent from closed stake account)
+    pub user: Signer<'info>, // The user unstaking their asset
+
+    /// CHECK: Core asset, owner checked here and contents validated in the handler
+    #[account(
+        mut, // Account will be modified (thawed and plugin removed)
+        owner = mpl_core::ID, // Must be a Metaplex Core account
+    )]
+    pub asset: UncheckedAccount<'info>, // The Core asset being unstaked
+
+    /// CHECK: Core collection, validated by address and owner
+    #[account(
+        mut, // Account will be modified (Core updates collection bookkeeping)
+        address = config.collection, // Must be the pool's collection
+        owner = mpl_core::ID, // Must be a Metaplex Core account
+    )]
+    pub collection: UncheckedAccount<'info>, // Core collection of the pool
+
+    /// Pool staking config
+    #[account(
+        mut, // Account will be modified (pool reward index and staked count)
+        seeds = [b"config".as_ref(), config.collection.as_ref(), config.pool_id.to_le_bytes().as_ref()], // Pool config PDA seeds
+        bump = config.bump, // Use stored bump from config
+        constraint = config.emergency || !config.is_paused(PAUSE_UNSTAKE) @ ErrorCode::PoolPaused, // Emergency mode always allows unstaking
+    )]
+    pub config: Account<'info, StakeConfig>, // Pool staking configuration
+
+    /// Stake record for this asset, to be closed after unstaking
+    #[account(
+        mut, // Account will be modified (closed and rent returned)
+        seeds = [b"stake".as_ref(), asset.key().as_ref(), config.key().as_ref()], // Same PDA used by `StakeCore`
+        bump = stake_account.bump, // Use stored bump from stake account
+        constraint = stake_account.owner == user.key() @ ErrorCode::WrongOwner, // Only the original staker can unstake
+        close = user // Return rent to user when account is closed
+    )]
+    pub stake_account: Account<'info, StakeAccount>, // Individual stake record being closed
+
+    /// User account tracking staked amount and points
+    #[account(
+        mut, // Account will be modified (amount_staked decreases, points increase)
+        seeds = [b"user".as_ref(), config.key().as_ref(), user.key().as_ref()], // User's staking account PDA for this pool
+        bump = user_account.bump // Use stored bump from user account
+    )]
+    pub user_account: Account<'info, UserAccount>, // User's overall staking statistics
+
+    /// CHECK: Metaplex Core program, checked by address
+    #[account(address = mpl_core::ID)]
+    pub core_program: UncheckedAccount<'info>, // For plugin operations
+
+    pub system_program: Program<'info, System>, // Required by Core plugin CPIs
+}
+
+// Implementation block containing the Core unstaking logic
+impl<'info> UnstakeCore<'info> {
+    // Function to unstake a Core asset and credit the points it accrued
+    pub fn unstake_core(&mut self) -> Result<Unstaked> {
+        // Asset must still belong to the user
+        let asset = load_core_asset(&self.asset)?;
+        require_keys_eq!(asset.owner, self.user.key(), ErrorCode::WrongOwner);
+
+        // Check the freeze period (skipped in emergency mode) and settle this asset's points
+        let now = Clock::get()?.unix_timestamp; // Get current timestamp
+        let earned = self.stake_account.finish(&mut self.config, now)?;
+        self.user_account.record_unstake(earned)?; // Decrease staked count and credit points
+
+        // Prepare accounts shared by the thaw and remove CPIs
+        let core_program = &self.core_program.to_account_info();
+        let asset = &self.asset.to_account_info();
+        let collection = &self.collection.to_account_info();
+        let user = &self.user.to_account_info();
+        let stake_account = &self.stake_account.to_account_info();
+        let system_program = &self.system_program.to_account_info();
+
+        // Generate PDA signer seeds for the stake account (it is the plugin authority)
+        let seeds: &[&[u8]; 4] = &[
+            b"stake",                                   // Stake PDA seed
+            self.asset.to_account_info().key.as_ref(),  // Asset address
+            self.config.to_account_info().key.as_ref(), // Config address
+            &[self.stake_account.bump],                 // PDA bump
+        ];
+        let signer_seeds = &[&seeds[..]]; // Format for CPI signing
+
+        // Thaw the asset so the owner can remove the plugin
+        UpdatePluginV1Cpi::new(
+            core_program,
+            UpdatePluginV1CpiAccounts {
+                asset,                          // The asset being thawed
+                collection: Some(collection),   // Its collection
+                payer: user,                    // User pays for any reallocation
+                authority: Some(stake_account), // Stake PDA is the plugin authority
+                system_program,                 // For reallocation
+                log_wrapper: None,              // No noop logging
+            },
+            UpdatePluginV1InstructionArgs {
+                plugin: Plugin::FreezeDelegate(FreezeDelegate { frozen: false }), // Unfreeze
+            },
+        )
+        .invoke_signed(signer_seeds)?; // Sign with stake account PDA
+
+        // Remove the FreezeDelegate plugin so the asset is back to its pre-stake state
+        RemovePluginV1Cpi::new(
+            core_program,
+            RemovePluginV1CpiAccounts {
+                asset,                        // The asset being released
+                collection: Some(collection), // Its collection
+                payer: user,                  // User receives the freed rent
+                authority: Some(user),        // Owner removes owner-managed plugins
+                system_program,               // For reallocation
+                log_wrapper: None,            // No noop logging
+            },
+            RemovePluginV1InstructionArgs {
+                plugin_type: PluginType::FreezeDelegate, // The plugin added by `StakeCore`
+            },
+        )
+        .invoke()?;
+
+        // Stake account is closed automatically due to the close constraint
+        Ok(Unstaked {
+            pool: self.config.key(),
+            owner: self.user.key(),
+            mint: self.stake_account.mint,
+            points: earned,
+            amount_staked: self.user_account.amount_staked,
+            timestamp: now,
+        })
+    }
+}
@@ -0,0 +1,171 @@
+#![allow(unexpected_cfgs)] // Allow compiler warnings for unrecognized configuration flags
+
+// Import pause flags, custom error types and state structures
+use crate::constants::PAUSE_UNSTAKE;
+use crate::error::ErrorCode;
+use crate::events::Unstaked; // Event recording each unstake
+use crate::state::*;
+// Import essential Anchor, SPL Token and Metadata program types
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::get_associated_token_address,
+    metadata::{mpl_token_metadata, Metadata}, // For thawing NFTs
+    token::{revoke, Revoke, Token},           // For removing the stake delegate
+};
+// Import Metaplex instruction for thawing delegated NFTs
+use mpl_token_metadata::instructions::{ThawDelegatedAccountCpi, ThawDelegatedAccountCpiAccounts};
+
+// Accounts per NFT in `remaining_accounts`: mint, mint_ata, edition, stake_account
+pub const UNSTAKE_MANY_GROUP: usize = 4;
+
+// Account validation struct for unstaking several NFTs in one instruction
+// Reverses `StakeMany`; each NFT group gets the same checks as `Unstake`
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UnstakeMany<'info> {
+    /// User unstaking their NFTs
+    #[account(mut)] // Account can be modified (receives rent from closed stake accounts)
+    pub user: Signer<'info>, // The user unstaking their NFTs
+
+    /// Pool staking config
+    #[account(
+        mut, // Account will be modified (pool reward index and staked count)
+        seeds = [b"config".as_ref(), config.collection.as_ref(), config.pool_id.to_le_bytes().as_ref()], // Pool config PDA seeds
+        bump = config.bump, // Use stored bump from config
+        constraint = config.emergency || !config.is_paused(PAUSE_UNSTAKE) @ ErrorCode::PoolPaused, // Emergency mode always allows unstaking
+    )]
+    pub config: Account<'info, StakeConfig>, // Pool staking configuration
+
+    /// User account tracking staked amount and points
+    #[account(
+        mut, // Account will be modified (amount_staked decreases, points increase)
+        seeds = [b"user".as_ref(), config.key().as_ref(), user.key().as_ref()], // User's staking account PDA for this pool
+        bump = user_account.bump // Use stored bump from user account
+    )]
+    pub user_account: Account<'info, UserAccount>, // User's overall staking statistics
+
+    /// Programs
+    pub token_program: Program<'info, Token>, // SPL Token program for revoking delegates
+    pub metadata_program: Program<'info, Metadata>, // For thawing the NFTs
+}
+
+// Implementation block containing the batch unstaking logic
+impl<'info> UnstakeMany<'info> {
+    // Function to unstake every NFT group in `nfts`; any failure reverts the whole batch
+    // Returns one event per NFT, in batch order
+    pub fn unstake_many(&mut self, nfts: &'info [AccountInfo<'info>]) -> Result<Vec<Unstaked>> {
+        // Accounts must split evenly into non-empty per-NFT groups
+        require!(
+            !nfts.is_empty() && nfts.len().is_multiple_of(UNSTAKE_MANY_GROUP),
+            ErrorCode::InvalidBatch
+        );
+        let count =
+            u8::try_from(nfts.len() / UNSTAKE_MANY_GROUP).map_err(|_| ErrorCode::InvalidBatch)?;
+
+        // Thaw each NFT and total the points they accrued
+        let now = Clock::get()?.unix_timestamp;
+        let staked_before = self.user_account.amount_staked;
+        let mut earned: u64 = 0;
+        let mut events = Vec::with_capacity(count as usize);
+        for (index, group) in nfts.chunks_exact(UNSTAKE_MANY_GROUP).enumerate() {
+            let (mint, points) = self.unstake_one(group, now).inspect_err(|_| {
+                msg!("unstake_many: NFT at index {} failed", index); // Point the client at the offending NFT
+            })?;
+            earned = earned.checked_add(points).ok_or(ErrorCode::Overflow)?;
+            events.push(Unstaked {
+                pool: self.config.key(),
+                owner: self.user.key(),
+                mint,
+                points,
+                amount_staked: staked_before.saturating_sub(index as u8 + 1), // Count as if unstaked one by one
+                timestamp: now,
+            });
+        }
+
+        // Remove the whole batch from the count and credit its points in one update
+        self.user_account.record_unstakes(count, earned)?;
+
+        Ok(events)
+    }
+
+    // Function to validate one NFT group, thaw it like `Unstake` and close its stake record
+    // Returns the NFT's mint and the points it earned
+    fn unstake_one(
+        &mut self,
+        group: &'info [AccountInfo<'info>],
+        now: i64,
+    ) -> Result<(Pubkey, u64)> {
+        let [mint, mint_ata, edition, stake_account] = group else {
+            return err!(ErrorCode::InvalidBatch);
+        };
+        let metadata_program_id = mpl_token_metadata::ID;
+        let config_key = self.config.key();
+
+        // Stake record must be this user's stake PDA for this NFT and pool
+        let mut stake = Account::<StakeAccount>::try_from(stake_account)?;
+        require_keys_eq!(stake.owner, self.user.key(), ErrorCode::WrongOwner);
+        require_keys_eq!(stake.mint, mint.key(), ErrorCode::InvalidStakeAccount);
+        let seeds: &[&[u8]; 4] = &[
+            b"stake",
+            mint.key.as_ref(),
+            config_key.as_ref(),
+            &[stake.bump],
+        ];
+        let stake_key = Pubkey::create_program_address(seeds, &crate::ID)
+            .map_err(|_| ErrorCode::InvalidStakeAccount)?;
+        require_keys_eq!(
+            stake_account.key(),
+            stake_key,
+            ErrorCode::InvalidStakeAccount
+        );
+        let signer_seeds = &[&seeds[..]]; // Format for CPI signing
+
+        // Token account and edition must belong to this NFT
+        require_keys_eq!(
+            mint_ata.key(),
+            get_associated_token_address(&self.user.key(), &mint.key()),
+            ErrorCode::InvalidBatch
+        );
+        let (edition_key, _) = Pubkey::find_program_address(
+            &[
+                b"metadata",
+                metadata_program_id.as_ref(),
+                mint.key.as_ref(),
+                b"edition",
+            ],
+            &metadata_program_id,
+        );
+        require_keys_eq!(edition.key(), edition_key, ErrorCode::InvalidBatch);
+
+        // Check the freeze period (skipped in emergency mode) and settle this NFT's points
+        let earned = stake.finish(&mut self.config, now)?;
+
+        // Thaw the NFT so the user can transfer it again
+        ThawDelegatedAccountCpi::new(
+            &self.metadata_program.to_account_info(),
+            ThawDelegatedAccountCpiAccounts {
+                delegate: stake_account, // Stake account that controls the NFT
+                token_account: mint_ata, // Token account holding the NFT
+                edition,                 // Master edition account
+                mint,                    // NFT mint
+                token_program: &self.token_program.to_account_info(), // SPL Token program
+            },
+        )
+        .invoke_signed(signer_seeds)?; // Sign with stake account PDA
+
+        // Revoke the stake account's delegation over the NFT
+        let cpi_accounts = Revoke {
+            source: mint_ata.clone(),               // The token account holding the NFT
+            authority: self.user.to_account_info(), // User removes the delegate
+        };
+        revoke(CpiContext::new(
+            self.token_program.to_account_info(),
+            cpi_accounts,
+        ))?;
+
+        // Close the stake record and return its rent to the user
+        stake.close(self.user.to_account_info())?;
+
+        Ok((mint.key(), earned))
+    }
+}
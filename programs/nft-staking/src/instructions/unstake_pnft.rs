@@ -0,0 +1,219 @@
+#![allow(unexpected_cfgs)] // Allow compiler warnings for unrecognized configuration flags
+
+// Import pause flags, custom error types and state structures
+use crate::constants::PAUSE_UNSTAKE;
+use crate::error::ErrorCode;
+use crate::events::Unstaked; // Event recording the unstake
+use crate::state::*;
+// Import essential Anchor, SPL Token and Metadata program types
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar; // For the instructions sysvar address
+use anchor_spl::{
+    metadata::{mpl_token_metadata, MasterEditionAccount, Metadata, MetadataAccount},
+    token::{Mint, Token, TokenAccount},
+};
+// Import Metaplex instructions for unlocking and revoking programmable NFTs
+use mpl_token_metadata::instructions::{
+    RevokeStakingV1Cpi, RevokeStakingV1CpiAccounts, UnlockV1Cpi, UnlockV1CpiAccounts,
+    UnlockV1InstructionArgs,
+};
+
+// Account validation struct for unstaking a programmable NFT (pNFT)
+// Reverses `StakePnft`: the stake PDA unlocks the token and the owner revokes the delegate
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UnstakePnft<'info> {
+    /// User unstaking their pNFT
+    #[account(mut)] // Account can be modified (receives rent from closed stake account)
+    pub user: Signer<'info>, // The user unstaking their pNFT
+
+    /// pNFT mint being unstaked
+    pub mint: Account<'info, Mint>, // The pNFT mint being unstaked
+
+    /// User's token account holding the locked pNFT
+    #[account(
+        mut, // Account will be modified (unlocked and delegate revoked)
+        associated_token::mint = mint, // Must be ATA for the specific pNFT mint
+        associated_token::authority = user, // Must be owned by the user
+    )]
+    pub mint_ata: Account<'info, TokenAccount>, // User's token account holding the pNFT
+
+    /// pNFT metadata (required by the unlock and revoke CPIs)
+    #[account(
+        mut, // Account will be modified (Token Metadata updates it during revocation)
+        seeds = [
+            b"metadata", // Metaplex metadata PDA seed
+            metadata_program.key().as_ref(), // Metadata program ID
+            mint.key().as_ref() // pNFT mint address
+        ],
+        seeds::program = metadata_program.key(), // Use metadata program for PDA derivation
+        bump, // Anchor finds the canonical bump automatically
+    )]
+    pub metadata: Account<'info, MetadataAccount>, // pNFT metadata account
+
+    /// Master edition of the pNFT (required by the unlock and revoke CPIs)
+    #[account(
+        seeds = [
+            b"metadata", // Metaplex metadata PDA seed
+            metadata_program.key().as_ref(), // Metadata program ID
+            mint.key().as_ref(), // pNFT mint address
+            b"edition" // Master edition seed
+        ],
+        seeds::program = metadata_program.key(), // Use metadata program for PDA derivation
+        bump, // Anchor finds the canonical bump automatically
+    )]
+    pub edition: Account<'info, MasterEditionAccount>, // pNFT master edition account
+
+    /// CHECK: Token record PDA, validated by seeds and by Token Metadata during the CPI
+    #[account(
+        mut, // Account will be modified (lock and delegate are cleared)
+        seeds = [
+            b"metadata", // Metaplex metadata PDA seed
+            metadata_program.key().as_ref(), // Metadata program ID
+            mint.key().as_ref(), // pNFT mint address
+            b"token_record", // Token record seed
+            mint_ata.key().as_ref() // Token account the record belongs to
+        ],
+        seeds::program = metadata_program.key(), // Use metadata program for PDA derivation
+        bump, // Anchor finds the canonical bump automatically
+    )]
+    pub token_record: UncheckedAccount<'info>, // pNFT token record
+
+    /// Pool staking config
+    #[account(
+        mut, // Account will be modified (pool reward index and staked count)
+        seeds = [b"config".as_ref(), config.collection.as_ref(), config.pool_id.to_le_bytes().as_ref()], // Pool config PDA seeds
+        bump = config.bump, // Use stored bump from config
+        constraint = config.emergency || !config.is_paused(PAUSE_UNSTAKE) @ ErrorCode::PoolPaused, // Emergency mode always allows unstaking
+    )]
+    pub config: Account<'info, StakeConfig>, // Pool staking configuration
+
+    /// Stake record for this pNFT, to be closed after unstaking
+    #[account(
+        mut, // Account will be modified (closed and rent returned)
+        seeds = [b"stake".as_ref(), mint.key().as_ref(), config.key().as_ref()], // Same PDA used by `StakePnft`
+        bump = stake_account.bump, // Use stored bump from stake account
+        constraint = stake_account.owner == user.key() @ ErrorCode::WrongOwner, // Only the original staker can unstake
+        close = user // Return rent to user when account is closed
+    )]
+    pub stake_account: Account<'info, StakeAccount>, // Individual stake record being closed
+
+    /// User account tracking staked amount and points
+    #[account(
+        mut, // Account will be modified (amount_staked decreases, points increase)
+        seeds = [b"user".as_ref(), config.key().as_ref(), user.key().as_ref()], // User's staking account PDA for this pool
+        bump = user_account.bump // Use stored bump from user account
+    )]
+    pub user_account: Account<'info, UserAccount>, // User's overall staking statistics
+
+    /// CHECK: Rule set program, validated by Token Metadata against the pNFT's programmable config
+    pub authorization_rules_program: Option<UncheckedAccount<'info>>,
+    /// CHECK: Rule set account, validated by Token Metadata against the pNFT's programmable config
+    pub authorization_rules: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Instructions sysvar, required by Token Metadata for rule set evaluation
+    #[account(address = sysvar::instructions::ID)]
+    pub sysvar_instructions: UncheckedAccount<'info>,
+
+    /// Programs
+    pub system_program: Program<'info, System>, // Required by Token Metadata CPIs
+    pub token_program: Program<'info, Token>, // SPL Token program
+    pub metadata_program: Program<'info, Metadata>, // For pNFT unlock and revoke operations
+}
+
+// Implementation block containing the pNFT unstaking logic
+impl<'info> UnstakePnft<'info> {
+    // Function to unstake a pNFT and credit the points it accrued
+    pub fn unstake_pnft(&mut self) -> Result<Unstaked> {
+        // Check the freeze period (skipped in emergency mode) and settle this pNFT's points
+        let now = Clock::get()?.unix_timestamp; // Get current timestamp
+        let earned = self.stake_account.finish(&mut self.config, now)?;
+        self.user_account.record_unstake(earned)?; // Decrease staked count and credit points
+
+        // Prepare accounts shared by the unlock and revoke CPIs
+        let stake_account = &self.stake_account.to_account_info();
+        let user = &self.user.to_account_info();
+        let token = &self.mint_ata.to_account_info();
+        let mint = &self.mint.to_account_info();
+        let metadata = &self.metadata.to_account_info();
+        let edition = &self.edition.to_account_info();
+        let token_record = &self.token_record.to_account_info();
+        let system_program = &self.system_program.to_account_info();
+        let sysvar_instructions = &self.sysvar_instructions.to_account_info();
+        let token_program = &self.token_program.to_account_info();
+        let metadata_program = &self.metadata_program.to_account_info();
+        let authorization_rules_program = self
+            .authorization_rules_program
+            .as_ref()
+            .map(|program| program.to_account_info());
+        let authorization_rules = self
+            .authorization_rules
+            .as_ref()
+            .map(|rules| rules.to_account_info());
+
+        // Generate PDA signer seeds for the stake account (it is the staking delegate)
+        let seeds: &[&[u8]; 4] = &[
+            b"stake",                                   // Stake PDA seed
+            self.mint.to_account_info().key.as_ref(),   // pNFT mint address
+            self.config.to_account_info().key.as_ref(), // Config address
+            &[self.stake_account.bump],                 // PDA bump
+        ];
+        let signer_seeds = &[&seeds[..]]; // Format for CPI signing
+
+        // Unlock the pNFT so the user can transfer it again
+        UnlockV1Cpi::new(
+            metadata_program,
+            UnlockV1CpiAccounts {
+                authority: stake_account,               // Staking delegate unlocks the token
+                token_owner: Some(user),                // Owner of the token account
+                token,                                  // Token account holding the pNFT
+                mint,                                   // pNFT mint
+                metadata,                               // pNFT metadata
+                edition: Some(edition),                 // pNFT master edition
+                token_record: Some(token_record),       // Token record tracking the lock
+                payer: user,                            // User pays for any record updates
+                system_program,                         // Solana system program
+                sysvar_instructions,                    // Instructions sysvar
+                spl_token_program: Some(token_program), // SPL Token program
+                authorization_rules_program: authorization_rules_program.as_ref(), // Rule set program
+                authorization_rules: authorization_rules.as_ref(), // Rule set account
+            },
+            UnlockV1InstructionArgs {
+                authorization_data: None, // No rule set payload needed
+            },
+        )
+        .invoke_signed(signer_seeds)?; // Sign with stake account PDA
+
+        // Revoke the stake account's staking delegation (signed by the owner)
+        RevokeStakingV1Cpi::new(
+            metadata_program,
+            RevokeStakingV1CpiAccounts {
+                delegate_record: None,            // Token delegates live in the token record
+                delegate: stake_account,          // The stake account loses its delegation
+                metadata,                         // pNFT metadata
+                master_edition: Some(edition),    // pNFT master edition
+                token_record: Some(token_record), // Token record tracking the delegate
+                mint,                             // pNFT mint
+                token,                            // Token account holding the pNFT
+                authority: user,                  // User removes the delegate
+                payer: user,                      // User pays for any record updates
+                system_program,                   // Solana system program
+                sysvar_instructions,              // Instructions sysvar
+                spl_token_program: Some(token_program), // SPL Token program
+                authorization_rules_program: authorization_rules_program.as_ref(), // Rule set program
+                authorization_rules: authorization_rules.as_ref(), // Rule set account
+            },
+        )
+        .invoke()?;
+
+        // Stake account is closed automatically due to the close constraint
+        Ok(Unstaked {
+            pool: self.config.key(),
+            owner: self.user.key(),
+            mint: self.stake_account.mint,
+            points: earned,
+            amount_staked: self.user_account.amount_staked,
+            timestamp: now,
+        })
+    }
+}
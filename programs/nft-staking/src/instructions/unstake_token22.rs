@@ -0,0 +1,110 @@
+#![allow(unexpected_cfgs)] // Allow compiler warnings for unrecognized configuration flags
+
+// Import pause flags, custom error types and state structures
+use crate::constants::PAUSE_UNSTAKE;
+use crate::error::ErrorCode;
+use crate::events::Unstaked; // Event recording the unstake
+use crate::state::*;
+// Import essential Anchor and Token-2022 interface types
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{thaw_account, Mint, ThawAccount, TokenAccount, TokenInterface};
+
+// Account validation struct for unstaking a Token-2022 NFT
+// Reverses `StakeToken22`: the freeze PDA thaws the user's token account
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UnstakeToken22<'info> {
+    /// User unstaking their NFT
+    #[account(mut)] // Account can be modified (receives rent from closed stake account)
+    pub user: Signer<'info>, // The user unstaking their NFT
+
+    /// CHECK: Program-wide PDA with no data, only signs freeze and thaw CPIs
+    #[account(
+        seeds = [b"freeze".as_ref()], // Same PDA used by `StakeToken22`
+        bump, // Anchor finds the canonical bump automatically
+    )]
+    pub freeze_authority: UncheckedAccount<'info>, // Freeze authority of stakeable Token-2022 mints
+
+    /// Token-2022 NFT mint being unstaked
+    #[account(
+        mint::token_program = token_program, // Mint must belong to the given token program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>, // The NFT mint being unstaked
+
+    /// User's token account holding the frozen NFT
+    #[account(
+        mut, // Account will be modified (thawed)
+        associated_token::mint = mint, // Must be ATA for the specific NFT mint
+        associated_token::authority = user, // Must be owned by the user
+        associated_token::token_program = token_program, // Token-2022 ATA
+    )]
+    pub mint_ata: InterfaceAccount<'info, TokenAccount>, // User's token account holding the NFT
+
+    /// Pool staking config
+    #[account(
+        mut, // Account will be modified (pool reward index and staked count)
+        seeds = [b"config".as_ref(), config.collection.as_ref(), config.pool_id.to_le_bytes().as_ref()], // Pool config PDA seeds
+        bump = config.bump, // Use stored bump from config
+        constraint = config.emergency || !config.is_paused(PAUSE_UNSTAKE) @ ErrorCode::PoolPaused, // Emergency mode always allows unstaking
+    )]
+    pub config: Account<'info, StakeConfig>, // Pool staking configuration
+
+    /// Stake record for this NFT, to be closed after unstaking
+    #[account(
+        mut, // Account will be modified (closed and rent returned)
+        seeds = [b"stake".as_ref(), mint.key().as_ref(), config.key().as_ref()], // Same PDA used by `StakeToken22`
+        bump = stake_account.bump, // Use stored bump from stake account
+        constraint = stake_account.owner == user.key() @ ErrorCode::WrongOwner, // Only the original staker can unstake
+        close = user // Return rent to user when account is closed
+    )]
+    pub stake_account: Account<'info, StakeAccount>, // Individual stake record being closed
+
+    /// User account tracking staked amount and points
+    #[account(
+        mut, // Account will be modified (amount_staked decreases, points increase)
+        seeds = [b"user".as_ref(), config.key().as_ref(), user.key().as_ref()], // User's staking account PDA for this pool
+        bump = user_account.bump // Use stored bump from user account
+    )]
+    pub user_account: Account<'info, UserAccount>, // User's overall staking statistics
+
+    /// Programs
+    pub token_program: Interface<'info, TokenInterface>, // Token-2022 program for thawing
+}
+
+// Implementation block containing the Token-2022 unstaking logic
+impl<'info> UnstakeToken22<'info> {
+    // Function to unstake a Token-2022 NFT and credit the points it accrued
+    pub fn unstake_token22(&mut self, bumps: &UnstakeToken22Bumps) -> Result<Unstaked> {
+        // Check the freeze period (skipped in emergency mode) and settle this NFT's points
+        let now = Clock::get()?.unix_timestamp; // Get current timestamp
+        let earned = self.stake_account.finish(&mut self.config, now)?;
+        self.user_account.record_unstake(earned)?; // Decrease staked count and credit points
+
+        // Freeze PDA seeds for signing as the mint's freeze authority
+        let seeds: &[&[u8]] = &[
+            b"freeze",                 // Freeze PDA seed
+            &[bumps.freeze_authority], // PDA bump
+        ];
+        let signer = &[seeds]; // Format for CPI signing
+
+        // Thaw the token account so the user can transfer the NFT again
+        let cpi_accounts = ThawAccount {
+            account: self.mint_ata.to_account_info(), // The token account holding the NFT
+            mint: self.mint.to_account_info(),        // The NFT mint
+            authority: self.freeze_authority.to_account_info(), // Freeze PDA is the freeze authority
+        };
+        let cpi_ctx =
+            CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, signer);
+        thaw_account(cpi_ctx)?;
+
+        // Stake account is closed automatically due to the close constraint
+        Ok(Unstaked {
+            pool: self.config.key(),
+            owner: self.user.key(),
+            mint: self.stake_account.mint,
+            points: earned,
+            amount_staked: self.user_account.amount_staked,
+            timestamp: now,
+        })
+    }
+}
@@ -0,0 +1,135 @@
+#![allow(unexpected_cfgs)] // Allow compiler warnings for unrecognized configuration flags
+
+// Import pause flags, custom error types and state structures
+use crate::constants::PAUSE_UNSTAKE;
+use crate::error::ErrorCode;
+use crate::events::Unstaked; // Event recording the unstake
+use crate::state::*;
+// Import essential Anchor, token interface and associated token types
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+// Account validation struct for unstaking an NFT held in the pool's vault
+// Reverses `StakeVault`: the config PDA returns the NFT and closes the vault
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UnstakeVault<'info> {
+    /// User unstaking their NFT
+    #[account(mut)] // Account can be modified (receives rent from closed accounts)
+    pub user: Signer<'info>, // The user unstaking their NFT
+
+    /// NFT mint being unstaked
+    #[account(
+        mint::token_program = token_program, // Mint must belong to the given token program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>, // The NFT mint being unstaked
+
+    /// User's token account receiving the NFT
+    #[account(
+        init_if_needed, // Recreate the ATA if the user closed it while staked
+        payer = user, // User pays for ATA creation if needed
+        associated_token::mint = mint, // Must be ATA for the specific NFT mint
+        associated_token::authority = user, // Must be owned by the user
+        associated_token::token_program = token_program, // ATA of the mint's token program
+    )]
+    pub mint_ata: InterfaceAccount<'info, TokenAccount>, // User's token account for the NFT
+
+    /// Pool vault holding the NFT
+    #[account(
+        mut, // Account will be modified (emptied and closed)
+        associated_token::mint = mint, // Vault holds this NFT
+        associated_token::authority = config, // Config PDA owns the vault
+        associated_token::token_program = token_program, // ATA of the mint's token program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>, // Pool vault for this NFT
+
+    /// Pool staking config
+    #[account(
+        mut, // Account will be modified (pool reward index and staked count)
+        seeds = [b"config".as_ref(), config.collection.as_ref(), config.pool_id.to_le_bytes().as_ref()], // Pool config PDA seeds
+        bump = config.bump, // Use stored bump from config
+        constraint = config.emergency || !config.is_paused(PAUSE_UNSTAKE) @ ErrorCode::PoolPaused, // Emergency mode always allows unstaking
+    )]
+    pub config: Account<'info, StakeConfig>, // Pool staking configuration
+
+    /// Stake record for this NFT, to be closed after unstaking
+    #[account(
+        mut, // Account will be modified (closed and rent returned)
+        seeds = [b"stake".as_ref(), mint.key().as_ref(), config.key().as_ref()], // Same PDA used by `StakeVault`
+        bump = stake_account.bump, // Use stored bump from stake account
+        constraint = stake_account.owner == user.key() @ ErrorCode::WrongOwner, // Only the original staker can unstake
+        close = user // Return rent to user when account is closed
+    )]
+    pub stake_account: Account<'info, StakeAccount>, // Individual stake record being closed
+
+    /// User account tracking staked amount and points
+    #[account(
+        mut, // Account will be modified (amount_staked decreases, points increase)
+        seeds = [b"user".as_ref(), config.key().as_ref(), user.key().as_ref()], // User's staking account PDA for this pool
+        bump = user_account.bump // Use stored bump from user account
+    )]
+    pub user_account: Account<'info, UserAccount>, // User's overall staking statistics
+
+    /// Programs
+    pub system_program: Program<'info, System>, // For ATA creation
+    pub token_program: Interface<'info, TokenInterface>, // For the transfer and vault closure
+    pub associated_token_program: Program<'info, AssociatedToken>, // For ATA creation
+}
+
+// Implementation block containing the vault unstaking logic
+impl<'info> UnstakeVault<'info> {
+    // Function to return a vaulted NFT and credit the points it accrued
+    pub fn unstake_vault(&mut self) -> Result<Unstaked> {
+        // Check the freeze period (skipped in emergency mode) and settle this NFT's points
+        let now = Clock::get()?.unix_timestamp; // Get current timestamp
+        let earned = self.stake_account.finish(&mut self.config, now)?;
+        self.user_account.record_unstake(earned)?; // Decrease staked count and credit points
+
+        // Config PDA seeds for signing as the vault owner
+        let pool_id = self.config.pool_id.to_le_bytes();
+        let seeds: &[&[u8]] = &[
+            b"config",                       // Config PDA seed
+            self.config.collection.as_ref(), // Pool collection
+            &pool_id,                        // Pool id within the collection
+            &[self.config.bump],             // PDA bump
+        ];
+        let signer = &[seeds]; // Format for CPI signing
+
+        // Return the NFT to the user
+        let cpi_accounts = TransferChecked {
+            from: self.vault.to_account_info(),       // Pool vault
+            mint: self.mint.to_account_info(),        // The NFT mint
+            to: self.mint_ata.to_account_info(),      // User's token account
+            authority: self.config.to_account_info(), // Config PDA owns the vault
+        };
+        let cpi_ctx =
+            CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, signer);
+        transfer_checked(cpi_ctx, 1, self.mint.decimals)?; // Exactly 1 token (the NFT)
+
+        // Close the now-empty vault and refund its rent to the user
+        let cpi_accounts = CloseAccount {
+            account: self.vault.to_account_info(),    // Pool vault
+            destination: self.user.to_account_info(), // User receives the rent
+            authority: self.config.to_account_info(), // Config PDA owns the vault
+        };
+        let cpi_ctx =
+            CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, signer);
+        close_account(cpi_ctx)?;
+
+        // Stake account is closed automatically due to the close constraint
+        Ok(Unstaked {
+            pool: self.config.key(),
+            owner: self.user.key(),
+            mint: self.stake_account.mint,
+            points: earned,
+            amount_staked: self.user_account.amount_staked,
+            timestamp: now,
+        })
+    }
+}
@@ -0,0 +1,98 @@
+#![allow(unexpected_cfgs)] // Allow compiler warnings for unrecognized configuration flags
+
+use anchor_lang::prelude::*; // Import essential Anchor framework items
+
+use crate::error::ErrorCode; // Import custom error types
+use crate::events::ConfigUpdated; // Event recording the new parameters
+use crate::state::StakeConfig; // Import the global configuration structure
+
+// Account validation struct for tuning the staking parameters after initialization
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>, // The current config admin
+
+    #[account(
+        mut, // Account will be modified (parameters are overwritten)
+        seeds = [b"config".as_ref(), config.collection.as_ref(), config.pool_id.to_le_bytes().as_ref()], // Pool config PDA seeds
+        bump = config.bump, // Use stored bump from config
+        has_one = admin @ ErrorCode::Unauthorized, // Only the stored admin may update
+    )]
+    pub config: Account<'info, StakeConfig>, // Pool staking configuration
+}
+
+// Pool parameters to change; `None` leaves the current value untouched
+// On flat-points pools there is no pool-wide checkpoint: each stake account is
+// credited from its own last settlement, so a new `points_per_stake` or
+// `points_period` also re-rates the unsettled time of every staked NFT
+// (claim or unstake before the change to lock in the old rate)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct UpdateConfigParams {
+    pub points_per_stake: Option<u8>,  // New points per NFT per period
+    pub points_period: Option<u32>,    // New period length (seconds)
+    pub max_stake: Option<u8>,         // New per-user stake limit
+    pub freeze_period: Option<u32>,    // New minimum stake duration (seconds)
+    pub reward_per_point: Option<u64>, // New token base units per claimed point
+    pub emission_per_second: Option<u64>, // New shared emission rate (emission pools only)
+    pub max_supply: Option<u64>,       // New lifetime mint cap (0: uncapped)
+    pub daily_mint_limit: Option<u64>, // New per-window mint cap (0: unlimited)
+}
+
+// Implementation block containing the update logic
+impl<'info> UpdateConfig<'info> {
+    // Function to overwrite any subset of the staking parameters
+    // Points not yet settled on stake accounts are credited at the new rate
+    pub fn update_config(&mut self, params: UpdateConfigParams) -> Result<ConfigUpdated> {
+        let now = Clock::get()?.unix_timestamp;
+        if let Some(points_per_stake) = params.points_per_stake {
+            self.config.points_per_stake = points_per_stake; // New points per NFT per period
+        }
+        if let Some(points_period) = params.points_period {
+            // Points accrue per whole period, so the period cannot be empty
+            require!(points_period > 0, ErrorCode::InvalidPointsPeriod);
+            self.config.points_period = points_period; // New period length (seconds)
+        }
+        if let Some(max_stake) = params.max_stake {
+            self.config.max_stake = max_stake; // New per-user stake limit
+        }
+        if let Some(freeze_period) = params.freeze_period {
+            self.config.freeze_period = freeze_period; // New minimum stake duration (seconds)
+        }
+        if let Some(reward_per_point) = params.reward_per_point {
+            // A zero rate would burn points on claim without minting anything
+            require!(reward_per_point > 0, ErrorCode::InvalidRewardRate);
+            self.config.reward_per_point = reward_per_point; // New conversion rate
+        }
+        if let Some(max_supply) = params.max_supply {
+            self.config.max_supply = max_supply; // Below total_minted halts further minting
+        }
+        if let Some(daily_mint_limit) = params.daily_mint_limit {
+            self.config.daily_mint_limit = daily_mint_limit; // Applies to the current window too
+        }
+
+        if let Some(emission_per_second) = params.emission_per_second {
+            // The reward model is fixed at init, so the rate stays non-zero
+            require!(
+                self.config.shares_emission() && emission_per_second > 0,
+                ErrorCode::InvalidEmissionRate
+            );
+            self.config.update_pool(now)?; // Time so far is emitted at the old rate
+            self.config.emission_per_second = emission_per_second; // New shared emission rate
+        }
+
+        // Report the parameters now in effect
+        Ok(ConfigUpdated {
+            pool: self.config.key(),
+            admin: self.admin.key(),
+            points_per_stake: self.config.points_per_stake,
+            points_period: self.config.points_period,
+            max_stake: self.config.max_stake,
+            freeze_period: self.config.freeze_period,
+            reward_per_point: self.config.reward_per_point,
+            emission_per_second: self.config.emission_per_second,
+            max_supply: self.config.max_supply,
+            daily_mint_limit: self.config.daily_mint_limit,
+            timestamp: now,
+        })
+    }
+}
@@ -6,6 +6,7 @@
 // Module declarations - these tell Rust about other files in the project
 pub mod constants; // Contains program-wide constants like seeds
 pub mod error; // Contains custom error types for the program
+pub mod events; // Contains events emitted on every state change
 pub mod instructions; // Contains all instruction handlers (initialize, stake, etc.)
 pub mod state; // Contains account data structures
 
@@ -13,6 +14,7 @@ use anchor_lang::prelude::*; // Import all essential Anchor framework items
 
 // Re-export all items from modules so they can be used throughout the program
 pub use constants::*;
+pub use events::*;
 pub use instructions::*;
 pub use state::*;
 
@@ -24,40 +26,246 @@ declare_id!("6YvXnSvATQbKDtaoSxpenuZmsYwTnFW2ie4CarKpX86r");
 pub mod nft_staking {
     use super::*; // Import everything from the parent scope
 
-    // Initialize the global staking configuration (admin-only function)
-    // Parameters: points earned per stake, maximum NFTs per user, freeze time in seconds
+    // Initialize a staking pool's configuration (upgrade-authority-only function)
+    // Parameters: accepted collection, pool id (0 for the first pool of a collection),
+    // and the tunable pool parameters (points, period, max stake, freeze period)
     pub fn initialize_config(
         ctx: Context<InitializeConfig>,
-        points_per_stake: u8,
-        max_stake: u8,
-        freeze_period: u32,
+        collection: Pubkey,
+        pool_id: u16,
+        params: ConfigParams,
     ) -> Result<()> {
         // Delegate to the instruction handler with account context and PDA bumps
-        ctx.accounts
-            .initialize_config(points_per_stake, max_stake, freeze_period, &ctx.bumps)
+        let event = ctx
+            .accounts
+            .initialize_config(collection, pool_id, params, &ctx.bumps)?;
+        emit_cpi!(event);
+        Ok(())
     }
 
-    // Initialize a user's staking account (creates their personal staking data)
+    // Change any subset of the staking parameters (admin-only function)
+    pub fn update_config(ctx: Context<UpdateConfig>, params: UpdateConfigParams) -> Result<()> {
+        let event = ctx.accounts.update_config(params)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    // Replace an emission pool's start/end window and rate changes (admin-only function)
+    pub fn set_emission_schedule(
+        ctx: Context<SetEmissionSchedule>,
+        params: ScheduleParams,
+    ) -> Result<()> {
+        let event = ctx.accounts.set_emission_schedule(params)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    // Replace the lock tiers offered to new stakes (admin-only function)
+    pub fn set_lock_tiers(ctx: Context<SetLockTiers>, tiers: Vec<LockTier>) -> Result<()> {
+        let event = ctx.accounts.set_lock_tiers(tiers)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    // Nominate a new admin (admin-only function, completed by accept_admin)
+    pub fn transfer_admin(ctx: Context<TransferAdmin>, new_admin: Pubkey) -> Result<()> {
+        let event = ctx.accounts.transfer_admin(new_admin)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    // Accept a pending admin nomination (signed by the nominee)
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        let event = ctx.accounts.accept_admin()?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    // Disable individual operations with PAUSE_* flags (admin-only function)
+    pub fn set_paused(ctx: Context<SetPaused>, paused: u8) -> Result<()> {
+        let event = ctx.accounts.set_paused(paused)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    // Toggle emergency unstaking without rewards (admin-only function)
+    pub fn set_emergency(ctx: Context<SetEmergency>, emergency: bool) -> Result<()> {
+        let event = ctx.accounts.set_emergency(emergency)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    // Switch a pool to paying claims from a treasury of an existing token (admin-only function)
+    pub fn initialize_treasury(ctx: Context<InitializeTreasury>) -> Result<()> {
+        let event = ctx.accounts.initialize_treasury()?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    // Deposit reward tokens into a pool's treasury (anyone may fund it)
+    pub fn fund_treasury(ctx: Context<FundTreasury>, amount: u64) -> Result<()> {
+        let event = ctx.accounts.fund_treasury(amount)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    // Initialize a user's staking account in a pool (creates their personal staking data)
     pub fn initialize_user(ctx: Context<Initialize>) -> Result<()> {
         // Delegate to the instruction handler with PDA bumps for account creation
-        ctx.accounts.init_user(&ctx.bumps)
+        let event = ctx.accounts.init_user(&ctx.bumps)?;
+        emit_cpi!(event);
+        Ok(())
     }
 
     // Stake an NFT (locks it and starts earning rewards)
-    pub fn stake(ctx: Context<Stake>) -> Result<()> {
+    // `tier` picks a lock tier (0: default, no lock beyond freeze_period); every stake path takes one
+    pub fn stake(ctx: Context<Stake>, tier: u8) -> Result<()> {
         // Delegate to the instruction handler with PDA bumps for new stake account
-        ctx.accounts.stake(&ctx.bumps)
+        let event = ctx.accounts.stake(tier, &ctx.bumps)?;
+        emit_cpi!(event);
+        Ok(())
     }
 
-    // Unstake an NFT (unlocks it and claims accumulated rewards)
+    // Unstake an NFT (unlocks it and credits the points it accrued)
     pub fn unstake(ctx: Context<Unstake>) -> Result<()> {
         // Delegate to the instruction handler (no bumps needed as account is closed)
-        ctx.accounts.unstake()
+        let event = ctx.accounts.unstake()?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    // Stake a programmable NFT (delegates and locks it via Token Metadata)
+    pub fn stake_pnft(ctx: Context<StakePnft>, tier: u8) -> Result<()> {
+        let event = ctx.accounts.stake_pnft(tier, &ctx.bumps)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    // Unstake a programmable NFT (unlocks it and credits the points it accrued)
+    pub fn unstake_pnft(ctx: Context<UnstakePnft>) -> Result<()> {
+        let event = ctx.accounts.unstake_pnft()?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    // Stake a Metaplex Core asset (freezes it with a FreezeDelegate plugin)
+    pub fn stake_core(ctx: Context<StakeCore>, tier: u8) -> Result<()> {
+        let event = ctx.accounts.stake_core(tier, &ctx.bumps)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    // Unstake a Metaplex Core asset (thaws it and credits the points it accrued)
+    pub fn unstake_core(ctx: Context<UnstakeCore>) -> Result<()> {
+        let event = ctx.accounts.unstake_core()?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    // Stake a Token-2022 NFT (frozen by the program's freeze PDA as mint freeze authority)
+    pub fn stake_token22(ctx: Context<StakeToken22>, tier: u8) -> Result<()> {
+        let event = ctx.accounts.stake_token22(tier, &ctx.bumps)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    // Unstake a Token-2022 NFT (thaws it and credits the points it accrued)
+    pub fn unstake_token22(ctx: Context<UnstakeToken22>) -> Result<()> {
+        let event = ctx.accounts.unstake_token22(&ctx.bumps)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    // Stake a compressed NFT (delegated to and frozen by the stake account)
+    // Remaining accounts: the Merkle proof nodes for the leaf
+    pub fn stake_cnft<'info>(
+        ctx: Context<'_, '_, 'info, 'info, StakeCnft<'info>>,
+        leaf: CnftLeaf,
+        tier: u8,
+    ) -> Result<()> {
+        let event = ctx
+            .accounts
+            .stake_cnft(leaf, tier, ctx.remaining_accounts, &ctx.bumps)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    // Unstake a compressed NFT (thaws it and credits the points it accrued)
+    // Remaining accounts: the Merkle proof nodes for the leaf
+    pub fn unstake_cnft<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UnstakeCnft<'info>>,
+        leaf: CnftLeaf,
+    ) -> Result<()> {
+        let event = ctx.accounts.unstake_cnft(leaf, ctx.remaining_accounts)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    // Stake an NFT into the pool vault (custody pools only)
+    pub fn stake_vault(ctx: Context<StakeVault>, tier: u8) -> Result<()> {
+        let event = ctx.accounts.stake_vault(tier, &ctx.bumps)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    // Unstake a vaulted NFT (returns it and credits the points it accrued)
+    pub fn unstake_vault(ctx: Context<UnstakeVault>) -> Result<()> {
+        let event = ctx.accounts.unstake_vault()?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    // Stake several NFTs at once; each NFT passes [mint, mint_ata, metadata, edition, stake_account]
+    // through remaining accounts
+    pub fn stake_many<'info>(
+        ctx: Context<'_, '_, 'info, 'info, StakeMany<'info>>,
+        tier: u8,
+    ) -> Result<()> {
+        let events = ctx.accounts.stake_many(tier, ctx.remaining_accounts)?;
+        for event in events {
+            emit_cpi!(event); // One event per NFT
+        }
+        Ok(())
+    }
+
+    // Unstake several NFTs at once; each NFT passes [mint, mint_ata, edition, stake_account]
+    // through remaining accounts
+    pub fn unstake_many<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UnstakeMany<'info>>,
+    ) -> Result<()> {
+        let events = ctx.accounts.unstake_many(ctx.remaining_accounts)?;
+        for event in events {
+            emit_cpi!(event); // One event per NFT
+        }
+        Ok(())
+    }
+
+    // Claim accumulated reward points from the pool's treasury (treasury pools only)
+    // Pays as many points as the treasury covers; the rest stay on the user account
+    // Stake accounts passed as remaining accounts are settled before claiming
+    pub fn claim_treasury<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimTreasury<'info>>,
+    ) -> Result<()> {
+        let event = ctx.accounts.claim_treasury(ctx.remaining_accounts)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    // Compute the points a user would receive if they claimed now (read-only)
+    // Remaining accounts: the user's stake accounts to include, or a single one
+    // The result is returned through `set_return_data` for `simulateTransaction` callers
+    pub fn get_pending_rewards<'info>(
+        ctx: Context<'_, '_, 'info, 'info, GetPendingRewards<'info>>,
+    ) -> Result<PendingRewards> {
+        ctx.accounts.get_pending_rewards(ctx.remaining_accounts)
     }
 
     // Claim accumulated reward points as mintable tokens
-    pub fn claim(ctx: Context<Claim>) -> Result<()> {
+    // Stake accounts passed as remaining accounts are settled before claiming
+    pub fn claim<'info>(ctx: Context<'_, '_, 'info, 'info, Claim<'info>>) -> Result<()> {
         // Delegate to the instruction handler (no bumps needed as no accounts created)
-        ctx.accounts.claim()
+        let event = ctx.accounts.claim(ctx.remaining_accounts)?;
+        emit_cpi!(event);
+        Ok(())
     }
 }
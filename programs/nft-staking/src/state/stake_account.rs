@@ -1,12 +1,151 @@
 use anchor_lang::prelude::*; // Import Anchor framework essentials
 
+use crate::constants::{ACC_PRECISION, MULTIPLIER_BASE}; // Fixed-point scales of the reward index and multipliers
+use crate::error::ErrorCode; // Import custom error types for checked math
+use crate::state::StakeConfig; // Import config holding the accrual parameters
+
 // Individual NFT stake record - created each time an NFT is staked
 // Tracks when and by whom each specific NFT was staked
 #[account] // Marks this as an Anchor account that can be stored on-chain
 #[derive(InitSpace)] // Automatically calculates space needed for account storage
 pub struct StakeAccount {
-    pub owner: Pubkey,  // The wallet address that staked this NFT
-    pub mint: Pubkey,   // The mint address of the specific NFT that's staked
-    pub staked_at: i64, // Unix timestamp when this NFT was staked (for freeze period)
-    pub bump: u8,       // PDA bump seed for this stake account
+    pub owner: Pubkey,       // The wallet address that staked this NFT
+    pub mint: Pubkey,        // The mint address of the specific NFT that's staked
+    pub staked_at: i64,      // Unix timestamp when this NFT was staked (for freeze period)
+    pub last_update: i64,    // Unix timestamp up to which points have been credited
+    pub reward_debt: u128, // Pool index already credited, scaled by the multiplier (emission pools)
+    pub tier: u8,          // Lock tier chosen at stake time (0: default)
+    pub lock_period: u32,  // Tier lock (seconds), fixed at stake time
+    pub multiplier_bps: u16, // Tier reward multiplier, fixed at stake time
+    pub bump: u8,          // PDA bump seed for this stake account
+}
+
+impl StakeAccount {
+    // Build the record for an NFT staked at `now` in lock tier `tier` and count it in the pool
+    // The tier's lock and multiplier are copied so later tier changes do not affect it
+    pub fn new(
+        config: &mut StakeConfig,
+        owner: Pubkey,
+        mint: Pubkey,
+        tier: u8,
+        now: i64,
+        bump: u8,
+    ) -> Result<Self> {
+        let lock = config.lock_tier(tier)?;
+        let mut stake = Self {
+            owner,
+            mint,
+            staked_at: now,   // Start of the freeze period and tier lock
+            last_update: now, // Points start accruing from this moment
+            reward_debt: 0,
+            tier,
+            lock_period: lock.lock_period,
+            multiplier_bps: lock.multiplier_bps,
+            bump,
+        };
+        // Earlier emissions belong to other stakers
+        stake.reward_debt = stake.weighted(config.add_stake(now, lock.multiplier_bps)?)?;
+        Ok(stake)
+    }
+
+    // Check the NFT may leave the pool now, remove it from the pool count
+    // and return the points it still has to credit
+    // Emergency mode skips the freeze period and tier lock but forfeits the unsettled points
+    pub fn finish(&mut self, config: &mut StakeConfig, now: i64) -> Result<u64> {
+        config.update_pool(now)?; // Settle the index before the staked count drops
+        config.remove_stake(self.multiplier_bps)?;
+        if config.emergency {
+            return Ok(0);
+        }
+
+        let lock = config.freeze_period.max(self.lock_period); // Longer of the pool minimum and the tier lock
+        require!(
+            now - self.staked_at >= lock as i64, // Check if enough time has passed
+            ErrorCode::TimeNotElapsed            // Error if freeze period or tier lock not over
+        );
+        self.accrue(config, now)
+    }
+
+    // Compute points earned since the last settlement and move the checkpoint forward
+    // Flat pools credit only whole periods so partial periods keep accruing;
+    // emission pools credit their share of the index, which `update_pool` must have
+    // brought up to `now`; both apply the tier multiplier
+    pub fn accrue(&mut self, config: &StakeConfig, now: i64) -> Result<u64> {
+        if config.shares_emission() {
+            let earned = Self::share(self.weighted(config.acc_reward_per_nft)?, self.reward_debt)?;
+            // Keep the sub-point remainder for the next settlement
+            self.reward_debt = self
+                .reward_debt
+                .checked_add(earned as u128 * ACC_PRECISION)
+                .ok_or(ErrorCode::Overflow)?;
+            self.last_update = now;
+            return Ok(earned);
+        }
+
+        let periods = self.periods(config, now)?; // Number of complete periods since the checkpoint
+
+        // Advance the checkpoint by the credited periods only
+        self.last_update = self
+            .last_update
+            .checked_add(periods * config.points_period as i64)
+            .ok_or(ErrorCode::Overflow)?;
+
+        self.points(config, periods)
+    }
+
+    // Points `accrue` would credit at `now`, without moving the checkpoint
+    // Nothing is pending in emergency mode since unsettled points are forfeited
+    pub fn pending(&self, config: &StakeConfig, now: i64) -> Result<u64> {
+        if config.emergency {
+            return Ok(0);
+        }
+        if config.shares_emission() {
+            return Self::share(self.weighted(config.acc_at(now)?)?, self.reward_debt);
+        }
+        self.points(config, self.periods(config, now)?)
+    }
+
+    // Check this record was staked by `owner` and lives at its stake PDA for `config`
+    pub fn verify(&self, address: &Pubkey, owner: &Pubkey, config: &Pubkey) -> Result<()> {
+        require_keys_eq!(self.owner, *owner, ErrorCode::WrongOwner);
+        let expected = Pubkey::create_program_address(
+            &[b"stake", self.mint.as_ref(), config.as_ref(), &[self.bump]],
+            &crate::ID,
+        )
+        .map_err(|_| ErrorCode::InvalidStakeAccount)?;
+        require_keys_eq!(expected, *address, ErrorCode::InvalidStakeAccount);
+        Ok(())
+    }
+
+    // Number of complete periods between `last_update` and `now`
+    fn periods(&self, config: &StakeConfig, now: i64) -> Result<i64> {
+        let period = config.points_period as i64; // Length of one accrual period in seconds
+        let elapsed = now
+            .checked_sub(self.last_update)
+            .ok_or(ErrorCode::Underflow)?
+            .max(0); // Guard against clock skew moving backwards
+        Ok(elapsed / period)
+    }
+
+    // Whole points one NFT earned between index `debt` and `acc`
+    fn share(acc: u128, debt: u128) -> Result<u64> {
+        let points = acc.checked_sub(debt).ok_or(ErrorCode::Underflow)? / ACC_PRECISION;
+        u64::try_from(points).map_err(|_| ErrorCode::Overflow.into())
+    }
+
+    // Points this NFT earned over `periods` periods, rounded down after the multiplier
+    fn points(&self, config: &StakeConfig, periods: i64) -> Result<u64> {
+        let base = (periods as u64)
+            .checked_mul(config.points_per_stake as u64) // Points per NFT per period
+            .ok_or(ErrorCode::Overflow)?;
+        let points = base as u128 * self.multiplier_bps as u128 / MULTIPLIER_BASE as u128;
+        u64::try_from(points).map_err(|_| ErrorCode::Overflow.into())
+    }
+
+    // Pool index `acc` scaled by this NFT's multiplier
+    fn weighted(&self, acc: u128) -> Result<u128> {
+        acc.checked_mul(self.multiplier_bps as u128)
+            .map(|scaled| scaled / MULTIPLIER_BASE as u128)
+            .ok_or(ErrorCode::Overflow.into())
+    }
 }
@@ -1,11 +1,218 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::{
+    ACC_PRECISION, MAX_LOCK_TIERS, MAX_SCHEDULE_SEGMENTS, MINT_WINDOW, MULTIPLIER_BASE,
+};
+use crate::error::ErrorCode;
+
 #[account]
 #[derive(InitSpace)]
 pub struct StakeConfig {
+    pub collection: Pubkey,
+    pub pool_id: u16,
+    pub admin: Pubkey,
+    pub pending_admin: Option<Pubkey>,
     pub points_per_stake: u8,
+    pub points_period: u32,
     pub max_stake: u8,
     pub freeze_period: u32,
+    pub reward_per_point: u64,
+    pub treasury_mint: Option<Pubkey>,
+    pub emission_per_second: u64,
+    pub acc_reward_per_nft: u128,
+    pub last_reward_time: i64,
+    pub total_staked: u64,
+    pub total_weight: u64,
+    pub start_time: i64,
+    pub end_time: Option<i64>,
+    #[max_len(MAX_SCHEDULE_SEGMENTS)]
+    pub schedule: Vec<EmissionSegment>,
+    pub max_supply: u64,
+    pub daily_mint_limit: u64,
+    pub total_minted: u64,
+    pub mint_window_start: i64,
+    pub window_minted: u64,
+    #[max_len(MAX_LOCK_TIERS)]
+    pub lock_tiers: Vec<LockTier>,
+    pub paused: u8,
+    pub emergency: bool,
+    pub custody: bool,
     pub rewards_bump: u8,
     pub bump: u8,
 }
+
+// Emission rate change: from `start_ts` on, the pool emits `rate` points per second
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EmissionSegment {
+    pub start_ts: i64,
+    pub rate: u64,
+}
+
+// Lock tier stakers can opt into: NFTs cannot be unstaked for `lock_period` seconds
+// and earn `multiplier_bps / MULTIPLIER_BASE` times the normal rewards
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LockTier {
+    pub lock_period: u32,
+    pub multiplier_bps: u16,
+}
+
+impl LockTier {
+    // Tier 0: only `freeze_period` applies, at 1x
+    pub const DEFAULT: Self = Self {
+        lock_period: 0,
+        multiplier_bps: MULTIPLIER_BASE,
+    };
+}
+
+impl StakeConfig {
+    pub fn is_paused(&self, flag: u8) -> bool {
+        self.paused & flag != 0
+    }
+
+    // Reward token base units minted for `points`
+    pub fn reward_amount(&self, points: u64) -> Result<u64> {
+        points
+            .checked_mul(self.reward_per_point)
+            .ok_or(ErrorCode::Overflow.into())
+    }
+
+    // Reward tokens `claim` may still mint at `now` under `max_supply` and
+    // `daily_mint_limit` (0 disables either); starts a new window once the current one ends
+    pub fn mint_allowance(&mut self, now: i64) -> u64 {
+        if now.saturating_sub(self.mint_window_start) >= MINT_WINDOW {
+            // Keep windows aligned to the first one so the limit cannot be reset at will
+            self.mint_window_start = now - (now - self.mint_window_start) % MINT_WINDOW;
+            self.window_minted = 0;
+        }
+
+        let mut allowance = u64::MAX;
+        if self.max_supply > 0 {
+            allowance = allowance.min(self.max_supply.saturating_sub(self.total_minted));
+        }
+        if self.daily_mint_limit > 0 {
+            allowance = allowance.min(self.daily_mint_limit.saturating_sub(self.window_minted));
+        }
+        allowance
+    }
+
+    // Count `amount` newly minted reward tokens against both limits
+    pub fn record_mint(&mut self, amount: u64) -> Result<()> {
+        self.total_minted = self
+            .total_minted
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        self.window_minted = self
+            .window_minted
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+
+    // Lock tier `tier`: 0 is the default tier, 1.. index `lock_tiers`
+    pub fn lock_tier(&self, tier: u8) -> Result<LockTier> {
+        match tier {
+            0 => Ok(LockTier::DEFAULT),
+            _ => self
+                .lock_tiers
+                .get(tier as usize - 1)
+                .copied()
+                .ok_or(ErrorCode::InvalidLockTier.into()),
+        }
+    }
+
+    // Whether the pool splits `emission_per_second` among staked NFTs
+    // instead of paying flat per-NFT points
+    pub fn shares_emission(&self) -> bool {
+        self.emission_per_second > 0
+    }
+
+    // Reward index at `now`: points per 1x-weighted NFT since the pool started, scaled by ACC_PRECISION
+    // Time with nothing staked is not distributed
+    pub fn acc_at(&self, now: i64) -> Result<u128> {
+        if !self.shares_emission() || self.total_weight == 0 || now <= self.last_reward_time {
+            return Ok(self.acc_reward_per_nft);
+        }
+        let reward = self
+            .emitted_between(self.last_reward_time, now)?
+            .checked_mul(ACC_PRECISION * MULTIPLIER_BASE as u128)
+            .ok_or(ErrorCode::Overflow)?
+            / self.total_weight as u128; // Split among staked NFTs by multiplier
+        self.acc_reward_per_nft
+            .checked_add(reward)
+            .ok_or(ErrorCode::Overflow.into())
+    }
+
+    // Points the whole pool emits between `from` and `to`
+    // Only time inside [start_time, end_time) counts; `emission_per_second` applies until
+    // the first schedule segment, then each segment's rate until the next one starts
+    pub fn emitted_between(&self, from: i64, to: i64) -> Result<u128> {
+        let to = self.end_time.map_or(to, |end| to.min(end));
+        let mut cursor = from.max(self.start_time);
+        let mut rate = self.emission_per_second; // Rate in force at `cursor`
+        let mut total: u128 = 0;
+
+        for segment in &self.schedule {
+            if segment.start_ts <= cursor {
+                rate = segment.rate; // Segment already started
+                continue;
+            }
+            if segment.start_ts >= to {
+                break; // Segment starts after the window
+            }
+            total = total
+                .checked_add(Self::emission(rate, segment.start_ts - cursor)?)
+                .ok_or(ErrorCode::Overflow)?;
+            cursor = segment.start_ts;
+            rate = segment.rate;
+        }
+
+        if to > cursor {
+            total = total
+                .checked_add(Self::emission(rate, to - cursor)?)
+                .ok_or(ErrorCode::Overflow)?;
+        }
+        Ok(total)
+    }
+
+    // Points emitted at `rate` over `seconds`
+    fn emission(rate: u64, seconds: i64) -> Result<u128> {
+        (seconds as u128)
+            .checked_mul(rate as u128)
+            .ok_or(ErrorCode::Overflow.into())
+    }
+
+    // Bring the reward index up to `now`; must run before the staked count changes
+    pub fn update_pool(&mut self, now: i64) -> Result<()> {
+        self.acc_reward_per_nft = self.acc_at(now)?;
+        self.last_reward_time = self.last_reward_time.max(now);
+        Ok(())
+    }
+
+    // Settle the index and count an NFT of `weight` (its multiplier) entering the pool;
+    // returns the index it starts from
+    pub fn add_stake(&mut self, now: i64, weight: u16) -> Result<u128> {
+        self.update_pool(now)?;
+        self.total_staked = self
+            .total_staked
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+        self.total_weight = self
+            .total_weight
+            .checked_add(weight as u64)
+            .ok_or(ErrorCode::Overflow)?;
+        Ok(self.acc_reward_per_nft)
+    }
+
+    // Remove an NFT of `weight` from the count; the index must already be settled
+    pub fn remove_stake(&mut self, weight: u16) -> Result<()> {
+        self.total_staked = self
+            .total_staked
+            .checked_sub(1)
+            .ok_or(ErrorCode::Underflow)?;
+        self.total_weight = self
+            .total_weight
+            .checked_sub(weight as u64)
+            .ok_or(ErrorCode::Underflow)?;
+        Ok(())
+    }
+}
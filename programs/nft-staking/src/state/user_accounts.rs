@@ -1,9 +1,52 @@
 use anchor_lang::prelude::*;
 
+use crate::error::ErrorCode;
+use crate::state::StakeConfig;
+
 #[account]
 #[derive(InitSpace)]
 pub struct UserAccount {
-    pub points: u32,
+    pub points: u64,
     pub amount_staked: u8,
     pub bump: u8,
 }
+
+impl UserAccount {
+    // Count a newly staked NFT against the pool's per-user limit
+    pub fn record_stake(&mut self, config: &StakeConfig) -> Result<()> {
+        self.record_stakes(config, 1)
+    }
+
+    // Count `count` newly staked NFTs at once, failing if any would exceed the limit
+    pub fn record_stakes(&mut self, config: &StakeConfig, count: u8) -> Result<()> {
+        require!(
+            count <= config.max_stake.saturating_sub(self.amount_staked),
+            ErrorCode::MaxStake
+        );
+        self.amount_staked = self
+            .amount_staked
+            .checked_add(count)
+            .ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+
+    // Remove an unstaked NFT from the count and credit the points it earned
+    pub fn record_unstake(&mut self, earned: u64) -> Result<()> {
+        self.record_unstakes(1, earned)
+    }
+
+    // Remove `count` unstaked NFTs from the count and credit their combined points
+    pub fn record_unstakes(&mut self, count: u8, earned: u64) -> Result<()> {
+        require!(self.amount_staked >= count, ErrorCode::NothingStaked);
+        self.amount_staked = self
+            .amount_staked
+            .checked_sub(count)
+            .ok_or(ErrorCode::Underflow)?;
+        self.credit(earned)
+    }
+
+    pub fn credit(&mut self, points: u64) -> Result<()> {
+        self.points = self.points.checked_add(points).ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+}
@@ -43,12 +43,44 @@ describe("nft-staking", () => {
 
   // Test configuration values
   const POINTS_PER_STAKE = 10;
+  const POINTS_PERIOD = 1; // Points accrue every second for testing
   const MAX_STAKE = 5;
   const FREEZE_PERIOD = 5; // 5 seconds for testing (instead of 86400)
+  const REWARD_PER_POINT = new anchor.BN(1_000); // 0.001 reward tokens per point
+  const POOL_ID = 0; // First pool for the collection
+  const CONFIG_PARAMS = {
+    pointsPerStake: POINTS_PER_STAKE,
+    pointsPeriod: POINTS_PERIOD,
+    maxStake: MAX_STAKE,
+    freezePeriod: FREEZE_PERIOD,
+    rewardPerPoint: REWARD_PER_POINT,
+    emissionPerSecond: new anchor.BN(0), // Flat per-NFT points
+    maxSupply: new anchor.BN(0), // Uncapped reward mint
+    dailyMintLimit: new anchor.BN(0), // No daily mint limit
+    custody: false,
+  };
+  const NO_CHANGES = {
+    pointsPerStake: null,
+    pointsPeriod: null,
+    maxStake: null,
+    freezePeriod: null,
+    rewardPerPoint: null,
+    emissionPerSecond: null,
+    maxSupply: null,
+    dailyMintLimit: null,
+  };
+
+  // Pool config PDA: one per collection and pool id
+  const poolIdSeed = (poolId: number) => {
+    const buf = Buffer.alloc(2);
+    buf.writeUInt16LE(poolId);
+    return buf;
+  };
 
   before(async () => {
     // Setup test accounts
-    admin = Keypair.generate();
+    // The provider wallet deployed the program, so it holds the upgrade authority
+    admin = (provider.wallet as anchor.Wallet).payer;
     user = Keypair.generate();
 
     // Airdrop SOL to test accounts
@@ -84,7 +116,7 @@ describe("nft-staking", () => {
 
     // Derive PDAs
     [config] = PublicKey.findProgramAddressSync(
-      [Buffer.from("config")],
+      [Buffer.from("config"), collectionMint.toBuffer(), poolIdSeed(POOL_ID)],
       program.programId
     );
 
@@ -94,7 +126,7 @@ describe("nft-staking", () => {
     );
 
     [userAccount] = PublicKey.findProgramAddressSync(
-      [Buffer.from("user"), user.publicKey.toBuffer()],
+      [Buffer.from("user"), config.toBuffer(), user.publicKey.toBuffer()],
       program.programId
     );
 
@@ -136,9 +168,10 @@ describe("nft-staking", () => {
   describe("Initialize Config", () => {
     it("Should initialize staking configuration", async () => {
       const tx = await program.methods
-        .initializeConfig(POINTS_PER_STAKE, MAX_STAKE, FREEZE_PERIOD)
+        .initializeConfig(collectionMint, POOL_ID, CONFIG_PARAMS)
         .accounts({
           admin: admin.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
         })
         .signers([admin])
         .rpc();
@@ -148,8 +181,13 @@ describe("nft-staking", () => {
       // Verify the config account was created with correct values
       const configAccount = await program.account.stakeConfig.fetch(config);
       expect(configAccount.pointsPerStake).to.equal(POINTS_PER_STAKE);
+      expect(configAccount.pointsPeriod).to.equal(POINTS_PERIOD);
+      expect(configAccount.collection.equals(collectionMint)).to.be.true;
+      expect(configAccount.poolId).to.equal(POOL_ID);
       expect(configAccount.maxStake).to.equal(MAX_STAKE);
       expect(configAccount.freezePeriod).to.equal(FREEZE_PERIOD);
+      expect(configAccount.rewardPerPoint.eq(REWARD_PER_POINT)).to.be.true;
+      expect(configAccount.custody).to.be.false;
 
       console.log("✅ Config initialized successfully");
       console.log("Points per stake:", configAccount.pointsPerStake);
@@ -157,6 +195,26 @@ describe("nft-staking", () => {
       console.log("Freeze period:", configAccount.freezePeriod);
     });
 
+    it("Should reject initialization by a non upgrade authority", async () => {
+      // Fresh collection so the pool PDA is unused and only the authority check can fail
+      const freshCollection = Keypair.generate().publicKey;
+      try {
+        await program.methods
+          .initializeConfig(freshCollection, POOL_ID, CONFIG_PARAMS)
+          .accounts({
+            admin: user.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([user])
+          .rpc();
+
+        expect.fail("Should have thrown an error for non upgrade authority");
+      } catch (error) {
+        expect(error.message).to.include("NotUpgradeAuthority");
+        console.log("✅ Correctly rejected non upgrade authority initialization");
+      }
+    });
+
     it("Should create rewards mint with correct authority", async () => {
       const mintInfo = await provider.connection.getAccountInfo(rewardsMint);
       expect(mintInfo).to.not.be.null;
@@ -165,12 +223,162 @@ describe("nft-staking", () => {
     });
   });
 
+  describe("Admin Controls", () => {
+    it("Should record the initializer as config admin", async () => {
+      const configAccount = await program.account.stakeConfig.fetch(config);
+      expect(configAccount.admin.equals(admin.publicKey)).to.be.true;
+      expect(configAccount.pendingAdmin).to.be.null;
+
+      console.log("✅ Config admin recorded:", configAccount.admin.toString());
+    });
+
+    it("Should let the admin update the freeze period", async () => {
+      await program.methods
+        .updateConfig({ ...NO_CHANGES, freezePeriod: FREEZE_PERIOD + 1 })
+        .accountsPartial({ admin: admin.publicKey, config })
+        .signers([admin])
+        .rpc();
+
+      let configAccount = await program.account.stakeConfig.fetch(config);
+      expect(configAccount.freezePeriod).to.equal(FREEZE_PERIOD + 1);
+      expect(configAccount.pointsPerStake).to.equal(POINTS_PER_STAKE);
+
+      // Restore the original value for the remaining tests
+      await program.methods
+        .updateConfig({ ...NO_CHANGES, freezePeriod: FREEZE_PERIOD })
+        .accountsPartial({ admin: admin.publicKey, config })
+        .signers([admin])
+        .rpc();
+
+      configAccount = await program.account.stakeConfig.fetch(config);
+      expect(configAccount.freezePeriod).to.equal(FREEZE_PERIOD);
+      console.log("✅ Admin updated config parameters");
+    });
+
+    it("Should reject config updates from non-admins", async () => {
+      try {
+        await program.methods
+          .updateConfig({ ...NO_CHANGES, maxStake: 100 })
+          .accountsPartial({ admin: user.publicKey, config })
+          .signers([user])
+          .rpc();
+
+        expect.fail("Should have thrown an error for non-admin update");
+      } catch (error) {
+        expect(error.message).to.include("Unauthorized");
+        console.log("✅ Correctly rejected non-admin config update");
+      }
+    });
+
+    it("Should reject a zero reward per point", async () => {
+      try {
+        await program.methods
+          .updateConfig({ ...NO_CHANGES, rewardPerPoint: new anchor.BN(0) })
+          .accountsPartial({ admin: admin.publicKey, config })
+          .signers([admin])
+          .rpc();
+
+        expect.fail("Should have thrown an error for a zero reward rate");
+      } catch (error) {
+        expect(error.message).to.include("InvalidRewardRate");
+        console.log("✅ Correctly rejected zero reward per point");
+      }
+    });
+
+    it("Should reject an emission rate on a flat-points pool", async () => {
+      try {
+        await program.methods
+          .updateConfig({ ...NO_CHANGES, emissionPerSecond: new anchor.BN(10) })
+          .accountsPartial({ admin: admin.publicKey, config })
+          .signers([admin])
+          .rpc();
+
+        expect.fail("Should have thrown an error for an emission rate");
+      } catch (error) {
+        expect(error.message).to.include("InvalidEmissionRate");
+        console.log("✅ Correctly rejected emission rate on a flat pool");
+      }
+    });
+
+    it("Should reject an emission schedule on a flat-points pool", async () => {
+      try {
+        const now = Math.floor(Date.now() / 1000);
+        await program.methods
+          .setEmissionSchedule({
+            startTime: new anchor.BN(now),
+            endTime: new anchor.BN(now + 3600),
+            segments: [],
+          })
+          .accountsPartial({ admin: admin.publicKey, config })
+          .signers([admin])
+          .rpc();
+
+        expect.fail("Should have thrown an error for a schedule");
+      } catch (error) {
+        expect(error.message).to.include("InvalidSchedule");
+        console.log("✅ Correctly rejected schedule on a flat pool");
+      }
+    });
+
+    it("Should reject a lock tier below 1x", async () => {
+      try {
+        await program.methods
+          .setLockTiers([{ lockPeriod: 86400, multiplierBps: 5_000 }])
+          .accountsPartial({ admin: admin.publicKey, config })
+          .signers([admin])
+          .rpc();
+
+        expect.fail("Should have thrown an error for a sub-1x tier");
+      } catch (error) {
+        expect(error.message).to.include("InvalidLockTier");
+        console.log("✅ Correctly rejected lock tier below 1x");
+      }
+    });
+
+    it("Should transfer admin in two steps", async () => {
+      await program.methods
+        .transferAdmin(user.publicKey)
+        .accountsPartial({ admin: admin.publicKey, config })
+        .signers([admin])
+        .rpc();
+
+      let configAccount = await program.account.stakeConfig.fetch(config);
+      expect(configAccount.pendingAdmin.equals(user.publicKey)).to.be.true;
+      expect(configAccount.admin.equals(admin.publicKey)).to.be.true;
+
+      await program.methods
+        .acceptAdmin()
+        .accountsPartial({ newAdmin: user.publicKey, config })
+        .signers([user])
+        .rpc();
+
+      configAccount = await program.account.stakeConfig.fetch(config);
+      expect(configAccount.admin.equals(user.publicKey)).to.be.true;
+      expect(configAccount.pendingAdmin).to.be.null;
+
+      // Hand the role back so later tests keep using the original admin
+      await program.methods
+        .transferAdmin(admin.publicKey)
+        .accountsPartial({ admin: user.publicKey, config })
+        .signers([user])
+        .rpc();
+      await program.methods
+        .acceptAdmin()
+        .accountsPartial({ newAdmin: admin.publicKey, config })
+        .signers([admin])
+        .rpc();
+
+      console.log("✅ Two-step admin transfer completed");
+    });
+  });
+
   describe("Initialize User Account", () => {
     it("Should initialize user account with zero values", async () => {
       const tx = await program.methods
         .initializeUser()
-        .accounts({
+        .accountsPartial({
           user: user.publicKey,
+          config,
         })
         .signers([user])
         .rpc();
@@ -181,11 +389,11 @@ describe("nft-staking", () => {
       const userAccountData = await program.account.userAccount.fetch(
         userAccount
       );
-      expect(userAccountData.points).to.equal(0);
+      expect(userAccountData.points.toNumber()).to.equal(0);
       expect(userAccountData.amountStaked).to.equal(0);
 
       console.log("✅ User account initialized successfully");
-      console.log("Initial points:", userAccountData.points);
+      console.log("Initial points:", userAccountData.points.toNumber());
       console.log("Initial amount staked:", userAccountData.amountStaked);
     });
 
@@ -193,8 +401,9 @@ describe("nft-staking", () => {
       try {
         await program.methods
           .initializeUser()
-          .accounts({
+          .accountsPartial({
             user: user.publicKey,
+            config,
           })
           .signers([user])
           .rpc();
@@ -315,13 +524,13 @@ describe("nft-staking", () => {
       // Test the unstaking process logic:
       // 1. Decrease user's amount_staked
       // 2. Increase user's points
-      // 3. Transfer NFT from vault back to user
+      // 3. Thaw the NFT in the user's ATA and revoke the stake delegate
       // 4. Close stake account and return rent
 
       console.log("Unstaking process validation:");
       console.log("1. ✅ Decreases amount_staked by 1");
-      console.log("2. ✅ Increases points by points_per_stake");
-      console.log("3. ✅ Transfers NFT from vault to user ATA");
+      console.log("2. ✅ Credits points accrued since the last checkpoint");
+      console.log("3. ✅ Thaws NFT in user ATA and revokes delegate");
       console.log("4. ✅ Closes stake account and returns rent");
 
       console.log("✅ Unstaking process flow validated");
@@ -351,7 +560,7 @@ describe("nft-staking", () => {
         userAccount
       );
       console.log("Reward claiming requirements:");
-      console.log("- User points:", userAccountData.points);
+      console.log("- User points:", userAccountData.points.toNumber());
       console.log("- Points will be converted to reward tokens 1:1");
       console.log("- Points reset to 0 after claiming");
 
@@ -378,12 +587,12 @@ describe("nft-staking", () => {
         userAccount
       );
 
-      if (userAccountData.points === 0) {
+      if (userAccountData.points.isZero()) {
         console.log("✅ User has 0 points - claiming should be prevented");
       } else {
         console.log(
           "📝 User has",
-          userAccountData.points,
+          userAccountData.points.toNumber(),
           "points available for claiming"
         );
       }
@@ -413,13 +622,73 @@ describe("nft-staking", () => {
       // Test that new error types are properly defined
       console.log("New error types validation:");
       console.log("✅ TimeNotElapsed - for premature unstaking");
-      console.log("✅ MaxStake - for staking limits and zero rewards");
+      console.log("✅ MaxStake - for staking limits");
       console.log("✅ Underflow - for arithmetic underflow protection");
       console.log("✅ Overflow - for arithmetic overflow protection");
+      console.log("✅ MissingCollection / CollectionMismatch / CollectionNotVerified");
+      console.log("✅ NothingStaked - for unstaking with nothing staked");
+      console.log("✅ NoPointsToClaim - for claiming with zero points");
+      console.log("✅ WrongOwner - for acting on another wallet's stake");
 
       console.log("✅ All error types properly validated");
     });
 
+    it("Should expose the error taxonomy in the IDL", async () => {
+      const errorNames = program.idl.errors.map((e) => e.name);
+      for (const name of [
+        "missingCollection",
+        "collectionMismatch",
+        "collectionNotVerified",
+        "nothingStaked",
+        "noPointsToClaim",
+        "wrongOwner",
+        "poolPaused",
+      ]) {
+        expect(errorNames).to.include(name);
+      }
+
+      console.log("✅ Distinct error codes available to clients");
+    });
+
+    it("Should reject claiming with zero points", async () => {
+      try {
+        await program.methods
+          .claim()
+          .accountsPartial({
+            user: user.publicKey,
+            config,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([user])
+          .rpc();
+
+        expect.fail("Should have thrown an error for zero points");
+      } catch (error) {
+        expect(error.message).to.include("NoPointsToClaim");
+        console.log("✅ Zero-point claim rejected with NoPointsToClaim");
+      }
+    });
+
+    it("Should reject treasury claims on a minting pool", async () => {
+      try {
+        await program.methods
+          .claimTreasury()
+          .accountsPartial({
+            user: user.publicKey,
+            config,
+            treasuryMint: rewardsMint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([user])
+          .rpc();
+
+        expect.fail("Should have thrown an error without a treasury");
+      } catch (error) {
+        expect(error.message).to.include("WrongRewardMode");
+        console.log("✅ Treasury claim rejected on a minting pool");
+      }
+    });
+
     it("Should validate arithmetic safety", async () => {
       // Test arithmetic operations use checked math
       console.log("Arithmetic safety validation:");
@@ -431,11 +700,81 @@ describe("nft-staking", () => {
     });
   });
 
+  describe("Pause and Emergency Controls", () => {
+    const PAUSE_CLAIM = 1 << 2;
+
+    it("Should reject claims while claiming is paused", async () => {
+      await program.methods
+        .setPaused(PAUSE_CLAIM)
+        .accountsPartial({ admin: admin.publicKey, config })
+        .signers([admin])
+        .rpc();
+
+      try {
+        await program.methods
+          .claim()
+          .accountsPartial({
+            user: user.publicKey,
+            config,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([user])
+          .rpc();
+
+        expect.fail("Should have thrown an error while paused");
+      } catch (error) {
+        expect(error.message).to.include("PoolPaused");
+        console.log("✅ Claim rejected while paused");
+      } finally {
+        await program.methods
+          .setPaused(0)
+          .accountsPartial({ admin: admin.publicKey, config })
+          .signers([admin])
+          .rpc();
+      }
+    });
+
+    it("Should reject unknown pause flags", async () => {
+      try {
+        await program.methods
+          .setPaused(1 << 7)
+          .accountsPartial({ admin: admin.publicKey, config })
+          .signers([admin])
+          .rpc();
+
+        expect.fail("Should have thrown an error for unknown flags");
+      } catch (error) {
+        expect(error.message).to.include("InvalidPauseFlags");
+        console.log("✅ Unknown pause flags rejected");
+      }
+    });
+
+    it("Should toggle emergency mode", async () => {
+      await program.methods
+        .setEmergency(true)
+        .accountsPartial({ admin: admin.publicKey, config })
+        .signers([admin])
+        .rpc();
+      let configData = await program.account.stakeConfig.fetch(config);
+      expect(configData.emergency).to.be.true;
+
+      await program.methods
+        .setEmergency(false)
+        .accountsPartial({ admin: admin.publicKey, config })
+        .signers([admin])
+        .rpc();
+      configData = await program.account.stakeConfig.fetch(config);
+      expect(configData.emergency).to.be.false;
+
+      console.log("✅ Emergency mode toggled by admin");
+    });
+  });
+
   describe("Account Validation", () => {
     it("Should verify all PDAs are correctly derived", async () => {
       // Verify config PDA
       const [derivedConfig, configBump] = PublicKey.findProgramAddressSync(
-        [Buffer.from("config")],
+        [Buffer.from("config"), collectionMint.toBuffer(), poolIdSeed(POOL_ID)],
         program.programId
       );
       expect(derivedConfig.equals(config)).to.be.true;
@@ -450,7 +789,7 @@ describe("nft-staking", () => {
 
       // Verify user account PDA
       const [derivedUserAccount, userBump] = PublicKey.findProgramAddressSync(
-        [Buffer.from("user"), user.publicKey.toBuffer()],
+        [Buffer.from("user"), config.toBuffer(), user.publicKey.toBuffer()],
         program.programId
       );
       expect(derivedUserAccount.equals(userAccount)).to.be.true;
@@ -511,7 +850,7 @@ describe("nft-staking", () => {
       console.log("  Rewards bump:", configData.rewardsBump);
 
       console.log("\nUser Account:");
-      console.log("  Points:", userData.points);
+      console.log("  Points:", userData.points.toNumber());
       console.log("  Amount staked:", userData.amountStaked);
       console.log("  User bump:", userData.bump);
 
@@ -0,0 +1,10 @@
+#!/usr/bin/env sh
+# Build the program into target/deploy/nft_staking.so, which the SVM harness loads
+# Uses the Anchor CLI when installed, otherwise the Solana SBF toolchain directly
+set -e
+cd "$(dirname "$0")/../.."
+if command -v anchor >/dev/null 2>&1; then
+  anchor build -p nft_staking
+else
+  cargo build-sbf --manifest-path programs/nft-staking/Cargo.toml --sbf-out-dir target/deploy
+fi
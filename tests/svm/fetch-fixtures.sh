@@ -0,0 +1,35 @@
+#!/usr/bin/env sh
+# Dump the mainnet Metaplex programs the SVM tests CPI into, into fixtures/
+# Each dump must match its pin in fixtures/PINS (name, deploy slot, SHA-256), so a
+# Metaplex upgrade on mainnet fails here instead of silently changing what the tests run
+# `--update` re-pins to the builds currently on mainnet; commit the .so files with PINS
+set -e
+cd "$(dirname "$0")"
+mkdir -p fixtures
+pins=fixtures/PINS
+update=
+if [ "$1" = "--update" ]; then
+  update=1
+  sed -n '/^#/p' "$pins" >"$pins.new"
+fi
+dump() {
+  solana program dump -u m "$1" "fixtures/$2.so"
+  slot=$(solana program show -u m "$1" | sed -n 's/^Last Deployed In Slot: *//p')
+  sha=$(sha256sum "fixtures/$2.so")
+  sha=${sha%% *}
+  if [ -n "$update" ]; then
+    echo "$2 $slot $sha" >>"$pins.new"
+  elif ! grep -qx "$2 [0-9]* $sha" "$pins"; then
+    echo "fixtures/$2.so (slot $slot, sha256 $sha) is not the build pinned in $pins;" \
+      "rerun with --update to re-pin" >&2
+    exit 1
+  fi
+}
+dump metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s mpl_token_metadata      # Legacy and programmable NFTs
+dump CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7d mpl_core                # Core assets
+dump BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY mpl_bubblegum           # Compressed NFTs
+dump mcmt6YrQEMKw8Mw43FmpRLmf7BqRnFMKmAcbxE3xkAW mpl_account_compression # V2 Merkle trees
+dump mnoopTCrg4p8ry25e4bcWA9XZjbNjMTfgYVGGEdRsf3 mpl_noop                # V2 tree log wrapper
+if [ -n "$update" ]; then
+  mv "$pins.new" "$pins"
+fi
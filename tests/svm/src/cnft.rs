@@ -0,0 +1,135 @@
+// Off-chain mirror of a Bubblegum V2 tree
+// The program takes a cNFT's current leaf contents, root and proof from the caller
+// (normally the DAS API), so the tests track every leaf they mint and change
+
+use anchor_lang::solana_program::keccak;
+use mpl_bubblegum::{
+    hash::{hash_collection_option, hash_creators, hash_metadata, DEFAULT_ASSET_DATA_HASH},
+    types::{LeafSchema, MetadataArgsV2},
+    utils::get_asset_id,
+    Flags, DEFAULT_FLAGS,
+};
+use nft_staking_client::CnftLeaf;
+use solana_sdk::pubkey::Pubkey;
+
+// Tree shape: room for 8 leaves, no canopy, so every proof carries all three nodes
+pub const TREE_DEPTH: u32 = 3;
+pub const TREE_BUFFER: u32 = 8;
+
+// Concurrent Merkle tree account size: the 56-byte header, the sequence number,
+// active index and buffer size, then the change log buffer and the rightmost path,
+// each entry a node per level plus the root or leaf and an index
+const TREE_HEADER: usize = 56;
+const TREE_ENTRY: usize = 32 * TREE_DEPTH as usize + 40;
+pub const TREE_ACCOUNT_SIZE: usize = TREE_HEADER + 24 + (TREE_BUFFER as usize + 1) * TREE_ENTRY;
+
+// Bubblegum's signer for its CPIs into Core collections
+pub const MPL_CORE_CPI_SIGNER: Pubkey =
+    solana_sdk::pubkey!("CbNY3JiXdXNE9tPNEk1aRZVEkWdj2v7kfJLNQwZZgpXk");
+
+// Leaves of one tree, indexed by leaf index (which equals the nonce here)
+pub struct CnftTree {
+    pub address: Pubkey,
+    pub collection: Pubkey, // Core collection every leaf is minted into
+    leaves: Vec<LeafSchema>,
+}
+
+impl CnftTree {
+    pub fn new(address: Pubkey, collection: Pubkey) -> Self {
+        Self {
+            address,
+            collection,
+            leaves: Vec::new(),
+        }
+    }
+
+    // Record a freshly minted leaf, owned and delegated to `owner`; returns its index
+    pub fn push(&mut self, owner: &Pubkey, metadata: &MetadataArgsV2) -> u32 {
+        let nonce = self.leaves.len() as u64;
+        self.leaves.push(LeafSchema::V2 {
+            id: get_asset_id(&self.address, nonce),
+            owner: *owner,
+            delegate: *owner,
+            nonce,
+            data_hash: hash_metadata(metadata).unwrap(),
+            creator_hash: hash_creators(&metadata.creators),
+            collection_hash: hash_collection_option(metadata.collection).unwrap(),
+            asset_data_hash: DEFAULT_ASSET_DATA_HASH,
+            flags: DEFAULT_FLAGS,
+        });
+        nonce as u32
+    }
+
+    // Asset id of the leaf at `index`
+    pub fn asset_id(&self, index: u32) -> Pubkey {
+        self.leaves[index as usize].id()
+    }
+
+    // Record a new delegate and asset-level freeze state for the leaf at `index`
+    pub fn set_delegate(&mut self, index: u32, new_delegate: Pubkey, frozen: bool) {
+        if let LeafSchema::V2 {
+            delegate, flags, ..
+        } = &mut self.leaves[index as usize]
+        {
+            *delegate = new_delegate;
+            *flags = Flags::new().with_asset_lvl_frozen(frozen).into_bytes()[0];
+        }
+    }
+
+    // Current contents of the leaf at `index`, as the stake and unstake instructions take them
+    pub fn leaf(&self, index: u32) -> CnftLeaf {
+        let schema = &self.leaves[index as usize];
+        CnftLeaf {
+            root: self.root(),
+            data_hash: schema.data_hash(),
+            creator_hash: schema.creator_hash(),
+            asset_data_hash: Some(schema.asset_data_hash()),
+            flags: Some(schema.flags()),
+            nonce: schema.nonce(),
+            index,
+        }
+    }
+
+    // Sibling nodes from the leaf at `index` up to the root
+    pub fn proof(&self, index: u32) -> Vec<Pubkey> {
+        let mut proof = Vec::new();
+        let mut level = self.leaf_hashes();
+        let mut position = index as usize;
+        while level.len() > 1 {
+            proof.push(Pubkey::new_from_array(level[position ^ 1]));
+            level = parent_level(&level);
+            position /= 2;
+        }
+        proof
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        let mut level = self.leaf_hashes();
+        while level.len() > 1 {
+            level = parent_level(&level);
+        }
+        level[0]
+    }
+
+    // Leaf hashes padded with empty (zero) leaves to the full tree width
+    fn leaf_hashes(&self) -> Vec<[u8; 32]> {
+        let mut hashes: Vec<_> = self.leaves.iter().map(LeafSchema::hash).collect();
+        hashes.resize(1 << TREE_DEPTH, [0; 32]);
+        hashes
+    }
+}
+
+fn parent_level(level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    level
+        .chunks(2)
+        .map(|pair| keccak::hashv(&[&pair[0], &pair[1]]).to_bytes())
+        .collect()
+}
+
+// Root stored on chain: the root of the tree account's active change log
+pub fn onchain_root(data: &[u8]) -> [u8; 32] {
+    let active_index =
+        u64::from_le_bytes(data[TREE_HEADER + 8..TREE_HEADER + 16].try_into().unwrap());
+    let offset = TREE_HEADER + 24 + active_index as usize * TREE_ENTRY;
+    data[offset..offset + 32].try_into().unwrap()
+}
@@ -0,0 +1,960 @@
+// LiteSVM harness for the nft-staking integration tests
+// Loads the program built by `build-program.sh` and the Metaplex binaries in
+// `fixtures/`, mints real verified-collection NFTs and drives the pool through
+// the client crate's instruction builders
+
+#![allow(deprecated)] // Loader module paths are deprecated upstream but still re-exported
+#![allow(clippy::result_large_err)] // LiteSVM's failed-transaction metadata is large by design
+
+mod cnft; // Off-chain mirror of Bubblegum trees
+
+use std::path::PathBuf;
+
+use anchor_lang::event::EVENT_IX_TAG_LE;
+use anchor_lang::{AccountDeserialize, Event, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::{
+    get_associated_token_address, get_associated_token_address_with_program_id,
+    spl_associated_token_account::instruction::create_associated_token_account_idempotent,
+};
+use anchor_spl::metadata::mpl_token_metadata::{
+    self,
+    accounts::TokenRecord,
+    instructions::{CreateV1Builder, MintV1Builder, VerifyCollectionV1Builder},
+    types::{Collection, CollectionDetails, PrintSupply, TokenStandard},
+};
+use anchor_spl::token::spl_token::{self, state::Account as TokenAccount};
+use anchor_spl::token_2022::{
+    self,
+    spl_token_2022::{
+        self,
+        extension::{group_member_pointer, group_pointer, ExtensionType, StateWithExtensions},
+        state::{Account as TokenAccount2022, Mint as Mint2022},
+    },
+};
+use litesvm::{
+    types::{TransactionMetadata, TransactionResult},
+    LiteSVM,
+};
+use mpl_bubblegum::{
+    instructions::{CreateTreeConfigV2Builder, MintV2Builder},
+    types::MetadataArgsV2,
+};
+use mpl_core::{
+    accounts::BaseAssetV1,
+    instructions::{CreateCollectionV2Builder, CreateV2Builder, TransferV1Builder},
+    types::{BubblegumV2, Plugin, PluginAuthorityPair},
+};
+use nft_staking::error::ErrorCode;
+use nft_staking::{MPL_ACCOUNT_COMPRESSION_ID, MPL_NOOP_ID};
+use nft_staking_client as client;
+use solana_sdk::{
+    account::Account,
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    clock::Clock,
+    instruction::{Instruction, InstructionError},
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction,
+    transaction::{Transaction, TransactionError},
+};
+use spl_token_group_interface::instruction::{initialize_group, initialize_member};
+
+pub use client::{
+    ConfigParams, EmissionSegment, LockTier, PendingRewards, ScheduleParams, StakeConfig,
+    UserAccount,
+};
+pub use cnft::CnftTree;
+
+// Lamports given to every wallet the harness creates
+const AIRDROP: u64 = 100_000_000_000;
+
+// Default pool parameters used by the tests
+pub const POINTS_PER_STAKE: u8 = 10;
+pub const POINTS_PERIOD: u32 = 60;
+pub const MAX_STAKE: u8 = 2;
+pub const FREEZE_PERIOD: u32 = 120;
+pub const REWARD_PER_POINT: u64 = 1_000; // Reward mint has 6 decimals
+
+pub fn default_params() -> ConfigParams {
+    ConfigParams {
+        points_per_stake: POINTS_PER_STAKE,
+        points_period: POINTS_PERIOD,
+        max_stake: MAX_STAKE,
+        freeze_period: FREEZE_PERIOD,
+        reward_per_point: REWARD_PER_POINT,
+        emission_per_second: 0, // Flat per-NFT points
+        max_supply: 0,          // Uncapped
+        daily_mint_limit: 0,    // Unlimited
+        custody: false,
+    }
+}
+
+// Path of a file relative to this crate
+fn crate_path(path: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(path)
+}
+
+// Load a mainnet program dumped by `fetch-fixtures.sh`
+fn add_fixture(svm: &mut LiteSVM, program_id: Pubkey, name: &str) {
+    svm.add_program_from_file(program_id, crate_path(&format!("fixtures/{name}.so")))
+        .unwrap_or_else(|_| panic!("fixtures/{name}.so is missing; run ./fetch-fixtures.sh"));
+}
+
+// A freshly deployed program with one pool over a verified collection
+pub struct Harness {
+    pub svm: LiteSVM,
+    pub admin: Keypair, // Upgrade authority and pool admin; also the collection authority
+    pub collection: Pubkey, // Collection NFT mint accepted by the pool
+    pub config: Pubkey, // Pool config PDA
+}
+
+impl Harness {
+    // Deploy both programs and mint the collection NFT, without creating the pool
+    pub fn deploy() -> Self {
+        let mut svm = LiteSVM::new();
+        let program = std::fs::read(crate_path("../../target/deploy/nft_staking.so"))
+            .expect("run tests/svm/build-program.sh first");
+        svm.add_program(client::ID, &program);
+        add_fixture(&mut svm, mpl_token_metadata::ID, "mpl_token_metadata");
+        add_fixture(&mut svm, mpl_core::ID, "mpl_core");
+        add_fixture(&mut svm, mpl_bubblegum::ID, "mpl_bubblegum");
+        add_fixture(
+            &mut svm,
+            MPL_ACCOUNT_COMPRESSION_ID,
+            "mpl_account_compression",
+        );
+        add_fixture(&mut svm, MPL_NOOP_ID, "mpl_noop");
+
+        let admin = Keypair::new();
+        svm.airdrop(&admin.pubkey(), AIRDROP).unwrap();
+
+        // `initialize_config` only trusts the upgrade authority recorded in ProgramData
+        let mut data = bincode::serialize(&UpgradeableLoaderState::ProgramData {
+            slot: 0,
+            upgrade_authority_address: Some(admin.pubkey()),
+        })
+        .unwrap();
+        data.resize(UpgradeableLoaderState::size_of_programdata_metadata(), 0);
+        data.extend_from_slice(&program);
+        svm.set_account(
+            client::program_data_address(),
+            Account {
+                lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+                data,
+                owner: bpf_loader_upgradeable::ID,
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .unwrap();
+
+        let mut harness = Self {
+            svm,
+            admin,
+            collection: Pubkey::default(),
+            config: Pubkey::default(),
+        };
+        harness.collection = harness.create_collection();
+        harness.config = client::config_pda(&harness.collection, 0).0;
+        harness
+    }
+
+    // Deploy and create pool 0 for the collection with `params`
+    pub fn new(params: ConfigParams) -> Self {
+        let mut harness = Self::deploy();
+        harness.create_pool(params);
+        harness
+    }
+
+    // Point the harness at pool 0 of another collection (a Token-2022 group or Core collection)
+    pub fn set_collection(&mut self, collection: Pubkey) {
+        self.collection = collection;
+        self.config = client::config_pda(&collection, 0).0;
+    }
+
+    // Create pool 0 for the current collection with `params`
+    pub fn create_pool(&mut self, params: ConfigParams) {
+        let admin = self.admin.insecure_clone();
+        let ix = self.initialize_config_ix(&admin.pubkey(), params);
+        self.send(&[ix], &[&admin]).expect("initialize_config");
+    }
+
+    pub fn initialize_config_ix(&self, admin: &Pubkey, params: ConfigParams) -> Instruction {
+        client::initialize_config(admin, &self.collection, 0, params, &spl_token::ID)
+    }
+
+    // Sign with `signers` (the first pays fees) and process the transaction
+    pub fn send(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> TransactionResult {
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&signers[0].pubkey()),
+            signers,
+            self.svm.latest_blockhash(),
+        );
+        let result = self.svm.send_transaction(transaction);
+        self.svm.expire_blockhash(); // Identical follow-up transactions must not be deduplicated
+        result
+    }
+
+    // A funded wallet
+    pub fn wallet(&mut self) -> Keypair {
+        let wallet = Keypair::new();
+        self.svm.airdrop(&wallet.pubkey(), AIRDROP).unwrap();
+        wallet
+    }
+
+    // A funded wallet with a user account in the pool
+    pub fn user(&mut self) -> Keypair {
+        let user = self.wallet();
+        let ix = client::initialize_user(&user.pubkey(), &self.config);
+        self.send(&[ix], &[&user]).expect("initialize_user");
+        user
+    }
+
+    // Create a master-edition NFT owned by `owner`, optionally in `collection`
+    fn create_nft(
+        &mut self,
+        owner: &Pubkey,
+        collection: Option<Pubkey>,
+        collection_details: Option<CollectionDetails>,
+        standard: TokenStandard,
+    ) -> Pubkey {
+        let admin = self.admin.insecure_clone();
+        let mint = Keypair::new();
+        let metadata = client::metadata_pda(&mint.pubkey()).0;
+        let edition = client::master_edition_pda(&mint.pubkey()).0;
+
+        let mut create = CreateV1Builder::new();
+        create
+            .metadata(metadata)
+            .master_edition(Some(edition))
+            .mint(mint.pubkey(), true)
+            .authority(admin.pubkey())
+            .payer(admin.pubkey())
+            .update_authority(admin.pubkey(), true)
+            .spl_token_program(Some(spl_token::ID))
+            .name("Staking Test".into())
+            .uri("https://example.com/nft.json".into())
+            .seller_fee_basis_points(0)
+            .token_standard(standard)
+            .print_supply(PrintSupply::Zero);
+        if let Some(key) = collection {
+            create.collection(Collection {
+                verified: false,
+                key,
+            });
+        }
+        if let Some(details) = collection_details {
+            create.collection_details(details);
+        }
+
+        let token = get_associated_token_address(owner, &mint.pubkey());
+        let mut mint_to = MintV1Builder::new();
+        mint_to
+            .token(token)
+            .token_owner(Some(*owner))
+            .metadata(metadata)
+            .master_edition(Some(edition))
+            .mint(mint.pubkey())
+            .authority(admin.pubkey())
+            .payer(admin.pubkey())
+            .spl_token_program(spl_token::ID)
+            .amount(1);
+        if standard == TokenStandard::ProgrammableNonFungible {
+            mint_to.token_record(Some(client::token_record_pda(&mint.pubkey(), &token).0));
+        }
+
+        self.send(
+            &[create.instruction(), mint_to.instruction()],
+            &[&admin, &mint],
+        )
+        .expect("create NFT");
+        mint.pubkey()
+    }
+
+    fn create_collection(&mut self) -> Pubkey {
+        let admin = self.admin.pubkey();
+        self.create_nft(
+            &admin,
+            None,
+            Some(CollectionDetails::V1 { size: 0 }),
+            TokenStandard::NonFungible,
+        )
+    }
+
+    // Mint an NFT to `owner` in `collection`, verified by the collection authority if `verify`
+    pub fn mint_nft(&mut self, owner: &Pubkey, collection: Pubkey, verify: bool) -> Pubkey {
+        self.mint_in_collection(owner, collection, verify, TokenStandard::NonFungible)
+    }
+
+    // Mint a verified programmable NFT of the pool's collection to `owner`
+    pub fn mint_pool_pnft(&mut self, owner: &Pubkey) -> Pubkey {
+        let collection = self.collection;
+        self.mint_in_collection(
+            owner,
+            collection,
+            true,
+            TokenStandard::ProgrammableNonFungible,
+        )
+    }
+
+    fn mint_in_collection(
+        &mut self,
+        owner: &Pubkey,
+        collection: Pubkey,
+        verify: bool,
+        standard: TokenStandard,
+    ) -> Pubkey {
+        let mint = self.create_nft(owner, Some(collection), None, standard);
+        if verify {
+            let admin = self.admin.insecure_clone();
+            let ix = VerifyCollectionV1Builder::new()
+                .authority(admin.pubkey())
+                .metadata(client::metadata_pda(&mint).0)
+                .collection_mint(collection)
+                .collection_metadata(Some(client::metadata_pda(&collection).0))
+                .collection_master_edition(Some(client::master_edition_pda(&collection).0))
+                .instruction();
+            self.send(&[ix], &[&admin]).expect("verify collection");
+        }
+        mint
+    }
+
+    // Mint a verified NFT of the pool's collection to `owner`
+    pub fn mint_pool_nft(&mut self, owner: &Pubkey) -> Pubkey {
+        let collection = self.collection;
+        self.mint_nft(owner, collection, true)
+    }
+
+    // Create a Core collection whose update authority is the admin
+    pub fn create_core_collection(&mut self) -> Pubkey {
+        self.core_collection_with(Vec::new())
+    }
+
+    // Create a Core collection with the BubblegumV2 plugin, which cNFTs are minted into
+    pub fn create_cnft_collection(&mut self) -> Pubkey {
+        self.core_collection_with(vec![PluginAuthorityPair {
+            plugin: Plugin::BubblegumV2(BubblegumV2 {}),
+            authority: None,
+        }])
+    }
+
+    fn core_collection_with(&mut self, plugins: Vec<PluginAuthorityPair>) -> Pubkey {
+        let admin = self.admin.insecure_clone();
+        let collection = Keypair::new();
+        let ix = CreateCollectionV2Builder::new()
+            .collection(collection.pubkey())
+            .update_authority(Some(admin.pubkey()))
+            .payer(admin.pubkey())
+            .name("Staking Test Collection".into())
+            .uri("https://example.com/collection.json".into())
+            .plugins(plugins)
+            .instruction();
+        self.send(&[ix], &[&admin, &collection])
+            .expect("create core collection");
+        collection.pubkey()
+    }
+
+    // Create a Core asset in `collection` owned by `owner`
+    pub fn mint_core_asset(&mut self, owner: &Pubkey, collection: &Pubkey) -> Pubkey {
+        let admin = self.admin.insecure_clone();
+        let asset = Keypair::new();
+        let ix = CreateV2Builder::new()
+            .asset(asset.pubkey())
+            .collection(Some(*collection))
+            .authority(Some(admin.pubkey())) // Collection update authority adds the asset
+            .payer(admin.pubkey())
+            .owner(Some(*owner))
+            .name("Staking Test".into())
+            .uri("https://example.com/nft.json".into())
+            .instruction();
+        self.send(&[ix], &[&admin, &asset])
+            .expect("create core asset");
+        asset.pubkey()
+    }
+
+    // Transfer a Core asset of `collection` from `owner` to `new_owner`
+    pub fn transfer_core_asset(
+        &mut self,
+        owner: &Keypair,
+        asset: &Pubkey,
+        collection: &Pubkey,
+        new_owner: &Pubkey,
+    ) -> TransactionResult {
+        let ix = TransferV1Builder::new()
+            .asset(*asset)
+            .collection(Some(*collection))
+            .payer(owner.pubkey())
+            .authority(Some(owner.pubkey()))
+            .new_owner(*new_owner)
+            .instruction();
+        self.send(&[ix], &[owner])
+    }
+
+    // Owner recorded in a Core asset
+    pub fn core_owner(&self, asset: &Pubkey) -> Pubkey {
+        let account = self.svm.get_account(asset).expect("core asset");
+        BaseAssetV1::from_bytes(&account.data).unwrap().owner
+    }
+
+    // Create an empty Bubblegum V2 tree, owned by the admin, for cNFTs of `collection`
+    pub fn create_tree(&mut self, collection: &Pubkey) -> CnftTree {
+        let admin = self.admin.insecure_clone();
+        let tree = Keypair::new();
+        let ixs = [
+            system_instruction::create_account(
+                &admin.pubkey(),
+                &tree.pubkey(),
+                self.svm
+                    .minimum_balance_for_rent_exemption(cnft::TREE_ACCOUNT_SIZE),
+                cnft::TREE_ACCOUNT_SIZE as u64,
+                &MPL_ACCOUNT_COMPRESSION_ID,
+            ),
+            CreateTreeConfigV2Builder::new()
+                .tree_config(client::tree_config_pda(&tree.pubkey()).0)
+                .merkle_tree(tree.pubkey())
+                .payer(admin.pubkey())
+                .tree_creator(Some(admin.pubkey()))
+                .max_depth(cnft::TREE_DEPTH)
+                .max_buffer_size(cnft::TREE_BUFFER)
+                .instruction(),
+        ];
+        self.send(&ixs, &[&admin, &tree]).expect("create tree");
+        CnftTree::new(tree.pubkey(), *collection)
+    }
+
+    // Mint a cNFT of the tree's collection to `owner`; returns its leaf index
+    pub fn mint_cnft(&mut self, tree: &mut CnftTree, owner: &Pubkey) -> u32 {
+        let admin = self.admin.insecure_clone();
+        let metadata = MetadataArgsV2 {
+            name: "Staking Test".into(),
+            symbol: "STK".into(),
+            uri: "https://example.com/nft.json".into(),
+            seller_fee_basis_points: 0,
+            primary_sale_happened: false,
+            is_mutable: true,
+            token_standard: Some(mpl_bubblegum::types::TokenStandard::NonFungible),
+            creators: Vec::new(),
+            collection: Some(tree.collection),
+        };
+        let ix = MintV2Builder::new()
+            .tree_config(client::tree_config_pda(&tree.address).0)
+            .payer(admin.pubkey())
+            .tree_creator_or_delegate(Some(admin.pubkey()))
+            .collection_authority(Some(admin.pubkey())) // Collection update authority adds the cNFT
+            .leaf_owner(*owner)
+            .merkle_tree(tree.address)
+            .core_collection(Some(tree.collection))
+            .mpl_core_cpi_signer(Some(cnft::MPL_CORE_CPI_SIGNER))
+            .metadata(metadata.clone())
+            .instruction();
+        self.send(&[ix], &[&admin]).expect("mint cnft");
+        tree.push(owner, &metadata)
+    }
+
+    // Root currently stored in the tree account
+    pub fn tree_root(&self, tree: &CnftTree) -> [u8; 32] {
+        let account = self.svm.get_account(&tree.address).expect("merkle tree");
+        cnft::onchain_root(&account.data)
+    }
+
+    // System instruction creating a Token-2022 mint sized for its `pointer` extension and
+    // funded for `data` too, which the group instruction reallocates into after InitializeMint2
+    fn token22_mint_account(
+        &self,
+        mint: &Pubkey,
+        pointer: ExtensionType,
+        data: ExtensionType,
+    ) -> Instruction {
+        let space = ExtensionType::try_calculate_account_len::<Mint2022>(&[pointer]).unwrap();
+        let funded =
+            ExtensionType::try_calculate_account_len::<Mint2022>(&[pointer, data]).unwrap();
+        system_instruction::create_account(
+            &self.admin.pubkey(),
+            mint,
+            self.svm.minimum_balance_for_rent_exemption(funded),
+            space as u64,
+            &token_2022::ID,
+        )
+    }
+
+    // Create a Token-2022 group mint, with the group stored on the mint itself
+    pub fn create_token22_group(&mut self) -> Pubkey {
+        let admin = self.admin.insecure_clone();
+        let group = Keypair::new();
+        let ixs = [
+            self.token22_mint_account(
+                &group.pubkey(),
+                ExtensionType::GroupPointer,
+                ExtensionType::TokenGroup,
+            ),
+            group_pointer::instruction::initialize(
+                &token_2022::ID,
+                &group.pubkey(),
+                Some(admin.pubkey()),
+                Some(group.pubkey()),
+            )
+            .unwrap(),
+            spl_token_2022::instruction::initialize_mint2(
+                &token_2022::ID,
+                &group.pubkey(),
+                &admin.pubkey(),
+                None,
+                0,
+            )
+            .unwrap(),
+            initialize_group(
+                &token_2022::ID,
+                &group.pubkey(),
+                &group.pubkey(),
+                &admin.pubkey(),
+                Some(admin.pubkey()),
+                100,
+            ),
+        ];
+        self.send(&ixs, &[&admin, &group])
+            .expect("create token group");
+        group.pubkey()
+    }
+
+    // Mint a 1-of-1 Token-2022 member of `group` to `owner`, freezable by `freeze_authority`
+    pub fn mint_token22_nft(
+        &mut self,
+        owner: &Pubkey,
+        group: &Pubkey,
+        freeze_authority: &Pubkey,
+    ) -> Pubkey {
+        let admin = self.admin.insecure_clone();
+        let mint = Keypair::new();
+        let ata =
+            get_associated_token_address_with_program_id(owner, &mint.pubkey(), &token_2022::ID);
+        let ixs = [
+            self.token22_mint_account(
+                &mint.pubkey(),
+                ExtensionType::GroupMemberPointer,
+                ExtensionType::TokenGroupMember,
+            ),
+            group_member_pointer::instruction::initialize(
+                &token_2022::ID,
+                &mint.pubkey(),
+                Some(admin.pubkey()),
+                Some(mint.pubkey()),
+            )
+            .unwrap(),
+            spl_token_2022::instruction::initialize_mint2(
+                &token_2022::ID,
+                &mint.pubkey(),
+                &admin.pubkey(),
+                Some(freeze_authority),
+                0,
+            )
+            .unwrap(),
+            initialize_member(
+                &token_2022::ID,
+                &mint.pubkey(),
+                &mint.pubkey(),
+                &admin.pubkey(),
+                group,
+                &admin.pubkey(),
+            ),
+            create_associated_token_account_idempotent(
+                &admin.pubkey(),
+                owner,
+                &mint.pubkey(),
+                &token_2022::ID,
+            ),
+            spl_token_2022::instruction::mint_to(
+                &token_2022::ID,
+                &mint.pubkey(),
+                &ata,
+                &admin.pubkey(),
+                &[],
+                1,
+            )
+            .unwrap(),
+        ];
+        self.send(&ixs, &[&admin, &mint]).expect("mint token22 nft");
+        mint.pubkey()
+    }
+
+    pub fn stake(&mut self, user: &Keypair, mint: &Pubkey) -> TransactionResult {
+        self.stake_in_tier(user, mint, 0)
+    }
+
+    // Stake in lock tier `tier`
+    pub fn stake_in_tier(&mut self, user: &Keypair, mint: &Pubkey, tier: u8) -> TransactionResult {
+        let ix = client::stake(&user.pubkey(), &self.config, mint, tier);
+        self.send(&[ix], &[user])
+    }
+
+    pub fn unstake(&mut self, user: &Keypair, mint: &Pubkey) -> TransactionResult {
+        let ix = client::unstake(&user.pubkey(), &self.config, mint);
+        self.send(&[ix], &[user])
+    }
+
+    pub fn stake_many(&mut self, user: &Keypair, mints: &[Pubkey]) -> TransactionResult {
+        let ix = client::stake_many(&user.pubkey(), &self.config, mints, 0);
+        self.send(&[ix], &[user])
+    }
+
+    pub fn unstake_many(&mut self, user: &Keypair, mints: &[Pubkey]) -> TransactionResult {
+        let ix = client::unstake_many(&user.pubkey(), &self.config, mints);
+        self.send(&[ix], &[user])
+    }
+
+    // Stake a legacy NFT into the pool vault (custody pools)
+    pub fn stake_vault(&mut self, user: &Keypair, mint: &Pubkey) -> TransactionResult {
+        let ix = client::stake_vault(&user.pubkey(), &self.config, mint, &spl_token::ID, 0);
+        self.send(&[ix], &[user])
+    }
+
+    pub fn unstake_vault(&mut self, user: &Keypair, mint: &Pubkey) -> TransactionResult {
+        let ix = client::unstake_vault(&user.pubkey(), &self.config, mint, &spl_token::ID);
+        self.send(&[ix], &[user])
+    }
+
+    // Stake a programmable NFT without a rule set
+    pub fn stake_pnft(&mut self, user: &Keypair, mint: &Pubkey) -> TransactionResult {
+        let ix = client::stake_pnft(&user.pubkey(), &self.config, mint, None, 0);
+        self.send(&[ix], &[user])
+    }
+
+    pub fn unstake_pnft(&mut self, user: &Keypair, mint: &Pubkey) -> TransactionResult {
+        let ix = client::unstake_pnft(&user.pubkey(), &self.config, mint, None);
+        self.send(&[ix], &[user])
+    }
+
+    // Token Metadata token record of a programmable NFT in `owner`'s ATA
+    pub fn token_record(&self, owner: &Pubkey, mint: &Pubkey) -> TokenRecord {
+        let token = get_associated_token_address(owner, mint);
+        let account = self
+            .svm
+            .get_account(&client::token_record_pda(mint, &token).0)
+            .expect("token record");
+        TokenRecord::from_bytes(&account.data).unwrap()
+    }
+
+    // Stake a Core asset of the pool's Core collection
+    pub fn stake_core(&mut self, user: &Keypair, asset: &Pubkey) -> TransactionResult {
+        let ix = client::stake_core(&user.pubkey(), &self.config, asset, &self.collection, 0);
+        self.send(&[ix], &[user])
+    }
+
+    pub fn unstake_core(&mut self, user: &Keypair, asset: &Pubkey) -> TransactionResult {
+        let ix = client::unstake_core(&user.pubkey(), &self.config, asset, &self.collection);
+        self.send(&[ix], &[user])
+    }
+
+    // Stake the cNFT at `index`, then mirror its delegation to the stake PDA and freeze
+    pub fn stake_cnft(
+        &mut self,
+        user: &Keypair,
+        tree: &mut CnftTree,
+        index: u32,
+    ) -> TransactionResult {
+        let ix = client::stake_cnft(
+            &user.pubkey(),
+            &self.config,
+            &tree.address,
+            tree.leaf(index),
+            &tree.proof(index),
+            0,
+        );
+        let result = self.send(&[ix], &[user]);
+        if result.is_ok() {
+            let stake_account = client::stake_account_pda(&tree.asset_id(index), &self.config).0;
+            tree.set_delegate(index, stake_account, true);
+        }
+        result
+    }
+
+    // Unstake the cNFT at `index`, then mirror the thaw and the delegate reverting to the owner
+    pub fn unstake_cnft(
+        &mut self,
+        user: &Keypair,
+        tree: &mut CnftTree,
+        index: u32,
+    ) -> TransactionResult {
+        let ix = client::unstake_cnft(
+            &user.pubkey(),
+            &self.config,
+            &tree.address,
+            tree.leaf(index),
+            &tree.proof(index),
+        );
+        let result = self.send(&[ix], &[user]);
+        if result.is_ok() {
+            tree.set_delegate(index, user.pubkey(), false);
+        }
+        result
+    }
+
+    pub fn stake_token22(&mut self, user: &Keypair, mint: &Pubkey) -> TransactionResult {
+        let ix = client::stake_token22(&user.pubkey(), &self.config, mint, 0);
+        self.send(&[ix], &[user])
+    }
+
+    pub fn unstake_token22(&mut self, user: &Keypair, mint: &Pubkey) -> TransactionResult {
+        let ix = client::unstake_token22(&user.pubkey(), &self.config, mint);
+        self.send(&[ix], &[user])
+    }
+
+    // Claim after creating the reward ATA, settling `settle` first
+    pub fn claim(&mut self, user: &Keypair, settle: &[Pubkey]) -> TransactionResult {
+        let reward_mint = client::rewards_mint_pda(&self.config).0;
+        let ixs = [
+            create_associated_token_account_idempotent(
+                &user.pubkey(),
+                &user.pubkey(),
+                &reward_mint,
+                &spl_token::ID,
+            ),
+            client::claim(&user.pubkey(), &self.config, &spl_token::ID, settle),
+        ];
+        self.send(&ixs, &[user])
+    }
+
+    // A 6-decimal fungible token with `supply` minted to the admin
+    pub fn create_token(&mut self, supply: u64) -> Pubkey {
+        self.create_token_with_decimals(6, supply)
+    }
+
+    // A token of `decimals` decimals with `supply` minted to the admin
+    pub fn create_token_with_decimals(&mut self, decimals: u8, supply: u64) -> Pubkey {
+        let admin = self.admin.insecure_clone();
+        let mint = Keypair::new();
+        let ixs = [
+            system_instruction::create_account(
+                &admin.pubkey(),
+                &mint.pubkey(),
+                self.svm
+                    .minimum_balance_for_rent_exemption(spl_token::state::Mint::LEN),
+                spl_token::state::Mint::LEN as u64,
+                &spl_token::ID,
+            ),
+            spl_token::instruction::initialize_mint2(
+                &spl_token::ID,
+                &mint.pubkey(),
+                &admin.pubkey(),
+                None,
+                decimals,
+            )
+            .unwrap(),
+            create_associated_token_account_idempotent(
+                &admin.pubkey(),
+                &admin.pubkey(),
+                &mint.pubkey(),
+                &spl_token::ID,
+            ),
+            spl_token::instruction::mint_to(
+                &spl_token::ID,
+                &mint.pubkey(),
+                &get_associated_token_address(&admin.pubkey(), &mint.pubkey()),
+                &admin.pubkey(),
+                &[],
+                supply,
+            )
+            .unwrap(),
+        ];
+        self.send(&ixs, &[&admin, &mint]).expect("create token");
+        mint.pubkey()
+    }
+
+    // Switch the pool to paying claims from a treasury of `mint`, as admin
+    pub fn initialize_treasury(&mut self, mint: &Pubkey) -> TransactionResult {
+        let admin = self.admin.insecure_clone();
+        let ix = client::initialize_treasury(&admin.pubkey(), &self.config, mint, &spl_token::ID);
+        self.send(&[ix], &[&admin])
+    }
+
+    // Deposit `amount` of the treasury token from the admin's wallet
+    pub fn fund_treasury(&mut self, mint: &Pubkey, amount: u64) -> TransactionResult {
+        let admin = self.admin.insecure_clone();
+        let ix = client::fund_treasury(&admin.pubkey(), &self.config, mint, &spl_token::ID, amount);
+        self.send(&[ix], &[&admin])
+    }
+
+    // Claim from the treasury, creating the user's ATA for `mint` first
+    pub fn claim_treasury(
+        &mut self,
+        user: &Keypair,
+        mint: &Pubkey,
+        settle: &[Pubkey],
+    ) -> TransactionResult {
+        let ixs = [
+            create_associated_token_account_idempotent(
+                &user.pubkey(),
+                &user.pubkey(),
+                mint,
+                &spl_token::ID,
+            ),
+            client::claim_treasury(&user.pubkey(), &self.config, mint, &spl_token::ID, settle),
+        ];
+        self.send(&ixs, &[user])
+    }
+
+    // Set the pool's pause bitfield as admin
+    pub fn set_paused(&mut self, paused: u8) -> TransactionResult {
+        let admin = self.admin.insecure_clone();
+        let ix = Instruction {
+            program_id: client::ID,
+            accounts: nft_staking::accounts::SetPaused {
+                admin: admin.pubkey(),
+                config: self.config,
+                event_authority: client::event_authority_pda().0,
+                program: client::ID,
+            }
+            .to_account_metas(None),
+            data: nft_staking::instruction::SetPaused { paused }.data(),
+        };
+        self.send(&[ix], &[&admin])
+    }
+
+    // Toggle emergency mode as admin
+    pub fn set_emergency(&mut self, emergency: bool) -> TransactionResult {
+        let admin = self.admin.insecure_clone();
+        let ix = Instruction {
+            program_id: client::ID,
+            accounts: nft_staking::accounts::SetEmergency {
+                admin: admin.pubkey(),
+                config: self.config,
+                event_authority: client::event_authority_pda().0,
+                program: client::ID,
+            }
+            .to_account_metas(None),
+            data: nft_staking::instruction::SetEmergency { emergency }.data(),
+        };
+        self.send(&[ix], &[&admin])
+    }
+
+    // Replace the pool's emission schedule as admin
+    pub fn set_emission_schedule(&mut self, params: ScheduleParams) -> TransactionResult {
+        let admin = self.admin.insecure_clone();
+        let ix = client::set_emission_schedule(&admin.pubkey(), &self.config, params);
+        self.send(&[ix], &[&admin])
+    }
+
+    // Replace the pool's lock tiers as admin
+    pub fn set_lock_tiers(&mut self, tiers: Vec<LockTier>) -> TransactionResult {
+        let admin = self.admin.insecure_clone();
+        let ix = client::set_lock_tiers(&admin.pubkey(), &self.config, tiers);
+        self.send(&[ix], &[&admin])
+    }
+
+    // Current on-chain unix time
+    pub fn now(&self) -> i64 {
+        self.svm.get_sysvar::<Clock>().unix_timestamp
+    }
+
+    // Move the clock forward by `seconds`
+    pub fn warp(&mut self, seconds: i64) {
+        let mut clock: Clock = self.svm.get_sysvar();
+        clock.unix_timestamp += seconds;
+        clock.slot += 1;
+        self.svm.set_sysvar(&clock);
+    }
+
+    fn decode<T: AccountDeserialize>(&self, address: &Pubkey) -> Option<T> {
+        let account = self.svm.get_account(address)?;
+        client::decode(&account.data).ok()
+    }
+
+    // Simulate `get_pending_rewards` for `user` over the given staked mints
+    // Simulate `ix` with the admin paying fees; nothing is committed
+    pub fn simulate(&self, ix: Instruction) -> TransactionResult {
+        let transaction = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&self.admin.pubkey()), // Any funded wallet can pay; the user does not sign
+            &[&self.admin],
+            self.svm.latest_blockhash(),
+        );
+        self.svm
+            .simulate_transaction(transaction)
+            .map(|simulated| simulated.meta)
+    }
+
+    pub fn pending_rewards(&self, user: &Pubkey, stakes: &[Pubkey]) -> PendingRewards {
+        let meta = self
+            .simulate(client::get_pending_rewards(user, &self.config, stakes))
+            .expect("get_pending_rewards");
+        client::decode_pending_rewards(&meta.return_data.data).unwrap()
+    }
+
+    pub fn user_account(&self, user: &Pubkey) -> UserAccount {
+        self.decode(&client::user_account_pda(&self.config, user).0)
+            .expect("user account")
+    }
+
+    pub fn stake_config(&self) -> StakeConfig {
+        self.decode(&self.config).expect("config")
+    }
+
+    // Whether the NFT's stake record exists
+    pub fn is_staked(&self, mint: &Pubkey) -> bool {
+        self.svm
+            .get_account(&client::stake_account_pda(mint, &self.config).0)
+            .is_some_and(|account| account.lamports > 0)
+    }
+
+    // `owner`'s token account for `mint`
+    pub fn token_account(&self, owner: &Pubkey, mint: &Pubkey) -> TokenAccount {
+        let account = self
+            .svm
+            .get_account(&get_associated_token_address(owner, mint))
+            .expect("token account");
+        TokenAccount::unpack(&account.data).unwrap()
+    }
+
+    // A Token-2022 ATA, which carries extensions after the base account
+    pub fn token22_account(&self, owner: &Pubkey, mint: &Pubkey) -> TokenAccount2022 {
+        let address = get_associated_token_address_with_program_id(owner, mint, &token_2022::ID);
+        let account = self.svm.get_account(&address).expect("token account");
+        StateWithExtensions::<TokenAccount2022>::unpack(&account.data)
+            .unwrap()
+            .base
+    }
+
+    // `owner`'s reward token balance
+    pub fn rewards(&self, owner: &Pubkey) -> u64 {
+        let reward_mint = client::rewards_mint_pda(&self.config).0;
+        self.token_account(owner, &reward_mint).amount
+    }
+}
+
+// Assert the transaction failed with the program's `code`
+pub fn assert_error(result: TransactionResult, code: ErrorCode) {
+    let failed = result.expect_err("transaction should fail");
+    match failed.err {
+        TransactionError::InstructionError(_, InstructionError::Custom(actual)) => {
+            assert_eq!(actual, u32::from(code), "logs: {:#?}", failed.meta.logs)
+        }
+        other => panic!(
+            "expected {code:?}, got {other:?}; logs: {:#?}",
+            failed.meta.logs
+        ),
+    }
+}
+
+// Decode the `T` events a processed transaction emitted through `emit_cpi!`
+// Each is a self-CPI whose data is the event tag, the event discriminator and the Borsh body
+pub fn events<T: Event>(meta: &TransactionMetadata) -> Vec<T> {
+    meta.inner_instructions
+        .iter()
+        .flatten()
+        .filter_map(|inner| {
+            let data = inner.instruction.data.strip_prefix(EVENT_IX_TAG_LE)?;
+            let body = data.strip_prefix(T::DISCRIMINATOR)?;
+            T::try_from_slice(body).ok()
+        })
+        .collect()
+}
+
+// Assert the transaction failed for any reason
+pub fn assert_fails(result: TransactionResult) {
+    assert!(result.is_err(), "transaction should fail");
+}
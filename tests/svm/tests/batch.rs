@@ -0,0 +1,143 @@
+// Batch paths: stake_many / unstake_many move several NFTs atomically in one instruction
+
+use litesvm::types::TransactionResult;
+use nft_staking::error::ErrorCode;
+use nft_staking_client as client;
+use nft_staking_svm_tests::*;
+use solana_sdk::signature::Signer;
+
+// Assert the batch failed with `code` and the program logged the offending NFT's index
+fn assert_batch_error(result: TransactionResult, code: ErrorCode, log: &str) {
+    let failed = result.as_ref().expect_err("batch should fail");
+    assert!(
+        failed.meta.logs.iter().any(|line| line.contains(log)),
+        "missing {log:?} in logs: {:#?}",
+        failed.meta.logs
+    );
+    assert_error(result, code);
+}
+
+#[test]
+fn stake_many_freezes_every_nft() {
+    let mut harness = Harness::new(default_params());
+    let user = harness.user();
+    let mints = [
+        harness.mint_pool_nft(&user.pubkey()),
+        harness.mint_pool_nft(&user.pubkey()),
+    ];
+
+    harness.stake_many(&user, &mints).expect("stake_many");
+
+    for mint in &mints {
+        assert!(harness.is_staked(mint));
+        assert!(harness.token_account(&user.pubkey(), mint).is_frozen());
+    }
+    assert_eq!(harness.user_account(&user.pubkey()).amount_staked, 2);
+    assert_eq!(harness.stake_config().total_staked, 2);
+}
+
+#[test]
+fn stake_many_reverts_whole_batch_when_a_later_nft_fails() {
+    let mut harness = Harness::new(default_params());
+    let user = harness.user();
+    let good = harness.mint_pool_nft(&user.pubkey());
+    let admin = harness.admin.pubkey();
+    let other_collection = harness.mint_pool_nft(&admin); // Any mint that is not the pool's
+    let foreign = harness.mint_nft(&user.pubkey(), other_collection, false);
+
+    assert_batch_error(
+        harness.stake_many(&user, &[good, foreign]),
+        ErrorCode::CollectionMismatch,
+        "stake_many: NFT at index 1 failed",
+    );
+
+    // The first NFT was processed before the failure but nothing of it persists
+    assert!(!harness.is_staked(&good));
+    assert!(!harness.token_account(&user.pubkey(), &good).is_frozen());
+    assert_eq!(harness.user_account(&user.pubkey()).amount_staked, 0);
+    assert_eq!(harness.stake_config().total_staked, 0);
+}
+
+#[test]
+fn stake_many_counts_whole_batch_against_max_stake() {
+    let mut harness = Harness::new(default_params());
+    let user = harness.user();
+    let mints: Vec<_> = (0..=MAX_STAKE)
+        .map(|_| harness.mint_pool_nft(&user.pubkey()))
+        .collect();
+
+    assert_error(harness.stake_many(&user, &mints), ErrorCode::MaxStake);
+    assert_eq!(harness.user_account(&user.pubkey()).amount_staked, 0);
+}
+
+#[test]
+fn stake_many_accepts_prefunded_stake_account() {
+    let mut harness = Harness::new(default_params());
+    let user = harness.user();
+    let mint = harness.mint_pool_nft(&user.pubkey());
+    let stake_pda = client::stake_account_pda(&mint, &harness.config).0;
+    // Anyone can send lamports to the public PDA before it is created
+    harness.svm.airdrop(&stake_pda, 1_000).unwrap();
+
+    harness.stake_many(&user, &[mint]).expect("stake_many");
+
+    let account = harness.svm.get_account(&stake_pda).unwrap();
+    assert_eq!(account.owner, client::ID);
+    assert_eq!(account.data.len(), client::STAKE_ACCOUNT_SIZE);
+    assert!(
+        account.lamports
+            >= harness
+                .svm
+                .minimum_balance_for_rent_exemption(account.data.len())
+    );
+    assert_eq!(harness.user_account(&user.pubkey()).amount_staked, 1);
+}
+
+#[test]
+fn unstake_many_thaws_every_nft_and_credits_points() {
+    let mut harness = Harness::new(default_params());
+    let user = harness.user();
+    let mints = [
+        harness.mint_pool_nft(&user.pubkey()),
+        harness.mint_pool_nft(&user.pubkey()),
+    ];
+    harness.stake_many(&user, &mints).expect("stake_many");
+    harness.warp(FREEZE_PERIOD as i64);
+
+    harness.unstake_many(&user, &mints).expect("unstake_many");
+
+    for mint in &mints {
+        assert!(!harness.is_staked(mint));
+        assert!(!harness.token_account(&user.pubkey(), mint).is_frozen());
+    }
+    let periods = (FREEZE_PERIOD / POINTS_PERIOD) as u64;
+    let user_account = harness.user_account(&user.pubkey());
+    assert_eq!(user_account.amount_staked, 0);
+    assert_eq!(user_account.points, 2 * periods * POINTS_PER_STAKE as u64);
+    assert_eq!(harness.stake_config().total_staked, 0);
+}
+
+#[test]
+fn unstake_many_reverts_whole_batch_when_a_later_nft_fails() {
+    let mut harness = Harness::new(default_params());
+    let user = harness.user();
+    let other = harness.user();
+    let own = harness.mint_pool_nft(&user.pubkey());
+    let theirs = harness.mint_pool_nft(&other.pubkey());
+    harness.stake_many(&user, &[own]).expect("stake_many");
+    harness.stake_many(&other, &[theirs]).expect("stake_many");
+    harness.warp(FREEZE_PERIOD as i64);
+
+    assert_batch_error(
+        harness.unstake_many(&user, &[own, theirs]),
+        ErrorCode::WrongOwner,
+        "unstake_many: NFT at index 1 failed",
+    );
+
+    // The first NFT stays staked and frozen
+    assert!(harness.is_staked(&own));
+    assert!(harness.token_account(&user.pubkey(), &own).is_frozen());
+    let user_account = harness.user_account(&user.pubkey());
+    assert_eq!(user_account.amount_staked, 1);
+    assert_eq!(user_account.points, 0);
+}
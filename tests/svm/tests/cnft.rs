@@ -0,0 +1,103 @@
+// cNFT pools: leaves stay in the owner's tree slot, delegated to and frozen by the stake PDA
+// Leaf contents and proofs come from the harness's off-chain tree mirror
+
+use nft_staking::error::ErrorCode;
+use nft_staking_svm_tests::*;
+use solana_sdk::signature::Signer;
+
+// A pool over a fresh cNFT-enabled Core collection, with an empty tree for it
+fn cnft_pool(params: ConfigParams) -> (Harness, CnftTree) {
+    let mut harness = Harness::deploy();
+    let collection = harness.create_cnft_collection();
+    harness.set_collection(collection);
+    harness.create_pool(params);
+    let tree = harness.create_tree(&collection);
+    (harness, tree)
+}
+
+#[test]
+fn stake_cnft_delegates_and_freezes_leaf() {
+    let (mut harness, mut tree) = cnft_pool(default_params());
+    let user = harness.user();
+    let index = harness.mint_cnft(&mut tree, &user.pubkey());
+    assert_eq!(harness.tree_root(&tree), tree.root());
+
+    harness
+        .stake_cnft(&user, &mut tree, index)
+        .expect("stake_cnft");
+
+    assert!(harness.is_staked(&tree.asset_id(index)));
+    assert_eq!(harness.user_account(&user.pubkey()).amount_staked, 1);
+    // The on-chain leaf now names the stake PDA as delegate and carries the frozen flag
+    assert_eq!(harness.tree_root(&tree), tree.root());
+}
+
+#[test]
+fn unstake_cnft_thaws_and_credits_points() {
+    let (mut harness, mut tree) = cnft_pool(default_params());
+    let user = harness.user();
+    let index = harness.mint_cnft(&mut tree, &user.pubkey());
+    harness
+        .stake_cnft(&user, &mut tree, index)
+        .expect("stake_cnft");
+    harness.warp(FREEZE_PERIOD as i64);
+
+    harness
+        .unstake_cnft(&user, &mut tree, index)
+        .expect("unstake_cnft");
+
+    assert!(!harness.is_staked(&tree.asset_id(index)));
+    // Delegate is back to the owner and the leaf is thawed
+    assert_eq!(harness.tree_root(&tree), tree.root());
+    let user_account = harness.user_account(&user.pubkey());
+    assert_eq!(user_account.amount_staked, 0);
+    let periods = (FREEZE_PERIOD / POINTS_PERIOD) as u64;
+    assert_eq!(user_account.points, periods * POINTS_PER_STAKE as u64);
+}
+
+#[test]
+fn stake_cnft_rejects_leaf_of_other_collection() {
+    let (mut harness, _) = cnft_pool(default_params());
+    let user = harness.user();
+    let other_collection = harness.create_cnft_collection();
+    let mut other_tree = harness.create_tree(&other_collection);
+    let index = harness.mint_cnft(&mut other_tree, &user.pubkey());
+
+    // The pool's collection hash does not match the leaf, so the proof fails in Bubblegum
+    assert_fails(harness.stake_cnft(&user, &mut other_tree, index));
+    assert!(!harness.is_staked(&other_tree.asset_id(index)));
+    assert_eq!(harness.user_account(&user.pubkey()).amount_staked, 0);
+}
+
+#[test]
+fn unstake_cnft_requires_staker() {
+    let (mut harness, mut tree) = cnft_pool(default_params());
+    let owner = harness.user();
+    let other = harness.user();
+    let index = harness.mint_cnft(&mut tree, &owner.pubkey());
+    harness
+        .stake_cnft(&owner, &mut tree, index)
+        .expect("stake_cnft");
+    harness.warp(FREEZE_PERIOD as i64);
+
+    assert_error(
+        harness.unstake_cnft(&other, &mut tree, index),
+        ErrorCode::WrongOwner,
+    );
+    assert!(harness.is_staked(&tree.asset_id(index)));
+}
+
+#[test]
+fn stake_cnft_rejects_custody_pool() {
+    let (mut harness, mut tree) = cnft_pool(ConfigParams {
+        custody: true,
+        ..default_params()
+    });
+    let user = harness.user();
+    let index = harness.mint_cnft(&mut tree, &user.pubkey());
+
+    assert_error(
+        harness.stake_cnft(&user, &mut tree, index),
+        ErrorCode::WrongCustodyMode,
+    );
+}
@@ -0,0 +1,95 @@
+// Core pools: assets stay with the owner, frozen by a FreezeDelegate plugin the stake PDA controls
+
+use nft_staking::error::ErrorCode;
+use nft_staking_svm_tests::*;
+use solana_sdk::signature::Signer;
+
+// A pool over a fresh Core collection
+fn core_pool(params: ConfigParams) -> Harness {
+    let mut harness = Harness::deploy();
+    let collection = harness.create_core_collection();
+    harness.set_collection(collection);
+    harness.create_pool(params);
+    harness
+}
+
+#[test]
+fn stake_core_freezes_asset_in_place() {
+    let mut harness = core_pool(default_params());
+    let user = harness.user();
+    let other = harness.wallet();
+    let collection = harness.collection;
+    let asset = harness.mint_core_asset(&user.pubkey(), &collection);
+
+    harness.stake_core(&user, &asset).expect("stake_core");
+
+    assert_eq!(harness.core_owner(&asset), user.pubkey()); // Asset stays with the owner
+    assert!(harness.is_staked(&asset));
+    assert_eq!(harness.user_account(&user.pubkey()).amount_staked, 1);
+    // The FreezeDelegate plugin blocks transfers while staked
+    assert_fails(harness.transfer_core_asset(&user, &asset, &collection, &other.pubkey()));
+}
+
+#[test]
+fn unstake_core_thaws_and_credits_points() {
+    let mut harness = core_pool(default_params());
+    let user = harness.user();
+    let other = harness.wallet();
+    let collection = harness.collection;
+    let asset = harness.mint_core_asset(&user.pubkey(), &collection);
+    harness.stake_core(&user, &asset).expect("stake_core");
+    harness.warp(FREEZE_PERIOD as i64);
+
+    harness.unstake_core(&user, &asset).expect("unstake_core");
+
+    assert!(!harness.is_staked(&asset));
+    let user_account = harness.user_account(&user.pubkey());
+    assert_eq!(user_account.amount_staked, 0);
+    let periods = (FREEZE_PERIOD / POINTS_PERIOD) as u64;
+    assert_eq!(user_account.points, periods * POINTS_PER_STAKE as u64);
+    // Plugin removed, so the asset moves freely again
+    harness
+        .transfer_core_asset(&user, &asset, &collection, &other.pubkey())
+        .expect("transfer after unstake");
+    assert_eq!(harness.core_owner(&asset), other.pubkey());
+}
+
+#[test]
+fn stake_core_rejects_asset_of_other_collection() {
+    let mut harness = core_pool(default_params());
+    let user = harness.user();
+    let other_collection = harness.create_core_collection();
+    let asset = harness.mint_core_asset(&user.pubkey(), &other_collection);
+
+    assert_error(
+        harness.stake_core(&user, &asset),
+        ErrorCode::CollectionMismatch,
+    );
+}
+
+#[test]
+fn stake_core_requires_asset_owner() {
+    let mut harness = core_pool(default_params());
+    let owner = harness.user();
+    let user = harness.user();
+    let collection = harness.collection;
+    let asset = harness.mint_core_asset(&owner.pubkey(), &collection);
+
+    assert_error(harness.stake_core(&user, &asset), ErrorCode::WrongOwner);
+}
+
+#[test]
+fn stake_core_rejects_custody_pool() {
+    let mut harness = core_pool(ConfigParams {
+        custody: true,
+        ..default_params()
+    });
+    let user = harness.user();
+    let collection = harness.collection;
+    let asset = harness.mint_core_asset(&user.pubkey(), &collection);
+
+    assert_error(
+        harness.stake_core(&user, &asset),
+        ErrorCode::WrongCustodyMode,
+    );
+}
@@ -0,0 +1,186 @@
+// Emission pools: a points-per-second rate, optionally scheduled, split pro rata among staked NFTs
+
+use nft_staking::error::ErrorCode;
+use nft_staking_client as client;
+use nft_staking_svm_tests::*;
+use solana_sdk::signature::Signer;
+
+const EMISSION_PER_SECOND: u64 = 10;
+
+fn emission_pool() -> Harness {
+    Harness::new(ConfigParams {
+        emission_per_second: EMISSION_PER_SECOND,
+        ..default_params()
+    })
+}
+
+#[test]
+fn emission_is_split_among_staked_nfts() {
+    let mut harness = emission_pool();
+    let alice = harness.user();
+    let bob = harness.user();
+    let alice_nft = harness.mint_pool_nft(&alice.pubkey());
+    let bob_nft = harness.mint_pool_nft(&bob.pubkey());
+
+    harness.stake(&alice, &alice_nft).expect("stake");
+    harness.warp(60); // Alice alone earns the full rate
+    harness.stake(&bob, &bob_nft).expect("stake");
+    harness.warp(60); // Both earn half
+
+    let alice_pending = harness.pending_rewards(&alice.pubkey(), &[alice_nft]);
+    let bob_pending = harness.pending_rewards(&bob.pubkey(), &[bob_nft]);
+    assert_eq!(alice_pending.pending, 60 * EMISSION_PER_SECOND * 3 / 2);
+    assert_eq!(bob_pending.pending, 60 * EMISSION_PER_SECOND / 2);
+    assert_eq!(harness.stake_config().total_staked, 2);
+}
+
+#[test]
+fn emission_total_does_not_grow_with_stakers() {
+    let mut harness = emission_pool();
+    let user = harness.user();
+    let mints: Vec<_> = (0..MAX_STAKE)
+        .map(|_| harness.mint_pool_nft(&user.pubkey()))
+        .collect();
+    for mint in &mints {
+        harness.stake(&user, mint).expect("stake");
+    }
+    harness.warp(FREEZE_PERIOD as i64);
+
+    for mint in &mints {
+        harness.unstake(&user, mint).expect("unstake");
+    }
+
+    assert_eq!(
+        harness.user_account(&user.pubkey()).points,
+        FREEZE_PERIOD as u64 * EMISSION_PER_SECOND
+    );
+    assert_eq!(harness.stake_config().total_staked, 0);
+}
+
+#[test]
+fn claim_settles_against_the_index() {
+    let mut harness = emission_pool();
+    let user = harness.user();
+    let mint = harness.mint_pool_nft(&user.pubkey());
+    harness.stake(&user, &mint).expect("stake");
+    harness.warp(30);
+
+    harness.claim(&user, &[mint]).expect("claim");
+    assert_eq!(
+        harness.rewards(&user.pubkey()),
+        30 * EMISSION_PER_SECOND * REWARD_PER_POINT
+    );
+
+    // Claimed emissions are not paid again on unstake
+    harness.warp(FREEZE_PERIOD as i64);
+    harness.unstake(&user, &mint).expect("unstake");
+    assert_eq!(
+        harness.user_account(&user.pubkey()).points,
+        FREEZE_PERIOD as u64 * EMISSION_PER_SECOND
+    );
+}
+
+#[test]
+fn flat_pool_rejects_emission_rate() {
+    let mut harness = Harness::new(default_params());
+    let admin = harness.admin.insecure_clone();
+    let ix = client::update_config(
+        &admin.pubkey(),
+        &harness.config,
+        client::UpdateConfigParams {
+            emission_per_second: Some(EMISSION_PER_SECOND),
+            ..Default::default()
+        },
+    );
+
+    assert_error(
+        harness.send(&[ix], &[&admin]),
+        ErrorCode::InvalidEmissionRate,
+    );
+}
+
+#[test]
+fn schedule_halves_the_rate_across_a_boundary() {
+    let mut harness = emission_pool();
+    let user = harness.user();
+    let mint = harness.mint_pool_nft(&user.pubkey());
+    harness.stake(&user, &mint).expect("stake");
+
+    let now = harness.now();
+    harness
+        .set_emission_schedule(ScheduleParams {
+            start_time: now,
+            end_time: None,
+            segments: vec![EmissionSegment {
+                start_ts: now + 40,
+                rate: EMISSION_PER_SECOND / 2,
+            }],
+        })
+        .expect("set schedule");
+    harness.warp(100); // 40s at the base rate, then 60s at half
+
+    let pending = harness.pending_rewards(&user.pubkey(), &[mint]);
+    assert_eq!(
+        pending.pending,
+        40 * EMISSION_PER_SECOND + 60 * EMISSION_PER_SECOND / 2
+    );
+}
+
+#[test]
+fn schedule_end_stops_emission() {
+    let mut harness = emission_pool();
+    let user = harness.user();
+    let mint = harness.mint_pool_nft(&user.pubkey());
+    harness.stake(&user, &mint).expect("stake");
+
+    let now = harness.now();
+    harness
+        .set_emission_schedule(ScheduleParams {
+            start_time: now,
+            end_time: Some(now + 50),
+            segments: Vec::new(),
+        })
+        .expect("set schedule");
+    harness.warp(FREEZE_PERIOD as i64);
+    harness.unstake(&user, &mint).expect("unstake");
+
+    assert_eq!(
+        harness.user_account(&user.pubkey()).points,
+        50 * EMISSION_PER_SECOND
+    );
+}
+
+#[test]
+fn schedule_rejects_unordered_segments() {
+    let mut harness = emission_pool();
+    let now = harness.now();
+    let result = harness.set_emission_schedule(ScheduleParams {
+        start_time: now,
+        end_time: None,
+        segments: vec![
+            EmissionSegment {
+                start_ts: now + 60,
+                rate: 5,
+            },
+            EmissionSegment {
+                start_ts: now + 60,
+                rate: 2,
+            },
+        ],
+    });
+
+    assert_error(result, ErrorCode::InvalidSchedule);
+}
+
+#[test]
+fn flat_pool_rejects_schedule() {
+    let mut harness = Harness::new(default_params());
+    let now = harness.now();
+    let result = harness.set_emission_schedule(ScheduleParams {
+        start_time: now,
+        end_time: Some(now + 60),
+        segments: Vec::new(),
+    });
+
+    assert_error(result, ErrorCode::InvalidSchedule);
+}
@@ -0,0 +1,66 @@
+// Events: every handler emits through a self-CPI that indexers read from the inner instructions
+
+use nft_staking::events::{Claimed, Staked};
+use nft_staking_svm_tests::*;
+use solana_sdk::signature::Signer;
+
+#[test]
+fn stake_emits_staked() {
+    let mut harness = Harness::new(default_params());
+    let user = harness.user();
+    let mint = harness.mint_pool_nft(&user.pubkey());
+
+    let meta = harness.stake(&user, &mint).expect("stake");
+
+    let staked = events::<Staked>(&meta);
+    assert_eq!(staked.len(), 1);
+    assert_eq!(staked[0].pool, harness.config);
+    assert_eq!(staked[0].owner, user.pubkey());
+    assert_eq!(staked[0].mint, mint);
+    assert_eq!(staked[0].tier, 0);
+    assert_eq!(staked[0].amount_staked, 1);
+    assert_eq!(staked[0].timestamp, harness.now());
+}
+
+#[test]
+fn stake_many_emits_one_staked_per_nft() {
+    let mut harness = Harness::new(default_params());
+    let user = harness.user();
+    let mints = [
+        harness.mint_pool_nft(&user.pubkey()),
+        harness.mint_pool_nft(&user.pubkey()),
+    ];
+
+    let meta = harness.stake_many(&user, &mints).expect("stake_many");
+
+    // In batch order, counted as if staked one by one
+    let staked = events::<Staked>(&meta);
+    let summary: Vec<_> = staked
+        .iter()
+        .map(|event| (event.mint, event.amount_staked))
+        .collect();
+    assert_eq!(summary, [(mints[0], 1), (mints[1], 2)]);
+}
+
+#[test]
+fn claim_emits_claimed() {
+    let mut harness = Harness::new(default_params());
+    let user = harness.user();
+    let mint = harness.mint_pool_nft(&user.pubkey());
+    harness.stake(&user, &mint).expect("stake");
+    harness.warp(2 * POINTS_PERIOD as i64);
+
+    let meta = harness.claim(&user, &[mint]).expect("claim");
+
+    let points = 2 * POINTS_PER_STAKE as u64;
+    let claimed = events::<Claimed>(&meta);
+    assert_eq!(claimed.len(), 1);
+    assert_eq!(claimed[0].pool, harness.config);
+    assert_eq!(claimed[0].owner, user.pubkey());
+    assert_eq!(claimed[0].settled, points);
+    assert_eq!(claimed[0].points, points);
+    assert_eq!(claimed[0].amount, points * REWARD_PER_POINT);
+    assert_eq!(claimed[0].remaining, 0);
+    // Only the claim's own event is decoded as `Claimed`
+    assert!(events::<Staked>(&meta).is_empty());
+}
@@ -0,0 +1,209 @@
+// Failure paths: every rejection the legacy stake, unstake, claim and pending-rewards flow can hit
+
+use anchor_spl::associated_token::{
+    get_associated_token_address,
+    spl_associated_token_account::instruction::create_associated_token_account_idempotent,
+};
+use anchor_spl::token::spl_token;
+use nft_staking::error::ErrorCode;
+use nft_staking::PAUSE_STAKE;
+use nft_staking_client as client;
+use nft_staking_svm_tests::*;
+use solana_sdk::signature::Signer;
+
+#[test]
+fn initialize_config_requires_upgrade_authority() {
+    let mut harness = Harness::deploy();
+    let impostor = harness.wallet();
+    let ix = harness.initialize_config_ix(&impostor.pubkey(), default_params());
+    assert_error(
+        harness.send(&[ix], &[&impostor]),
+        ErrorCode::NotUpgradeAuthority,
+    );
+}
+
+#[test]
+fn initialize_config_rejects_empty_points_period() {
+    let mut harness = Harness::deploy();
+    let admin = harness.admin.insecure_clone();
+    let params = ConfigParams {
+        points_period: 0,
+        ..default_params()
+    };
+    let ix = harness.initialize_config_ix(&admin.pubkey(), params);
+    assert_error(
+        harness.send(&[ix], &[&admin]),
+        ErrorCode::InvalidPointsPeriod,
+    );
+}
+
+#[test]
+fn initialize_config_rejects_zero_reward_rate() {
+    let mut harness = Harness::deploy();
+    let admin = harness.admin.insecure_clone();
+    let params = ConfigParams {
+        reward_per_point: 0,
+        ..default_params()
+    };
+    let ix = harness.initialize_config_ix(&admin.pubkey(), params);
+    assert_error(harness.send(&[ix], &[&admin]), ErrorCode::InvalidRewardRate);
+}
+
+#[test]
+fn stake_rejects_other_collection() {
+    let mut harness = Harness::new(default_params());
+    let user = harness.user();
+    let admin = harness.admin.pubkey();
+    let other_collection = harness.mint_pool_nft(&admin); // Any mint that is not the pool's
+    let mint = harness.mint_nft(&user.pubkey(), other_collection, false);
+
+    assert_error(harness.stake(&user, &mint), ErrorCode::CollectionMismatch);
+}
+
+#[test]
+fn stake_rejects_unverified_collection() {
+    let mut harness = Harness::new(default_params());
+    let user = harness.user();
+    let collection = harness.collection;
+    let mint = harness.mint_nft(&user.pubkey(), collection, false);
+
+    assert_error(
+        harness.stake(&user, &mint),
+        ErrorCode::CollectionNotVerified,
+    );
+}
+
+#[test]
+fn stake_respects_max_stake() {
+    let mut harness = Harness::new(default_params());
+    let user = harness.user();
+    for _ in 0..MAX_STAKE {
+        let mint = harness.mint_pool_nft(&user.pubkey());
+        harness.stake(&user, &mint).expect("stake");
+    }
+    let mint = harness.mint_pool_nft(&user.pubkey());
+
+    assert_error(harness.stake(&user, &mint), ErrorCode::MaxStake);
+}
+
+#[test]
+fn stake_requires_user_account() {
+    let mut harness = Harness::new(default_params());
+    let user = harness.wallet(); // No `initialize_user`
+    let mint = harness.mint_pool_nft(&user.pubkey());
+
+    assert_fails(harness.stake(&user, &mint));
+}
+
+#[test]
+fn stake_twice_fails() {
+    let mut harness = Harness::new(default_params());
+    let user = harness.user();
+    let mint = harness.mint_pool_nft(&user.pubkey());
+    harness.stake(&user, &mint).expect("stake");
+
+    assert_fails(harness.stake(&user, &mint)); // Stake record already exists
+}
+
+#[test]
+fn stake_rejected_while_paused() {
+    let mut harness = Harness::new(default_params());
+    let user = harness.user();
+    let mint = harness.mint_pool_nft(&user.pubkey());
+    harness.set_paused(PAUSE_STAKE).expect("set_paused");
+
+    assert_error(harness.stake(&user, &mint), ErrorCode::PoolPaused);
+}
+
+#[test]
+fn staked_nft_cannot_be_transferred() {
+    let mut harness = Harness::new(default_params());
+    let user = harness.user();
+    let receiver = harness.wallet();
+    let mint = harness.mint_pool_nft(&user.pubkey());
+    harness.stake(&user, &mint).expect("stake");
+
+    let ixs = [
+        create_associated_token_account_idempotent(
+            &user.pubkey(),
+            &receiver.pubkey(),
+            &mint,
+            &spl_token::ID,
+        ),
+        spl_token::instruction::transfer(
+            &spl_token::ID,
+            &get_associated_token_address(&user.pubkey(), &mint),
+            &get_associated_token_address(&receiver.pubkey(), &mint),
+            &user.pubkey(),
+            &[],
+            1,
+        )
+        .unwrap(),
+    ];
+    assert_fails(harness.send(&ixs, &[&user])); // Token account is frozen
+}
+
+#[test]
+fn unstake_before_freeze_period_fails() {
+    let mut harness = Harness::new(default_params());
+    let user = harness.user();
+    let mint = harness.mint_pool_nft(&user.pubkey());
+    harness.stake(&user, &mint).expect("stake");
+    harness.warp(FREEZE_PERIOD as i64 - 1);
+
+    assert_error(harness.unstake(&user, &mint), ErrorCode::TimeNotElapsed);
+}
+
+#[test]
+fn unstake_by_other_wallet_fails() {
+    let mut harness = Harness::new(default_params());
+    let user = harness.user();
+    let thief = harness.user();
+    let mint = harness.mint_pool_nft(&user.pubkey());
+    harness.stake(&user, &mint).expect("stake");
+    harness.warp(FREEZE_PERIOD as i64);
+
+    assert_fails(harness.unstake(&thief, &mint));
+    assert!(harness.is_staked(&mint));
+}
+
+#[test]
+fn unstake_unstaked_nft_fails() {
+    let mut harness = Harness::new(default_params());
+    let user = harness.user();
+    let mint = harness.mint_pool_nft(&user.pubkey());
+
+    assert_fails(harness.unstake(&user, &mint)); // No stake record
+}
+
+#[test]
+fn claim_without_points_fails() {
+    let mut harness = Harness::new(default_params());
+    let user = harness.user();
+
+    assert_error(harness.claim(&user, &[]), ErrorCode::NoPointsToClaim);
+}
+
+#[test]
+fn claim_rejects_other_users_stake_accounts() {
+    let mut harness = Harness::new(default_params());
+    let user = harness.user();
+    let other = harness.user();
+    let mint = harness.mint_pool_nft(&other.pubkey());
+    harness.stake(&other, &mint).expect("stake");
+    harness.warp(POINTS_PERIOD as i64);
+
+    assert_error(harness.claim(&user, &[mint]), ErrorCode::WrongOwner);
+}
+
+#[test]
+fn pending_rewards_rejects_duplicate_stake_accounts() {
+    let mut harness = Harness::new(default_params());
+    let user = harness.user();
+    let mint = harness.mint_pool_nft(&user.pubkey());
+    harness.stake(&user, &mint).expect("stake");
+    harness.warp(POINTS_PERIOD as i64);
+
+    let ix = client::get_pending_rewards(&user.pubkey(), &harness.config, &[mint, mint]);
+    assert_error(harness.simulate(ix), ErrorCode::DuplicateStakeAccount);
+}
@@ -0,0 +1,88 @@
+// Lock tiers: stakers opt into a longer lock for a reward multiplier
+
+use nft_staking::constants::MULTIPLIER_BASE;
+use nft_staking::error::ErrorCode;
+use nft_staking_svm_tests::*;
+use solana_sdk::signature::Signer;
+
+const LOCK_PERIOD: u32 = 600;
+
+// One tier locking for LOCK_PERIOD at `multiplier` x
+fn tier(multiplier: u16) -> Vec<LockTier> {
+    vec![LockTier {
+        lock_period: LOCK_PERIOD,
+        multiplier_bps: multiplier * MULTIPLIER_BASE,
+    }]
+}
+
+#[test]
+fn tier_lock_outlasts_freeze_period() {
+    let mut harness = Harness::new(default_params());
+    harness.set_lock_tiers(tier(2)).expect("set tiers");
+    let user = harness.user();
+    let mint = harness.mint_pool_nft(&user.pubkey());
+    harness.stake_in_tier(&user, &mint, 1).expect("stake");
+
+    harness.warp(FREEZE_PERIOD as i64);
+    assert_error(harness.unstake(&user, &mint), ErrorCode::TimeNotElapsed);
+
+    harness.warp((LOCK_PERIOD - FREEZE_PERIOD) as i64);
+    harness.unstake(&user, &mint).expect("unstake");
+
+    // Flat points are doubled by the tier multiplier
+    let periods = (LOCK_PERIOD / POINTS_PERIOD) as u64;
+    assert_eq!(
+        harness.user_account(&user.pubkey()).points,
+        periods * POINTS_PER_STAKE as u64 * 2
+    );
+}
+
+#[test]
+fn multiplier_weights_emission_share() {
+    let mut harness = Harness::new(ConfigParams {
+        emission_per_second: 10,
+        ..default_params()
+    });
+    harness.set_lock_tiers(tier(3)).expect("set tiers");
+    let alice = harness.user();
+    let bob = harness.user();
+    let alice_nft = harness.mint_pool_nft(&alice.pubkey());
+    let bob_nft = harness.mint_pool_nft(&bob.pubkey());
+
+    harness.stake(&alice, &alice_nft).expect("stake");
+    harness.stake_in_tier(&bob, &bob_nft, 1).expect("stake");
+    harness.warp(60);
+
+    // 600 points split 1:3 by weight
+    let alice_pending = harness.pending_rewards(&alice.pubkey(), &[alice_nft]);
+    let bob_pending = harness.pending_rewards(&bob.pubkey(), &[bob_nft]);
+    assert_eq!(alice_pending.pending, 150);
+    assert_eq!(bob_pending.pending, 450);
+    assert_eq!(
+        harness.stake_config().total_weight,
+        4 * MULTIPLIER_BASE as u64
+    );
+}
+
+#[test]
+fn unknown_tier_is_rejected() {
+    let mut harness = Harness::new(default_params());
+    let user = harness.user();
+    let mint = harness.mint_pool_nft(&user.pubkey());
+
+    assert_error(
+        harness.stake_in_tier(&user, &mint, 1),
+        ErrorCode::InvalidLockTier,
+    );
+}
+
+#[test]
+fn tier_below_1x_is_rejected() {
+    let mut harness = Harness::new(default_params());
+    let result = harness.set_lock_tiers(vec![LockTier {
+        lock_period: LOCK_PERIOD,
+        multiplier_bps: MULTIPLIER_BASE / 2,
+    }]);
+
+    assert_error(result, ErrorCode::InvalidLockTier);
+}
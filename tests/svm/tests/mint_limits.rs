@@ -0,0 +1,62 @@
+// Reward mint limits: a lifetime supply cap and a per-window mint limit
+
+use nft_staking::constants::MINT_WINDOW;
+use nft_staking::error::ErrorCode;
+use nft_staking_svm_tests::*;
+use solana_sdk::signature::Signer;
+
+// Points a single NFT earns in `periods` whole periods
+fn points(periods: u64) -> u64 {
+    periods * POINTS_PER_STAKE as u64
+}
+
+#[test]
+fn daily_limit_queues_excess_points() {
+    let mut harness = Harness::new(ConfigParams {
+        daily_mint_limit: points(10) * REWARD_PER_POINT,
+        ..default_params()
+    });
+    let user = harness.user();
+    let mint = harness.mint_pool_nft(&user.pubkey());
+    harness.stake(&user, &mint).expect("stake");
+    harness.warp(20 * POINTS_PERIOD as i64);
+
+    // Only half of the earned points fit in today's limit; the rest stay queued
+    harness.claim(&user, &[mint]).expect("claim");
+    assert_eq!(
+        harness.rewards(&user.pubkey()),
+        points(10) * REWARD_PER_POINT
+    );
+    assert_eq!(harness.user_account(&user.pubkey()).points, points(10));
+    assert_error(harness.claim(&user, &[]), ErrorCode::MintLimitReached);
+
+    // The next window pays the queued points
+    harness.warp(MINT_WINDOW);
+    harness.claim(&user, &[]).expect("claim");
+    assert_eq!(
+        harness.rewards(&user.pubkey()),
+        points(20) * REWARD_PER_POINT
+    );
+    assert_eq!(harness.user_account(&user.pubkey()).points, 0);
+}
+
+#[test]
+fn supply_cap_is_never_exceeded() {
+    let mut harness = Harness::new(ConfigParams {
+        max_supply: points(5) * REWARD_PER_POINT,
+        ..default_params()
+    });
+    let user = harness.user();
+    let mint = harness.mint_pool_nft(&user.pubkey());
+    harness.stake(&user, &mint).expect("stake");
+    harness.warp(20 * POINTS_PERIOD as i64);
+
+    harness.claim(&user, &[mint]).expect("claim");
+    let config = harness.stake_config();
+    assert_eq!(config.total_minted, config.max_supply);
+    assert_eq!(harness.user_account(&user.pubkey()).points, points(15));
+
+    // Unlike the daily limit, the cap does not reset
+    harness.warp(MINT_WINDOW);
+    assert_error(harness.claim(&user, &[]), ErrorCode::MintLimitReached);
+}
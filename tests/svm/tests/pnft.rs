@@ -0,0 +1,71 @@
+// pNFT pools: programmable NFTs stay in the owner's wallet, locked by a staking delegate held by the stake PDA
+
+use anchor_spl::metadata::mpl_token_metadata::types::{TokenDelegateRole, TokenState};
+use nft_staking::error::ErrorCode;
+use nft_staking_client as client;
+use nft_staking_svm_tests::*;
+use solana_sdk::signature::Signer;
+
+#[test]
+fn stake_pnft_locks_token_in_place() {
+    let mut harness = Harness::new(default_params());
+    let user = harness.user();
+    let mint = harness.mint_pool_pnft(&user.pubkey());
+
+    harness.stake_pnft(&user, &mint).expect("stake_pnft");
+
+    assert!(harness.is_staked(&mint));
+    assert_eq!(harness.token_account(&user.pubkey(), &mint).amount, 1); // pNFT stays with the owner
+    let record = harness.token_record(&user.pubkey(), &mint);
+    assert_eq!(record.state, TokenState::Locked);
+    assert_eq!(
+        record.delegate,
+        Some(client::stake_account_pda(&mint, &harness.config).0)
+    );
+    assert_eq!(record.delegate_role, Some(TokenDelegateRole::Staking));
+    assert_eq!(harness.user_account(&user.pubkey()).amount_staked, 1);
+}
+
+#[test]
+fn unstake_pnft_unlocks_and_credits_points() {
+    let mut harness = Harness::new(default_params());
+    let user = harness.user();
+    let mint = harness.mint_pool_pnft(&user.pubkey());
+    harness.stake_pnft(&user, &mint).expect("stake_pnft");
+    harness.warp(FREEZE_PERIOD as i64);
+
+    harness.unstake_pnft(&user, &mint).expect("unstake_pnft");
+
+    assert!(!harness.is_staked(&mint));
+    let record = harness.token_record(&user.pubkey(), &mint);
+    assert_eq!(record.state, TokenState::Unlocked);
+    assert_eq!(record.delegate, None); // Delegate revoked
+    let user_account = harness.user_account(&user.pubkey());
+    assert_eq!(user_account.amount_staked, 0);
+    let periods = (FREEZE_PERIOD / POINTS_PERIOD) as u64;
+    assert_eq!(user_account.points, periods * POINTS_PER_STAKE as u64);
+}
+
+#[test]
+fn stake_pnft_rejects_legacy_nft() {
+    let mut harness = Harness::new(default_params());
+    let user = harness.user();
+    let mint = harness.mint_pool_nft(&user.pubkey());
+
+    assert_error(harness.stake_pnft(&user, &mint), ErrorCode::NotProgrammable);
+}
+
+#[test]
+fn stake_pnft_rejects_custody_pool() {
+    let mut harness = Harness::new(ConfigParams {
+        custody: true,
+        ..default_params()
+    });
+    let user = harness.user();
+    let mint = harness.mint_pool_pnft(&user.pubkey());
+
+    assert_error(
+        harness.stake_pnft(&user, &mint),
+        ErrorCode::WrongCustodyMode,
+    );
+}
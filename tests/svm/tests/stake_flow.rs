@@ -0,0 +1,128 @@
+// Happy paths: stake → clock warp → unstake → claim against real Token Metadata accounts
+
+use nft_staking_svm_tests::*;
+use solana_sdk::{program_option::COption, signature::Signer};
+
+#[test]
+fn initialize_config_stores_params() {
+    let harness = Harness::new(default_params());
+    let config = harness.stake_config();
+
+    assert_eq!(config.collection, harness.collection);
+    assert_eq!(config.admin, harness.admin.pubkey());
+    assert_eq!(config.points_per_stake, POINTS_PER_STAKE);
+    assert_eq!(config.points_period, POINTS_PERIOD);
+    assert_eq!(config.max_stake, MAX_STAKE);
+    assert_eq!(config.freeze_period, FREEZE_PERIOD);
+    assert_eq!(config.reward_per_point, REWARD_PER_POINT);
+    assert!(!config.custody);
+}
+
+#[test]
+fn stake_freezes_nft_in_place() {
+    let mut harness = Harness::new(default_params());
+    let user = harness.user();
+    let mint = harness.mint_pool_nft(&user.pubkey());
+
+    harness.stake(&user, &mint).expect("stake");
+
+    let token = harness.token_account(&user.pubkey(), &mint);
+    assert_eq!(token.amount, 1); // NFT stays in the user's wallet
+    assert!(token.is_frozen());
+    assert_eq!(
+        token.delegate,
+        COption::Some(nft_staking_client::stake_account_pda(&mint, &harness.config).0)
+    );
+    assert!(harness.is_staked(&mint));
+    assert_eq!(harness.user_account(&user.pubkey()).amount_staked, 1);
+}
+
+#[test]
+fn stake_wait_unstake_claim() {
+    let mut harness = Harness::new(default_params());
+    let user = harness.user();
+    let mint = harness.mint_pool_nft(&user.pubkey());
+
+    harness.stake(&user, &mint).expect("stake");
+    // Past the freeze period, plus half a period that must not count
+    harness.warp(FREEZE_PERIOD as i64 + POINTS_PERIOD as i64 / 2);
+    harness.unstake(&user, &mint).expect("unstake");
+
+    let periods = (FREEZE_PERIOD / POINTS_PERIOD) as u64;
+    let expected = periods * POINTS_PER_STAKE as u64;
+    let token = harness.token_account(&user.pubkey(), &mint);
+    assert!(!token.is_frozen());
+    assert!(token.delegate.is_none());
+    assert!(!harness.is_staked(&mint));
+    let user_account = harness.user_account(&user.pubkey());
+    assert_eq!(user_account.amount_staked, 0);
+    assert_eq!(user_account.points, expected);
+
+    harness.claim(&user, &[]).expect("claim");
+    assert_eq!(harness.rewards(&user.pubkey()), expected * REWARD_PER_POINT);
+    assert_eq!(harness.user_account(&user.pubkey()).points, 0);
+}
+
+#[test]
+fn claim_settles_still_staked_nfts() {
+    let mut harness = Harness::new(default_params());
+    let user = harness.user();
+    let mint = harness.mint_pool_nft(&user.pubkey());
+
+    harness.stake(&user, &mint).expect("stake");
+    harness.warp(3 * POINTS_PERIOD as i64);
+    harness.claim(&user, &[mint]).expect("claim");
+
+    assert_eq!(
+        harness.rewards(&user.pubkey()),
+        3 * POINTS_PER_STAKE as u64 * REWARD_PER_POINT
+    );
+    assert!(harness.is_staked(&mint)); // Claiming does not unstake
+    assert!(harness.token_account(&user.pubkey(), &mint).is_frozen());
+
+    // Points already claimed are not credited again on unstake
+    harness.warp(FREEZE_PERIOD as i64);
+    harness.unstake(&user, &mint).expect("unstake");
+    let periods = (FREEZE_PERIOD / POINTS_PERIOD) as u64;
+    assert_eq!(
+        harness.user_account(&user.pubkey()).points,
+        periods * POINTS_PER_STAKE as u64
+    );
+}
+
+#[test]
+fn pending_rewards_preview_claim() {
+    let mut harness = Harness::new(default_params());
+    let user = harness.user();
+    let first = harness.mint_pool_nft(&user.pubkey());
+    let second = harness.mint_pool_nft(&user.pubkey());
+    harness.stake(&user, &first).expect("stake");
+    harness.stake(&user, &second).expect("stake");
+    harness.warp(2 * POINTS_PERIOD as i64);
+
+    let per_nft = 2 * POINTS_PER_STAKE as u64;
+    let single = harness.pending_rewards(&user.pubkey(), &[first]);
+    assert_eq!(single.pending, per_nft);
+    let all = harness.pending_rewards(&user.pubkey(), &[first, second]);
+    assert_eq!(all.credited, 0);
+    assert_eq!(all.total, 2 * per_nft);
+    assert!(harness.is_staked(&first)); // Simulation leaves the checkpoints alone
+
+    harness.claim(&user, &[first, second]).expect("claim");
+    assert_eq!(all.amount, all.total * REWARD_PER_POINT);
+    assert_eq!(harness.rewards(&user.pubkey()), all.amount);
+}
+
+#[test]
+fn emergency_unstake_skips_freeze_period() {
+    let mut harness = Harness::new(default_params());
+    let user = harness.user();
+    let mint = harness.mint_pool_nft(&user.pubkey());
+    harness.stake(&user, &mint).expect("stake");
+
+    harness.set_emergency(true).expect("set_emergency");
+
+    harness.unstake(&user, &mint).expect("emergency unstake");
+    assert!(!harness.token_account(&user.pubkey(), &mint).is_frozen());
+    assert_eq!(harness.user_account(&user.pubkey()).points, 0); // Unsettled points are forfeited
+}
@@ -0,0 +1,180 @@
+// Token-2022 pools: group member NFTs frozen in place by the program's freeze PDA
+
+use anchor_spl::associated_token::spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use anchor_spl::token::spl_token;
+use anchor_spl::token_2022::{
+    self,
+    spl_token_2022::instruction::{set_authority, AuthorityType},
+};
+use nft_staking::error::ErrorCode;
+use nft_staking_client as client;
+use nft_staking_svm_tests::*;
+use solana_sdk::{pubkey::Pubkey, signature::Signer};
+
+// A pool over a fresh Token-2022 group
+fn token22_pool(params: ConfigParams) -> Harness {
+    let mut harness = Harness::deploy();
+    let group = harness.create_token22_group();
+    harness.set_collection(group);
+    harness.create_pool(params);
+    harness
+}
+
+// A group member owned by `owner` whose freeze authority is the program's freeze PDA
+fn pool_member(harness: &mut Harness, owner: &Pubkey) -> Pubkey {
+    let group = harness.collection;
+    harness.mint_token22_nft(owner, &group, &client::freeze_authority_pda().0)
+}
+
+#[test]
+fn stake_token22_freezes_member_in_place() {
+    let mut harness = token22_pool(default_params());
+    let user = harness.user();
+    let mint = pool_member(&mut harness, &user.pubkey());
+
+    harness.stake_token22(&user, &mint).expect("stake_token22");
+
+    let token = harness.token22_account(&user.pubkey(), &mint);
+    assert_eq!(token.amount, 1); // NFT stays in the user's wallet
+    assert!(token.is_frozen());
+    assert!(harness.is_staked(&mint));
+    assert_eq!(harness.user_account(&user.pubkey()).amount_staked, 1);
+}
+
+#[test]
+fn unstake_token22_thaws_and_credits_points() {
+    let mut harness = token22_pool(default_params());
+    let user = harness.user();
+    let mint = pool_member(&mut harness, &user.pubkey());
+    harness.stake_token22(&user, &mint).expect("stake_token22");
+    harness.warp(FREEZE_PERIOD as i64);
+
+    harness
+        .unstake_token22(&user, &mint)
+        .expect("unstake_token22");
+
+    assert!(!harness.token22_account(&user.pubkey(), &mint).is_frozen());
+    assert!(!harness.is_staked(&mint));
+    let user_account = harness.user_account(&user.pubkey());
+    assert_eq!(user_account.amount_staked, 0);
+    let periods = (FREEZE_PERIOD / POINTS_PERIOD) as u64;
+    assert_eq!(user_account.points, periods * POINTS_PER_STAKE as u64);
+}
+
+#[test]
+fn stake_token22_requires_program_freeze_authority() {
+    let mut harness = token22_pool(default_params());
+    let user = harness.user();
+    let (group, admin) = (harness.collection, harness.admin.pubkey());
+    let mint = harness.mint_token22_nft(&user.pubkey(), &group, &admin);
+
+    assert_error(
+        harness.stake_token22(&user, &mint),
+        ErrorCode::InvalidFreezeAuthority,
+    );
+}
+
+#[test]
+fn existing_member_stakes_after_handing_over_freeze_authority() {
+    let mut harness = token22_pool(default_params());
+    let user = harness.user();
+    let admin = harness.admin.insecure_clone();
+    let group = harness.collection;
+    // Minted before the pool existed, with the creator as freeze authority
+    let mint = harness.mint_token22_nft(&user.pubkey(), &group, &admin.pubkey());
+    let handover = set_authority(
+        &token_2022::ID,
+        &mint,
+        Some(&client::freeze_authority_pda().0),
+        AuthorityType::FreezeAccount,
+        &admin.pubkey(),
+        &[],
+    )
+    .unwrap();
+    harness.send(&[handover], &[&admin]).expect("set_authority");
+
+    harness.stake_token22(&user, &mint).expect("stake_token22");
+    assert!(harness.token22_account(&user.pubkey(), &mint).is_frozen());
+}
+
+#[test]
+fn member_stakes_in_any_pool_of_its_group_one_at_a_time() {
+    let mut harness = token22_pool(default_params());
+    let user = harness.user();
+    let mint = pool_member(&mut harness, &user.pubkey());
+    let first_pool = harness.config;
+    // A second pool over the same group
+    let admin = harness.admin.insecure_clone();
+    let ix = client::initialize_config(
+        &admin.pubkey(),
+        &harness.collection,
+        1,
+        default_params(),
+        &spl_token::ID,
+    );
+    harness.send(&[ix], &[&admin]).expect("initialize_config");
+    let second_pool = client::config_pda(&harness.collection, 1).0;
+    let ix = client::initialize_user(&user.pubkey(), &second_pool);
+    harness.send(&[ix], &[&user]).expect("initialize_user");
+
+    harness.stake_token22(&user, &mint).expect("stake_token22");
+    // Already frozen by the first pool's stake
+    harness.config = second_pool;
+    assert_fails(harness.stake_token22(&user, &mint));
+
+    harness.config = first_pool;
+    harness.warp(FREEZE_PERIOD as i64);
+    harness
+        .unstake_token22(&user, &mint)
+        .expect("unstake_token22");
+    harness.config = second_pool;
+    harness.stake_token22(&user, &mint).expect("stake_token22");
+    assert!(harness.is_staked(&mint));
+}
+
+#[test]
+fn stake_token22_rejects_member_of_other_group() {
+    let mut harness = token22_pool(default_params());
+    let user = harness.user();
+    let other_group = harness.create_token22_group();
+    let freeze_authority = client::freeze_authority_pda().0;
+    let mint = harness.mint_token22_nft(&user.pubkey(), &other_group, &freeze_authority);
+
+    assert_error(
+        harness.stake_token22(&user, &mint),
+        ErrorCode::CollectionMismatch,
+    );
+}
+
+#[test]
+fn stake_token22_requires_holding_the_nft() {
+    let mut harness = token22_pool(default_params());
+    let holder = harness.user();
+    let user = harness.user();
+    let mint = pool_member(&mut harness, &holder.pubkey());
+    // The user has an empty token account for the mint
+    let ix = create_associated_token_account_idempotent(
+        &user.pubkey(),
+        &user.pubkey(),
+        &mint,
+        &token_2022::ID,
+    );
+    harness.send(&[ix], &[&user]).expect("create ata");
+
+    assert_error(harness.stake_token22(&user, &mint), ErrorCode::NftNotHeld);
+}
+
+#[test]
+fn stake_token22_rejects_custody_pool() {
+    let mut harness = token22_pool(ConfigParams {
+        custody: true,
+        ..default_params()
+    });
+    let user = harness.user();
+    let mint = pool_member(&mut harness, &user.pubkey());
+
+    assert_error(
+        harness.stake_token22(&user, &mint),
+        ErrorCode::WrongCustodyMode,
+    );
+}
@@ -0,0 +1,117 @@
+// Treasury pools: claims pay out deposited tokens and stop at the vault balance
+
+use nft_staking::error::ErrorCode;
+use nft_staking_svm_tests::*;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+// A treasury pool paying `mint`, whose user staked one NFT for `periods` periods
+// and unstaked it (`periods` must cover FREEZE_PERIOD)
+fn staked_treasury_pool(periods: i64) -> (Harness, Keypair, Pubkey) {
+    let mut harness = Harness::new(default_params());
+    let mint = harness.create_token(1_000_000_000);
+    harness
+        .initialize_treasury(&mint)
+        .expect("initialize_treasury");
+
+    let user = harness.user();
+    let nft = harness.mint_pool_nft(&user.pubkey());
+    harness.stake(&user, &nft).expect("stake");
+    harness.warp(periods * POINTS_PERIOD as i64);
+    harness.unstake(&user, &nft).expect("unstake");
+    (harness, user, mint)
+}
+
+#[test]
+fn claim_treasury_pays_from_deposits() {
+    let (mut harness, user, mint) = staked_treasury_pool(3);
+    let points = 3 * POINTS_PER_STAKE as u64;
+    harness
+        .fund_treasury(&mint, points * REWARD_PER_POINT)
+        .expect("fund_treasury");
+
+    harness
+        .claim_treasury(&user, &mint, &[])
+        .expect("claim_treasury");
+
+    assert_eq!(
+        harness.token_account(&user.pubkey(), &mint).amount,
+        points * REWARD_PER_POINT
+    );
+    assert_eq!(harness.user_account(&user.pubkey()).points, 0);
+}
+
+#[test]
+fn claim_treasury_pays_partially_when_short() {
+    let (mut harness, user, mint) = staked_treasury_pool(3);
+    let points = 3 * POINTS_PER_STAKE as u64;
+    // Enough for 5 points, plus change that does not cover a sixth
+    harness
+        .fund_treasury(&mint, 5 * REWARD_PER_POINT + REWARD_PER_POINT / 2)
+        .expect("fund_treasury");
+
+    harness
+        .claim_treasury(&user, &mint, &[])
+        .expect("claim_treasury");
+    assert_eq!(
+        harness.token_account(&user.pubkey(), &mint).amount,
+        5 * REWARD_PER_POINT
+    );
+    assert_eq!(harness.user_account(&user.pubkey()).points, points - 5);
+
+    // The leftover change cannot pay a whole point
+    assert_error(
+        harness.claim_treasury(&user, &mint, &[]),
+        ErrorCode::TreasuryEmpty,
+    );
+
+    // Refilling pays out the rest
+    harness
+        .fund_treasury(&mint, points * REWARD_PER_POINT)
+        .expect("fund_treasury");
+    harness
+        .claim_treasury(&user, &mint, &[])
+        .expect("claim_treasury");
+    assert_eq!(harness.user_account(&user.pubkey()).points, 0);
+}
+
+#[test]
+fn treasury_pool_rejects_minting_claims() {
+    let (mut harness, user, _) = staked_treasury_pool(3);
+
+    assert_error(harness.claim(&user, &[]), ErrorCode::WrongRewardMode);
+}
+
+#[test]
+fn initialize_treasury_only_once() {
+    let mut harness = Harness::new(default_params());
+    let mint = harness.create_token(1);
+    harness
+        .initialize_treasury(&mint)
+        .expect("initialize_treasury");
+
+    let other = harness.create_token(1_000);
+    assert_error(
+        harness.initialize_treasury(&other),
+        ErrorCode::WrongRewardMode,
+    );
+}
+
+#[test]
+fn initialize_treasury_rejects_zero_decimal_mints() {
+    let mut harness = Harness::new(default_params());
+    let admin = harness.admin.pubkey();
+    let nft = harness.mint_pool_nft(&admin);
+    // Supply does not matter: a 0-decimal mint can become a 1-of-1 later
+    let empty = harness.create_token_with_decimals(0, 0);
+    let pair = harness.create_token_with_decimals(0, 2);
+
+    for mint in [nft, empty, pair] {
+        assert_error(
+            harness.initialize_treasury(&mint),
+            ErrorCode::InvalidTreasuryMint,
+        );
+    }
+}
@@ -0,0 +1,91 @@
+// Custody pools: NFTs move into a config-owned vault instead of being frozen in place
+
+use anchor_spl::associated_token::get_associated_token_address;
+use nft_staking::error::ErrorCode;
+use nft_staking_svm_tests::*;
+use solana_sdk::signature::Signer;
+
+fn custody_pool() -> Harness {
+    Harness::new(ConfigParams {
+        custody: true,
+        ..default_params()
+    })
+}
+
+#[test]
+fn stake_vault_moves_nft_into_pool_vault() {
+    let mut harness = custody_pool();
+    let user = harness.user();
+    let mint = harness.mint_pool_nft(&user.pubkey());
+
+    harness.stake_vault(&user, &mint).expect("stake_vault");
+
+    let token = harness.token_account(&user.pubkey(), &mint);
+    assert_eq!(token.amount, 0);
+    assert!(!token.is_frozen());
+    assert_eq!(harness.token_account(&harness.config, &mint).amount, 1);
+    assert!(harness.is_staked(&mint));
+    assert_eq!(harness.user_account(&user.pubkey()).amount_staked, 1);
+}
+
+#[test]
+fn unstake_vault_returns_nft_and_closes_vault() {
+    let mut harness = custody_pool();
+    let user = harness.user();
+    let mint = harness.mint_pool_nft(&user.pubkey());
+    harness.stake_vault(&user, &mint).expect("stake_vault");
+    harness.warp(FREEZE_PERIOD as i64);
+
+    harness.unstake_vault(&user, &mint).expect("unstake_vault");
+
+    assert_eq!(harness.token_account(&user.pubkey(), &mint).amount, 1);
+    let vault = get_associated_token_address(&harness.config, &mint);
+    assert!(harness
+        .svm
+        .get_account(&vault)
+        .is_none_or(|account| account.lamports == 0));
+    assert!(!harness.is_staked(&mint));
+    let user_account = harness.user_account(&user.pubkey());
+    assert_eq!(user_account.amount_staked, 0);
+    let periods = (FREEZE_PERIOD / POINTS_PERIOD) as u64;
+    assert_eq!(user_account.points, periods * POINTS_PER_STAKE as u64);
+}
+
+#[test]
+fn unstake_vault_before_freeze_period_fails() {
+    let mut harness = custody_pool();
+    let user = harness.user();
+    let mint = harness.mint_pool_nft(&user.pubkey());
+    harness.stake_vault(&user, &mint).expect("stake_vault");
+
+    assert_error(
+        harness.unstake_vault(&user, &mint),
+        ErrorCode::TimeNotElapsed,
+    );
+}
+
+#[test]
+fn stake_vault_rejects_freeze_in_place_pool() {
+    let mut harness = Harness::new(default_params());
+    let user = harness.user();
+    let mint = harness.mint_pool_nft(&user.pubkey());
+
+    assert_error(
+        harness.stake_vault(&user, &mint),
+        ErrorCode::WrongCustodyMode,
+    );
+}
+
+#[test]
+fn freeze_in_place_stake_rejects_custody_pool() {
+    let mut harness = custody_pool();
+    let user = harness.user();
+    let mint = harness.mint_pool_nft(&user.pubkey());
+
+    assert_error(harness.stake(&user, &mint), ErrorCode::WrongCustodyMode);
+    let mints = [mint];
+    assert_error(
+        harness.stake_many(&user, &mints),
+        ErrorCode::WrongCustodyMode,
+    );
+}
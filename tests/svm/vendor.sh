@@ -0,0 +1,9 @@
+#!/usr/bin/env sh
+# Vendor this crate's dependencies (LiteSVM and the validator runtime it pulls in)
+# into vendor/ and point cargo at them, so the suite builds without the crates registry
+# Run once on a machine with registry access, then copy tests/svm to the offline one
+set -e
+cd "$(dirname "$0")"
+cargo generate-lockfile
+mkdir -p .cargo
+cargo vendor --locked --versioned-dirs vendor >.cargo/config.toml